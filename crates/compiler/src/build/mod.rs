@@ -111,9 +111,10 @@ impl Compiler {
       // return Err(CompilationError::GenericError(error_messages.join(", ")));
     }
 
-    // Topo sort the module graph
+    // Topo sort the module graph and mark the async modules
     let mut module_graph = self.context.module_graph.write();
     module_graph.update_execution_order_for_modules();
+    module_graph.update_async_modules();
 
     self.context.plugin_driver.build_end(&self.context)
   }
@@ -384,7 +385,12 @@ impl Compiler {
           kind: resolve_param.kind.clone(),
           order,
         },
-      ).expect("failed to add edge to the module graph, the endpoint modules of the edge should be in the graph")
+      ).expect("failed to add edge to the module graph, the endpoint modules of the edge should be in the graph");
+
+      // the web worker is bundled as a separate entry, see [ModuleGraph::add_worker_entry]
+      if resolve_param.kind.is_worker() {
+        module_graph.add_worker_entry(&module_id);
+      }
     }
   }
 
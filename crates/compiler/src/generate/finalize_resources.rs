@@ -2,6 +2,8 @@ use std::sync::Arc;
 
 use farmfe_core::context::CompilationContext;
 
+use super::{hash_resources::hash_resources, manifest::emit_manifest};
+
 pub fn finalize_resources(context: &Arc<CompilationContext>) -> farmfe_core::error::Result<()> {
   {
     let mut resources_map = context.resources_map.lock();
@@ -9,6 +11,10 @@ pub fn finalize_resources(context: &Arc<CompilationContext>) -> farmfe_core::err
     context
       .plugin_driver
       .finalize_resources(&mut resources_map, context)?;
+
+    // all references between the resources are resolved after the finalize_resources hook
+    hash_resources(&mut resources_map, context);
+    emit_manifest(&mut resources_map, context);
   }
 
   Ok(())
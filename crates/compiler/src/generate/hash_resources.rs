@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use farmfe_core::{
+  context::CompilationContext,
+  hashbrown::HashMap,
+  petgraph::{algo::tarjan_scc, graph::DiGraph},
+  resource::{Resource, ResourceOrigin, ResourceType},
+};
+use farmfe_toolkit::{fs::content_hash_placeholder, hash::sha256};
+
+/// Replace the content hash placeholders in the names of the resources generated from the resource pots, see [content_hash_placeholder].
+/// The hashes are computed after all references between the resources are resolved (the dynamic resources map, the library chunks,
+/// the html injection, etc.), so the hash of a resource changes when the names of the resources it references change.
+/// The referenced resources are hashed first, the resources that reference each other are hashed together.
+///
+/// In update mode only the regenerated resources have the placeholders, the other resources are restored to the names with the
+/// placeholders by [HASHED_NAMES_KEY] and hashed again, so the references to the regenerated resources are updated as well.
+pub fn hash_resources(
+  resources_map: &mut HashMap<String, Resource>,
+  context: &Arc<CompilationContext>,
+) {
+  restore_hashed_names(resources_map, context);
+
+  // (name, placeholder) of the resources whose names contain the content hash placeholder
+  let mut pending = resources_map
+    .values()
+    .filter_map(|resource| {
+      let ResourceOrigin::ResourcePot(resource_pot_id) = &resource.origin else {
+        return None;
+      };
+
+      // the source maps are named after their resources
+      if matches!(
+        resource.resource_type,
+        ResourceType::SourceMap(_) | ResourceType::Runtime
+      ) {
+        return None;
+      }
+
+      let placeholder = content_hash_placeholder(
+        &resource_pot_id.to_string(),
+        &resource.resource_type.to_ext(),
+      );
+
+      resource
+        .name
+        .contains(&placeholder)
+        .then(|| (resource.name.clone(), placeholder))
+    })
+    .collect::<Vec<_>>();
+
+  if pending.is_empty() {
+    return;
+  }
+
+  // make the order of the resources in a cycle deterministic
+  pending.sort();
+
+  let mut graph = DiGraph::<usize, ()>::new();
+  let nodes = (0..pending.len())
+    .map(|i| graph.add_node(i))
+    .collect::<Vec<_>>();
+
+  for (i, (name, _)) in pending.iter().enumerate() {
+    let Ok(code) = std::str::from_utf8(&resources_map[name].bytes) else {
+      continue;
+    };
+
+    for (j, (dep_name, _)) in pending.iter().enumerate() {
+      if i != j && name_occurrences(code, dep_name).next().is_some() {
+        graph.add_edge(nodes[i], nodes[j], ());
+      }
+    }
+  }
+
+  // (name with the placeholder, name with the content hash)
+  let mut renamed = vec![];
+
+  // the strongly connected components are returned in reverse topological order, that is the dependencies come first
+  for component in tarjan_scc(&graph) {
+    let mut members = component
+      .into_iter()
+      .map(|node| graph[node])
+      .collect::<Vec<_>>();
+    members.sort();
+
+    let mut bytes = vec![];
+
+    for i in &members {
+      let resource = resources_map.get_mut(&pending[*i].0).unwrap();
+      replace_names(resource, &renamed);
+      bytes.extend_from_slice(&resource.bytes);
+    }
+
+    let component_renamed = members
+      .iter()
+      .map(|i| {
+        let (name, placeholder) = &pending[*i];
+        let content_hash = if members.len() == 1 {
+          sha256(&bytes, 8)
+        } else {
+          sha256(&[&bytes[..], name.as_bytes()].concat(), 8)
+        };
+
+        (name.clone(), name.replace(placeholder, &content_hash))
+      })
+      .collect::<Vec<_>>();
+
+    // the references to the resources themselves, e.g. the source mapping url, or to the other resources in the cycle
+    for i in &members {
+      replace_names(
+        resources_map.get_mut(&pending[*i].0).unwrap(),
+        &component_renamed,
+      );
+    }
+
+    renamed.extend(component_renamed);
+  }
+
+  for (_, mut resource) in resources_map.drain().collect::<Vec<_>>() {
+    if !pending.iter().any(|(name, _)| name == &resource.name) {
+      replace_names(&mut resource, &renamed);
+    }
+
+    resource.name = rename(&resource.name, &renamed);
+    resources_map.insert(resource.name.clone(), resource);
+  }
+
+  let mut resource_pot_map = context.resource_pot_map.write();
+
+  for resource_pot in resource_pot_map.resource_pots_mut() {
+    let names = resource_pot
+      .resources()
+      .into_iter()
+      .cloned()
+      .collect::<Vec<_>>();
+
+    for name in names {
+      let new_name = rename(&name, &renamed);
+
+      if new_name != name {
+        resource_pot.remove_resource(&name);
+        resource_pot.add_resource(new_name);
+      }
+    }
+  }
+
+  // hashed name -> name with the placeholder
+  let hashed_names = renamed
+    .into_iter()
+    .map(|(name, hashed_name)| (hashed_name, name))
+    .collect::<Vec<_>>();
+  context
+    .meta
+    .custom
+    .insert(HASHED_NAMES_KEY.to_string(), Box::new(hashed_names));
+}
+
+/// The key of the hashed names of the last compilation in the custom context meta, see [hash_resources]
+const HASHED_NAMES_KEY: &str = "hash_resources_hashed_names";
+
+/// Restore the names of the resources hashed by the last compilation and the references to them to the names with the placeholders
+fn restore_hashed_names(
+  resources_map: &mut HashMap<String, Resource>,
+  context: &Arc<CompilationContext>,
+) {
+  let Some((_, hashed_names)) = context.meta.custom.remove(HASHED_NAMES_KEY) else {
+    return;
+  };
+  let Ok(hashed_names) = hashed_names.downcast::<Vec<(String, String)>>() else {
+    return;
+  };
+
+  for (_, mut resource) in resources_map.drain().collect::<Vec<_>>() {
+    replace_names(&mut resource, &hashed_names);
+    resource.name = rename(&resource.name, &hashed_names);
+    resources_map.insert(resource.name.clone(), resource);
+  }
+
+  let mut resource_pot_map = context.resource_pot_map.write();
+
+  for resource_pot in resource_pot_map.resource_pots_mut() {
+    let names = resource_pot
+      .resources()
+      .into_iter()
+      .cloned()
+      .collect::<Vec<_>>();
+
+    for name in names {
+      let restored_name = rename(&name, &hashed_names);
+
+      if restored_name != name {
+        resource_pot.remove_resource(&name);
+        resource_pot.add_resource(restored_name);
+      }
+    }
+  }
+}
+
+/// replace the names in the code of the resource, the assets never reference other resources
+fn replace_names(resource: &mut Resource, renamed: &[(String, String)]) {
+  if !matches!(resource.origin, ResourceOrigin::ResourcePot(_)) {
+    return;
+  }
+
+  let mut code = match String::from_utf8(std::mem::take(&mut resource.bytes)) {
+    Ok(code) => code,
+    Err(err) => {
+      resource.bytes = err.into_bytes();
+      return;
+    }
+  };
+
+  for (name, new_name) in renamed {
+    let occurrences = name_occurrences(&code, name).collect::<Vec<_>>();
+
+    if occurrences.is_empty() {
+      continue;
+    }
+
+    let mut replaced = String::with_capacity(code.len());
+    let mut last = 0;
+
+    for start in occurrences {
+      replaced.push_str(&code[last..start]);
+      replaced.push_str(new_name);
+      last = start + name.len();
+    }
+
+    replaced.push_str(&code[last..]);
+    code = replaced;
+  }
+
+  resource.bytes = code.into_bytes();
+}
+
+/// rename the resource or its source map, e.g. `index.js.map` is renamed with `index.js`
+fn rename(name: &str, renamed: &[(String, String)]) -> String {
+  renamed
+    .iter()
+    .find_map(|(old_name, new_name)| {
+      let rest = name.strip_prefix(old_name.as_str())?;
+      (rest.is_empty() || rest.starts_with('.')).then(|| format!("{}{}", new_name, rest))
+    })
+    .unwrap_or_else(|| name.to_string())
+}
+
+/// The byte offsets of `name` in `code` that are not a part of other names, e.g. `a.js` in `"./a.js"` but not in `"./ba.js"`.
+/// The name can be followed by another extension, e.g. `a.js` in `a.js.map`
+fn name_occurrences<'a>(code: &'a str, name: &'a str) -> impl Iterator<Item = usize> + 'a {
+  let is_name_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+
+  code
+    .match_indices(name)
+    .map(|(start, _)| start)
+    .filter(move |start| {
+      let before = code[..*start].chars().next_back();
+      let after = code[start + name.len()..].chars().next();
+
+      !before.map_or(false, is_name_char) && after.map_or(true, |c| c == '.' || !is_name_char(c))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{name_occurrences, rename};
+
+  #[test]
+  fn match_full_names() {
+    let code = r#"import("./a.js");import("./ba.js");//# sourceMappingURL=a.js.map"#;
+    assert_eq!(name_occurrences(code, "a.js").count(), 2);
+    assert_eq!(name_occurrences(code, "ba.js").count(), 1);
+
+    let renamed = vec![("a.js".to_string(), "a.1234.js".to_string())];
+    assert_eq!(rename("a.js", &renamed), "a.1234.js");
+    assert_eq!(rename("a.js.map", &renamed), "a.1234.js.map");
+    assert_eq!(rename("ba.js", &renamed), "ba.js");
+    assert_eq!(rename("a.jsx", &renamed), "a.jsx");
+  }
+}
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use farmfe_core::{
+  context::CompilationContext,
+  hashbrown::HashMap,
+  resource::{resource_pot::ResourcePotId, Resource, ResourceOrigin, ResourceType},
+  serde_json::{self, json, Map, Value},
+};
+
+pub const MANIFEST_FILENAME: &str = "manifest.json";
+
+/// Emit the manifest that maps the entries, the dynamic imported modules and the assets to their final resource names, for example:
+/// ```json
+/// {
+///   "index.ts": { "file": "index.js", "isEntry": true, "imports": ["vendor.8a6c0f3e.js"], "css": ["index_css.0d1e2f3a.css"], "dynamicImports": ["plugin.ts"] },
+///   "plugin.ts": { "file": "plugin.1b2c3d4e.js", "isDynamicEntry": true },
+///   "logo.png": { "file": "logo.5f6a7b8c.png" }
+/// }
+/// ```
+/// The keys are the module ids, that is the paths relative to the root.
+pub fn emit_manifest(
+  resources_map: &mut HashMap<String, Resource>,
+  context: &Arc<CompilationContext>,
+) {
+  if !context.config.output.manifest {
+    return;
+  }
+
+  let module_graph = context.module_graph.read();
+  let module_group_graph = context.module_group_graph.read();
+  let resource_pot_map = context.resource_pot_map.read();
+  // the resources of a resource pot except the source maps and the runtime, sorted by name
+  let resources_of = |resource_pot_id: &ResourcePotId| {
+    let mut resources = resource_pot_map
+      .resource_pot(resource_pot_id)
+      .map(|resource_pot| {
+        resource_pot
+          .resources()
+          .into_iter()
+          .filter_map(|name| resources_map.get(name))
+          .filter(|resource| {
+            !matches!(
+              resource.resource_type,
+              ResourceType::SourceMap(_) | ResourceType::Runtime
+            )
+          })
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+    resources.sort_by(|a, b| a.name.cmp(&b.name));
+    resources
+  };
+  let mut manifest = Map::new();
+
+  for module_group in module_group_graph.module_groups() {
+    let Some(module) = module_graph.module(&module_group.id) else {
+      continue;
+    };
+    let Some(root_resource_pot) = module.resource_pot.as_ref() else {
+      continue;
+    };
+
+    // the root module is a css entry if its resource pot only generates css
+    let root_resources = resources_of(root_resource_pot);
+    let Some(file) = root_resources
+      .iter()
+      .find(|resource| !matches!(resource.resource_type, ResourceType::Css))
+      .or_else(|| root_resources.first())
+    else {
+      continue;
+    };
+
+    let mut resource_pot_ids = module_group.resource_pots().iter().collect::<Vec<_>>();
+    resource_pot_ids.sort_by_key(|id| id.to_string());
+    let mut imports = vec![];
+    let mut css = vec![];
+
+    for resource in resource_pot_ids.into_iter().flat_map(resources_of) {
+      if resource.name == file.name {
+        continue;
+      }
+
+      match resource.resource_type {
+        ResourceType::Js => imports.push(resource.name.clone()),
+        ResourceType::Css => css.push(resource.name.clone()),
+        _ => {}
+      }
+    }
+
+    let mut dynamic_imports = module_group_graph
+      .dependencies_ids(&module_group.id)
+      .into_iter()
+      .map(|id| id.to_string())
+      .collect::<Vec<_>>();
+    dynamic_imports.sort();
+
+    let mut entry = Map::new();
+    entry.insert("file".to_string(), json!(file.name));
+
+    if module_graph.entries.contains_key(&module_group.id) {
+      entry.insert("isEntry".to_string(), json!(true));
+    } else {
+      entry.insert("isDynamicEntry".to_string(), json!(true));
+    }
+
+    for (key, value) in [
+      ("imports", imports),
+      ("css", css),
+      ("dynamicImports", dynamic_imports),
+    ] {
+      if !value.is_empty() {
+        entry.insert(key.to_string(), json!(value));
+      }
+    }
+
+    manifest.insert(module_group.id.to_string(), Value::Object(entry));
+  }
+
+  for resource in resources_map.values() {
+    if let (ResourceOrigin::Module(module_id), ResourceType::Asset(_)) =
+      (&resource.origin, &resource.resource_type)
+    {
+      manifest.insert(module_id.to_string(), json!({ "file": resource.name }));
+    }
+  }
+
+  resources_map.insert(
+    MANIFEST_FILENAME.to_string(),
+    Resource {
+      name: MANIFEST_FILENAME.to_string(),
+      bytes: serde_json::to_string_pretty(&manifest)
+        .unwrap()
+        .into_bytes(),
+      emitted: false,
+      resource_type: ResourceType::Custom("json".to_string()),
+      origin: ResourceOrigin::Compiler,
+    },
+  );
+}
@@ -11,6 +11,8 @@ use crate::{
 };
 
 pub(crate) mod finalize_resources;
+pub(crate) mod hash_resources;
+pub(crate) mod manifest;
 pub(crate) mod partial_bundling;
 pub(crate) mod render_resource_pots;
 
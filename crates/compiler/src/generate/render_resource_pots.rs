@@ -9,7 +9,9 @@ use farmfe_core::{
   rayon::prelude::{IntoParallelIterator, ParallelIterator},
   resource::{resource_pot::ResourcePot, Resource, ResourceType},
 };
-use farmfe_toolkit::fs::{transform_output_entry_filename, transform_output_filename};
+use farmfe_toolkit::fs::{
+  content_hash_placeholder, transform_output_entry_filename, transform_output_filename_with_hash,
+};
 
 pub fn render_resource_pots_and_generate_resources(
   resource_pots: Vec<&mut ResourcePot>,
@@ -52,20 +54,26 @@ pub fn render_resource_pots_and_generate_resources(
         let name_before_update = r.name.clone();
         // ignore runtime resource
         if !matches!(r.resource_type, ResourceType::Runtime) {
+          // the content hash is computed by hash_resources when the references between the resources are resolved
+          let content_hash = content_hash_placeholder(
+            resource_pot.id.to_string().as_str(),
+            &r.resource_type.to_ext(),
+          );
+
           if let Some(name) = resource_pot.entry_module.as_ref() {
             let entry_name = entries.get(name).unwrap();
             r.name = transform_output_entry_filename(
               context.config.output.entry_filename.clone(),
               resource_pot.id.to_string().as_str(),
               entry_name,
-              &r.bytes,
+              &content_hash,
               &r.resource_type.to_ext(),
             );
           } else {
-            r.name = transform_output_filename(
+            r.name = transform_output_filename_with_hash(
               context.config.output.filename.clone(),
               &r.name,
-              &r.bytes,
+              &content_hash,
               &r.resource_type.to_ext(),
             );
           }
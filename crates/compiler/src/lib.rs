@@ -58,6 +58,8 @@ impl Compiler {
 
     let mut context = CompilationContext::new(config, plugins)?;
     context.plugin_driver.config(&mut context.config)?;
+    // the root and mode may be modified by plugins, so reload the env variables
+    context.reload_env();
     Ok(Self {
       context: Arc::new(context),
     })
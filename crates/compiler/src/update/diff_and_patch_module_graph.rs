@@ -89,6 +89,10 @@ pub fn patch_module_graph(
   }
 
   for ((from, to), edge_info) in added_edge_info {
+    if edge_info.is_worker() {
+      module_graph.add_worker_entry(&to);
+    }
+
     module_graph.add_edge(&from, &to, edge_info).unwrap();
   }
 
@@ -98,6 +102,13 @@ pub fn patch_module_graph(
     removed_modules.insert(removed.clone(), removed_module);
   }
 
+  // the worker entries whose last `new Worker(...)` is removed
+  for (_, deps_diff_result) in diff_result.deps_changes.iter() {
+    for (removed_dep, _) in &deps_diff_result.removed {
+      module_graph.remove_worker_entry(removed_dep);
+    }
+  }
+
   // we must remove updated module at last, cause petgraph will remove edge when remove node
   for updated in start_points {
     let module = {
@@ -238,8 +249,8 @@ fn diff_module_deps(
   let mut removed_modules_vec = removed_deps
     .into_iter()
     .filter_map(|(id, _)| {
-      // entry should not be removed for any reason
-      if module_graph.entries.contains_key(&id) {
+      // entry should not be removed for any reason, except the worker entry that is no longer created
+      if module_graph.entries.contains_key(&id) && !module_graph.is_worker_entry(&id) {
         return None;
       }
 
@@ -1,5 +1,6 @@
 use std::{
   collections::HashMap,
+  path::Path,
   sync::{mpsc::Sender, Arc},
 };
 
@@ -63,20 +64,43 @@ impl Compiler {
     let (thread_pool, err_sender, err_receiver) = Self::create_thread_pool();
     let update_context = Arc::new(UpdateContext::new());
 
+    // reload the env variables if any env file changed, the modules using `import.meta.env` are updated through the watch graph
+    if paths
+      .iter()
+      .any(|(path, _)| self.context.env_store.read().is_env_file(path))
+    {
+      self.context.reload_env();
+    }
+
     let watch_graph = self.context.watch_graph.read();
     let module_graph = self.context.module_graph.read();
     // fetch watch file relation module, and replace watch file
     let paths: Vec<(String, UpdateType)> = paths
       .into_iter()
       .flat_map(|(path, update_type)| {
-        if watch_graph.has_module(&path) {
-          let r: Vec<(String, UpdateType)> = watch_graph
-            .relation_roots(&path)
-            .into_iter()
-            .map(|item| (item.to_owned(), UpdateType::Updated))
-            .collect();
-
-          if module_graph.has_module(&ModuleId::new(path.as_str(), "", &self.context.config.root)) {
+        let in_module_graph =
+          module_graph.has_module(&ModuleId::new(path.as_str(), "", &self.context.config.root));
+        // a file added to or removed from a watched directory(e.g. the base directory of `import.meta.glob`) updates the modules watching the directory
+        let watched_dirs = if !in_module_graph || !Path::new(&path).exists() {
+          watch_graph.ancestor_dirs(&path)
+        } else {
+          vec![]
+        };
+
+        if watch_graph.has_module(&path) || !watched_dirs.is_empty() {
+          let mut r: Vec<(String, UpdateType)> = vec![];
+          let watched = watched_dirs
+            .iter()
+            .copied()
+            .chain(watch_graph.has_module(&path).then_some(&path));
+
+          for item in watched.flat_map(|item| watch_graph.relation_roots(item)) {
+            if !r.iter().any(|(p, _)| p == item) {
+              r.push((item.to_owned(), UpdateType::Updated));
+            }
+          }
+
+          if in_module_graph && watched_dirs.is_empty() {
             return [r, vec![(path, update_type)]].concat();
           };
 
@@ -380,6 +404,7 @@ impl Compiler {
       &mut module_graph,
       &mut update_module_graph,
     );
+    module_graph.update_async_modules();
 
     let mut module_group_graph = self.context.module_group_graph.write();
 
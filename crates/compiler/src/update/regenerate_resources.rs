@@ -17,6 +17,7 @@ use farmfe_core::{
 
 use farmfe_plugin_css::transform_resource_pot::transform_css_resource_pot;
 use farmfe_plugin_runtime::render_resource_pot::resource_pot_to_runtime_object_lit;
+use farmfe_plugin_script::get_worker_urls;
 
 use crate::generate::{
   partial_bundling::call_partial_bundling_hook,
@@ -78,7 +79,8 @@ pub fn render_and_generate_update_resource(
     update_resource_pot.add_module(module_id.clone());
   }
 
-  let ast = resource_pot_to_runtime_object_lit(&mut update_resource_pot, &module_graph, context)?;
+  let (ast, comments) =
+    resource_pot_to_runtime_object_lit(&mut update_resource_pot, &module_graph, context)?;
   // The hmr result should alway be a js resource
   update_resource_pot.meta = ResourcePotMetaData::Js(JsResourcePotMetaData {
     ast: SwcModule {
@@ -89,6 +91,7 @@ pub fn render_and_generate_update_resource(
       span: DUMMY_SP,
       shebang: None,
     },
+    comments,
   });
 
   let update_resources = render_resource_pot_generate_resources(
@@ -103,8 +106,19 @@ pub fn render_and_generate_update_resource(
     .find(|r| matches!(r.resource_type, ResourceType::Js))
     .unwrap();
 
+  drop(module_graph);
+
+  // the update resource is not finalized, replace the worker url placeholders here
+  let mut code = String::from_utf8(js_resource.bytes).unwrap();
+
+  for (placeholder, url) in get_worker_urls(context) {
+    if code.contains(placeholder.as_str()) {
+      code = code.replace(placeholder.as_str(), &url);
+    }
+  }
+
   // TODO: also return sourcemap
-  Ok(String::from_utf8(js_resource.bytes).unwrap())
+  Ok(code)
 }
 
 pub fn regenerate_resources_for_affected_module_groups(
@@ -123,7 +137,10 @@ pub fn regenerate_resources_for_affected_module_groups(
 
   for updated_module_id in updated_module_ids {
     let module = module_graph.module(updated_module_id).unwrap();
-    let resource_pot_id = module.resource_pot.as_ref().unwrap();
+    // the assets only referenced by `import.meta.url` are not in any resource pot
+    let Some(resource_pot_id) = module.resource_pot.as_ref() else {
+      continue;
+    };
 
     if !affected_resource_pots_ids.contains(resource_pot_id) {
       affected_resource_pots_ids.push(resource_pot_id.clone());
@@ -238,7 +255,28 @@ fn generate_and_diff_resource_pots(
       }
     }
 
-    if !resource_pot_map.has_resource_pot(&resource_pot.id) {
+    if let Some(old_resource_pot) = resource_pot_map.resource_pot(&resource_pot.id) {
+      // the id of a named resource pot(e.g. `webpackChunkName`) is stable, regenerate it when its modules are changed
+      let mut old_modules = old_resource_pot.modules();
+      let mut modules = resource_pot.modules();
+      old_modules.sort();
+      modules.sort();
+
+      if old_modules != modules {
+        let mut resource_maps = context.resources_map.lock();
+
+        for resource in old_resource_pot.resources() {
+          resource_maps.remove(resource);
+        }
+
+        drop(resource_maps);
+
+        if !new_resource_pot_ids.contains(&resource_pot.id) {
+          new_resource_pot_ids.push(resource_pot.id.clone());
+        }
+        resource_pot_map.add_resource_pot(resource_pot);
+      }
+    } else {
       new_resource_pot_ids.push(resource_pot.id.clone());
       resource_pot_map.add_resource_pot(resource_pot);
     }
@@ -70,37 +70,40 @@ pub fn create_css_compiler(
   compiler
 }
 
-pub fn create_compiler(
+pub fn create_config(
   input: HashMap<String, String>,
   cwd: PathBuf,
   crate_path: PathBuf,
   minify: bool,
-) -> Compiler {
-  let compiler = Compiler::new(
-    Config {
-      input,
-      root: cwd.to_string_lossy().to_string(),
-      runtime: generate_runtime(crate_path),
-      output: farmfe_core::config::OutputConfig {
-        filename: "[resourceName].[ext]".to_string(),
-        ..Default::default()
-      },
-      mode: Mode::Production,
-      external: vec![
-        ConfigRegex(Regex::new("^react-refresh$").unwrap()),
-        ConfigRegex(Regex::new("^module$").unwrap()),
-      ],
-      sourcemap: SourcemapConfig::Bool(false),
-      lazy_compilation: false,
-      minify,
-      preset_env: Box::new(PresetEnvConfig::Bool(false)),
+) -> Config {
+  Config {
+    input,
+    root: cwd.to_string_lossy().to_string(),
+    runtime: generate_runtime(crate_path),
+    output: farmfe_core::config::OutputConfig {
+      filename: "[resourceName].[ext]".to_string(),
       ..Default::default()
     },
-    vec![],
-  )
-  .unwrap();
+    mode: Mode::Production,
+    external: vec![
+      ConfigRegex(Regex::new("^react-refresh$").unwrap()),
+      ConfigRegex(Regex::new("^module$").unwrap()),
+    ],
+    sourcemap: SourcemapConfig::Bool(false),
+    lazy_compilation: false,
+    minify,
+    preset_env: Box::new(PresetEnvConfig::Bool(false)),
+    ..Default::default()
+  }
+}
 
-  compiler
+pub fn create_compiler(
+  input: HashMap<String, String>,
+  cwd: PathBuf,
+  crate_path: PathBuf,
+  minify: bool,
+) -> Compiler {
+  Compiler::new(create_config(input, cwd, crate_path, minify), vec![]).unwrap()
 }
 
 pub fn create_compiler_with_plugins(
@@ -163,7 +166,7 @@ pub fn get_compiler_result(compiler: &Compiler, entry_name: Option<&String>) ->
     }
 
     result.push(match entry_name {
-      Some(entry_name) if name.starts_with(entry_name) => (
+      Some(entry_name) if name.starts_with(&format!("{}.", entry_name)) => (
         "1".into(),
         format!("//{}.{}:\n ", entry_name, resource.resource_type.to_ext()),
         String::from_utf8_lossy(&resource.bytes),
@@ -188,7 +191,6 @@ pub fn get_compiler_result(compiler: &Compiler, entry_name: Option<&String>) ->
 }
 
 pub fn load_expected_result(cwd: PathBuf) -> String {
-  
   std::fs::read_to_string(cwd.join("output.js")).unwrap_or("".to_string())
 }
 
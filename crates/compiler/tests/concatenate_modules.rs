@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use farmfe_compiler::Compiler;
+use farmfe_core::config::Config;
+use farmfe_testing_helpers::fixture;
+
+use crate::common::{assert_compiler_result, create_config};
+
+mod common;
+
+#[test]
+fn concatenate_modules_test() {
+  fixture!(
+    "tests/fixtures/concatenate_modules/**/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      println!("testing concatenate modules: {:?}", cwd);
+
+      let entry_name = "index".to_string();
+      let config = Config {
+        concatenate_modules: true,
+        ..create_config(
+          HashMap::from([(entry_name.clone(), "./index.ts".to_string())]),
+          cwd.to_path_buf(),
+          crate_path,
+          false,
+        )
+      };
+      let compiler = Compiler::new(config, vec![]).unwrap();
+      compiler.compile().unwrap();
+
+      assert_compiler_result(&compiler, Some(&entry_name));
+    }
+  );
+}
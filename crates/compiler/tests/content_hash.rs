@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use farmfe_compiler::Compiler;
+use farmfe_core::config::{Config, OutputConfig};
+use farmfe_testing_helpers::fixture;
+
+use crate::common::{assert_compiler_result, create_config};
+
+mod common;
+
+#[test]
+fn content_hash_test() {
+  fixture!(
+    "tests/fixtures/content_hash/**/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      println!("testing content hash: {:?}", cwd);
+
+      let create_compiler = || {
+        let config = create_config(
+          HashMap::from([("index".to_string(), "./index.ts".to_string())]),
+          cwd.to_path_buf(),
+          crate_path.clone(),
+          false,
+        );
+        let config = Config {
+          output: OutputConfig {
+            entry_filename: "[entryName].[contentHash].[ext]".to_string(),
+            filename: "[resourceName].[contentHash].[ext]".to_string(),
+            assets_filename: "[resourceName].[contentHash].[ext]".to_string(),
+            // the fixture directory is named after the output mode
+            library: cwd.ends_with("library"),
+            manifest: true,
+            ..config.output.clone()
+          },
+          ..config
+        };
+        let compiler = Compiler::new(config, vec![]).unwrap();
+        compiler.compile().unwrap();
+        compiler
+      };
+
+      let compiler = create_compiler();
+      // the hashes are stable between builds
+      let resource_names = |compiler: &Compiler| {
+        let mut names = compiler
+          .context()
+          .resources_map
+          .lock()
+          .keys()
+          .cloned()
+          .collect::<Vec<_>>();
+        names.sort();
+        names
+      };
+      assert_eq!(
+        resource_names(&compiler),
+        resource_names(&create_compiler())
+      );
+
+      assert_compiler_result(&compiler, None);
+    }
+  );
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use farmfe_compiler::Compiler;
+use farmfe_core::config::{
+  preset_env::PresetEnvConfig, Config, Mode, ModuleFormat, OutputConfig, SourcemapConfig,
+};
+use farmfe_testing_helpers::fixture;
+
+use crate::common::{assert_compiler_result, generate_runtime};
+
+mod common;
+
+#[test]
+fn entry_exports_test() {
+  fixture!(
+    "tests/fixtures/entry_exports/**/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      let format = if cwd.to_string_lossy().ends_with("cjs") {
+        ModuleFormat::CommonJs
+      } else {
+        ModuleFormat::EsModule
+      };
+
+      let entry_name = "index".to_string();
+      let compiler = Compiler::new(
+        Config {
+          input: HashMap::from([(entry_name.clone(), "./index.ts".to_string())]),
+          root: cwd.to_string_lossy().to_string(),
+          runtime: generate_runtime(crate_path),
+          output: OutputConfig {
+            filename: "[resourceName].[ext]".to_string(),
+            format,
+            ..Default::default()
+          },
+          mode: Mode::Production,
+          sourcemap: SourcemapConfig::Bool(false),
+          lazy_compilation: false,
+          minify: false,
+          preset_env: Box::new(PresetEnvConfig::Bool(false)),
+          ..Default::default()
+        },
+        vec![],
+      )
+      .unwrap();
+      compiler.compile().unwrap();
+
+      assert_compiler_result(&compiler, Some(&entry_name));
+    }
+  );
+}
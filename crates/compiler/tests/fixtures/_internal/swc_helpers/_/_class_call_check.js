@@ -0,0 +1,4 @@
+export function _class_call_check(instance, Constructor) {
+  if (!(instance instanceof Constructor)) throw new TypeError("Cannot call a class as a function");
+}
+export { _class_call_check as _ };
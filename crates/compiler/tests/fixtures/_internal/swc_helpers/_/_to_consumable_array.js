@@ -0,0 +1,4 @@
+export function _to_consumable_array(arr) {
+  return Array.isArray(arr) ? arr.slice() : Array.from(arr);
+}
+export { _to_consumable_array as _ };
@@ -0,0 +1,4 @@
+export function _ts_metadata(k, v) {
+  if (typeof Reflect === "object" && typeof Reflect.metadata === "function") return Reflect.metadata(k, v);
+}
+export { _ts_metadata as _ };
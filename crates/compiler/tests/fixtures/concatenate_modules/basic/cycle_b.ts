@@ -0,0 +1,5 @@
+import { cycleA } from './cycle_a';
+
+export function cycleB() {
+  return cycleA === undefined ? 'b' : cycleA;
+}
@@ -0,0 +1,7 @@
+import { shared } from './shared';
+
+export const prefix = 'value: ';
+
+export default function (v: string) {
+  return shared + v;
+}
@@ -0,0 +1,12 @@
+import { add, PI } from './math';
+import format, { prefix as p } from './format';
+import { shared } from './shared';
+import './side_effects';
+import { value } from './reexport';
+import { cycleA } from './cycle_a';
+
+export { add };
+export { prefix } from './format';
+
+const name = 'index';
+console.log(name, add(1, 2), PI, format(p), { shared, value }, cycleA);
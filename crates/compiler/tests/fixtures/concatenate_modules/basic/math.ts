@@ -0,0 +1,10 @@
+import { round } from './utils';
+
+const name = 'math';
+console.log(name);
+
+export function add(a: number, b: number) {
+  return round(a + b);
+}
+
+export const PI = 3.14;
@@ -0,0 +1,176 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({  });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "1e69ff7b": function(module, exports, farmRequire, dynamicRequire) {
+        console.log("side effects");
+    },
+    "5715d047": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "cycleA", {
+            enumerable: true,
+            get: function() {
+                return cycleA;
+            }
+        });
+        var _cycle_b = farmRequire("ffd167dc");
+        const cycleA = "a" + (0, _cycle_b.cycleB)();
+    },
+    "86920496": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            add: function() {
+                return add;
+            },
+            PI: function() {
+                return PI;
+            }
+        });
+        const name = "utils";
+        function round(n) {
+            console.log(name);
+            return Math.round(n);
+        }
+        const name1 = "math";
+        console.log(name1);
+        function add(a, b) {
+            return round(a + b);
+        }
+        const PI = 3.14;
+    },
+    "aa67029b": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "value", {
+            enumerable: true,
+            get: function() {
+                return _default;
+            }
+        });
+        const name = "value";
+        var _default = name.length;
+    },
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            add: function() {
+                return _math.add;
+            },
+            prefix: function() {
+                return _format.prefix;
+            }
+        });
+        var _interop_require_wildcard = farmRequire("@swc/helpers/_/_interop_require_wildcard");
+        var _math = farmRequire("86920496");
+        var _format = /*#__PURE__*/ _interop_require_wildcard._(farmRequire("ea26609e"));
+        var _shared = farmRequire("ded636f8");
+        farmRequire("1e69ff7b");
+        var _reexport = farmRequire("aa67029b");
+        var _cycle_a = farmRequire("5715d047");
+        const name = "index";
+        console.log(name, (0, _math.add)(1, 2), _math.PI, (0, _format.default)(_format.prefix), {
+            shared: _shared.shared,
+            value: _reexport.value
+        }, _cycle_a.cycleA);
+    },
+    "ded636f8": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "shared", {
+            enumerable: true,
+            get: function() {
+                return shared;
+            }
+        });
+        const shared = "shared";
+    },
+    "ea26609e": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            prefix: function() {
+                return prefix;
+            },
+            default: function() {
+                return _default;
+            }
+        });
+        var _shared = farmRequire("ded636f8");
+        const prefix = "value: ";
+        function _default(v) {
+            return _shared.shared + v;
+        }
+    },
+    "ffd167dc": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "cycleB", {
+            enumerable: true,
+            get: function() {
+                return cycleB;
+            }
+        });
+        var _cycle_a = farmRequire("5715d047");
+        function cycleB() {
+            return _cycle_a.cycleA === undefined ? "b" : _cycle_a.cycleA;
+        }
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");var __farm_entry_export_0__ = entry.add;var __farm_entry_export_1__ = entry.prefix;export { __farm_entry_export_0__ as add, __farm_entry_export_1__ as prefix };
\ No newline at end of file
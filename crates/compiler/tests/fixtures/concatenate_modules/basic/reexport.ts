@@ -0,0 +1 @@
+export { default as value } from './value';
@@ -0,0 +1,6 @@
+const name = 'utils';
+
+export function round(n: number) {
+  console.log(name);
+  return Math.round(n);
+}
@@ -0,0 +1,3 @@
+const name = 'value';
+
+export default name.length;
@@ -0,0 +1,3 @@
+console.log('index');
+import { dep } from './dep';
+console.log(dep);
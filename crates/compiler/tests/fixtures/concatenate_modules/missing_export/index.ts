@@ -0,0 +1,3 @@
+// @ts-ignore
+import { found, missing } from './dep';
+console.log(found, missing);
@@ -0,0 +1,49 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({  });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "05ee5ec7": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "found", {
+            enumerable: true,
+            get: function() {
+                return found;
+            }
+        });
+        const found = "found";
+    },
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        var _dep = farmRequire("05ee5ec7");
+        console.log(_dep.found, _dep.missing);
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");
\ No newline at end of file
@@ -0,0 +1,65 @@
+//62f92878.eab5f165.css:
+ .logo {
+  background: url("logo.42ef5241.svg");
+}
+
+//82cfc115.addb7dfb.js:
+ const format = (value)=>`[${value}]`;
+export { format };
+
+
+//9d3b79a7.5df2cb7d.js:
+ import { format } from "./82cfc115.addb7dfb.js";
+var plugin_namespace = Object.freeze({
+    __proto__: null,
+    get name () {
+        return name;
+    }
+});
+const name = format("plugin");
+export { plugin_namespace };
+
+
+//index.2579d76d.js:
+ import { format } from "./82cfc115.addb7dfb.js";
+import "./62f92878.eab5f165.css";
+var logo_default = "/logo.42ef5241.svg";
+const loadPlugin = ()=>import("./9d3b79a7.5df2cb7d.js").then(function(m) {
+        return m.plugin_namespace;
+    });
+function render() {
+    return format(logo_default);
+}
+export { loadPlugin, render as default };
+
+
+//logo.42ef5241.svg:
+ <svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"></svg>
+
+
+//manifest.json:
+ {
+  "index.ts": {
+    "css": [
+      "62f92878.eab5f165.css"
+    ],
+    "dynamicImports": [
+      "plugin.ts"
+    ],
+    "file": "index.2579d76d.js",
+    "imports": [
+      "82cfc115.addb7dfb.js"
+    ],
+    "isEntry": true
+  },
+  "logo.svg": {
+    "file": "logo.42ef5241.svg"
+  },
+  "plugin.ts": {
+    "file": "9d3b79a7.5df2cb7d.js",
+    "imports": [
+      "82cfc115.addb7dfb.js"
+    ],
+    "isDynamicEntry": true
+  }
+}
\ No newline at end of file
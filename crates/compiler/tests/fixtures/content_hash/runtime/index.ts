@@ -0,0 +1,9 @@
+import { format } from './format';
+import logo from './logo.svg';
+import './style.css';
+
+export const loadPlugin = () => import('./plugin');
+
+export default function render() {
+  return format(logo);
+}
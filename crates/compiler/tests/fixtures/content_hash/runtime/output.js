@@ -0,0 +1,152 @@
+//62f92878.eab5f165.css:
+ .logo {
+  background: url("logo.42ef5241.svg");
+}
+
+//82cfc115.f91dcdba.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "ea26609e": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "format", {
+            enumerable: true,
+            get: function() {
+                return format;
+            }
+        });
+        const format = (value)=>`[${value}]`;
+    }
+});
+
+
+//9d3b79a7.a442d030.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "c1dcabaf": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "name", {
+            enumerable: true,
+            get: function() {
+                return name;
+            }
+        });
+        var _format = farmRequire("ea26609e");
+        const name = (0, _format.format)("plugin");
+    }
+});
+
+
+//index.12683c33.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources(['82cfc115.f91dcdba.js']);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({ 'c1dcabaf': [{ path: '9d3b79a7.a442d030.js', type: 'script' },], });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            loadPlugin: function() {
+                return loadPlugin;
+            },
+            default: function() {
+                return render;
+            }
+        });
+        var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
+        var _format = farmRequire("ea26609e");
+        var _logosvg = /*#__PURE__*/ _interop_require_default._(farmRequire("f7b8c393"));
+        "";
+        const loadPlugin = ()=>dynamicRequire("c1dcabaf");
+        function render() {
+            return (0, _format.format)(_logosvg.default);
+        }
+    },
+    "f7b8c393": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "default", {
+            enumerable: true,
+            get: function() {
+                return _default;
+            }
+        });
+        var _default = "/logo.42ef5241.svg";
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");var __farm_entry_export_0__ = entry.loadPlugin;export { __farm_entry_export_0__ as loadPlugin };export default entry.default;
+
+//logo.42ef5241.svg:
+ <svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"></svg>
+
+
+//manifest.json:
+ {
+  "index.ts": {
+    "css": [
+      "62f92878.eab5f165.css"
+    ],
+    "dynamicImports": [
+      "plugin.ts"
+    ],
+    "file": "index.12683c33.js",
+    "imports": [
+      "82cfc115.f91dcdba.js"
+    ],
+    "isEntry": true
+  },
+  "logo.svg": {
+    "file": "logo.42ef5241.svg"
+  },
+  "plugin.ts": {
+    "file": "9d3b79a7.a442d030.js",
+    "imports": [
+      "82cfc115.f91dcdba.js"
+    ],
+    "isDynamicEntry": true
+  }
+}
\ No newline at end of file
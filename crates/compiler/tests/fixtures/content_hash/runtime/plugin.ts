@@ -0,0 +1,3 @@
+import { format } from './format';
+
+export const name = format('plugin');
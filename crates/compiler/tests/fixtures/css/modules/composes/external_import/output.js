@@ -53,7 +53,7 @@
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
         "";
-        var _actioncss = _interop_require_default._(farmRequire("8b6840d6"));
+        var _actioncss = /*#__PURE__*/ _interop_require_default._(farmRequire("8b6840d6"));
         var _default = {
             "base": `farm-base ${_actioncss.default["action"]}`
         };
@@ -64,7 +64,7 @@
             value: true
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
-        var _indexcss = _interop_require_default._(farmRequire("95fe6ac5"));
+        var _indexcss = /*#__PURE__*/ _interop_require_default._(farmRequire("95fe6ac5"));
         console.log(_indexcss.default.base);
     }
 });
@@ -44,7 +44,7 @@
             value: true
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
-        var _indexcss = _interop_require_default._(farmRequire("95fe6ac5"));
+        var _indexcss = /*#__PURE__*/ _interop_require_default._(farmRequire("95fe6ac5"));
         console.log(_indexcss.default.base);
     }
 });
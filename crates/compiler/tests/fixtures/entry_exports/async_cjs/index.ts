@@ -0,0 +1,6 @@
+import { add } from '../modules/utils';
+
+const config = await Promise.resolve({ base: 1 });
+
+export const total = add(config.base, 2);
+export default config;
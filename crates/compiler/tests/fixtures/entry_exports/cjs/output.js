@@ -0,0 +1,146 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({  });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "05215633": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            Square: function() {
+                return Square;
+            },
+            width: function() {
+                return width;
+            },
+            height: function() {
+                return height;
+            }
+        });
+        var _export_star = farmRequire("@swc/helpers/_/_export_star");
+        _export_star._(farmRequire("4265a997"), exports);
+        class Square {
+        }
+        const { width, size: { height } } = {
+            width: 1,
+            size: {
+                height: 2
+            }
+        };
+    },
+    "24b26b30": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "default", {
+            enumerable: true,
+            get: function() {
+                return log;
+            }
+        });
+        function log(message) {
+            console.log(message);
+        }
+    },
+    "4265a997": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            version: function() {
+                return version;
+            },
+            add: function() {
+                return add;
+            },
+            default: function() {
+                return noop;
+            }
+        });
+        const version = "1.0.0";
+        function add(a, b) {
+            return a + b;
+        }
+        function noop() {}
+    },
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            utils: function() {
+                return _utils;
+            },
+            default: function() {
+                return _logger.default;
+            },
+            noop: function() {
+                return _utils.default;
+            },
+            sum: function() {
+                return _utils.add;
+            },
+            name: function() {
+                return name;
+            },
+            entryName: function() {
+                return name;
+            },
+            "entry-name": function() {
+                return name;
+            }
+        });
+        var _export_star = farmRequire("@swc/helpers/_/_export_star");
+        var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
+        var _interop_require_wildcard = farmRequire("@swc/helpers/_/_interop_require_wildcard");
+        _export_star._(farmRequire("05215633"), exports);
+        var _utils = /*#__PURE__*/ _interop_require_wildcard._(farmRequire("4265a997"));
+        var _logger = /*#__PURE__*/ _interop_require_default._(farmRequire("24b26b30"));
+        const name = "entry";
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");Object.defineProperty(module.exports, "__esModule", { value: true });module.exports.utils = entry.utils;module.exports.default = entry.default;module.exports.noop = entry.noop;module.exports.sum = entry.sum;module.exports.name = entry.name;module.exports.entryName = entry.entryName;module.exports["entry-name"] = entry["entry-name"];module.exports.Square = entry.Square;module.exports.width = entry.width;module.exports.height = entry.height;module.exports.version = entry.version;module.exports.add = entry.add;
\ No newline at end of file
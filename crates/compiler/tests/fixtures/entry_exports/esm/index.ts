@@ -0,0 +1,7 @@
+export * from '../modules/shapes';
+export * as utils from '../modules/utils';
+export { default } from '../modules/logger';
+export { default as noop, add as sum } from '../modules/utils';
+
+const name = 'entry';
+export { name, name as entryName, name as 'entry-name' };
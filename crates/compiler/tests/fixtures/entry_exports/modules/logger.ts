@@ -0,0 +1,3 @@
+export default function log(message: string) {
+  console.log(message);
+}
@@ -0,0 +1,3 @@
+export * from './utils';
+export class Square {}
+export const { width, size: { height } } = { width: 1, size: { height: 2 } };
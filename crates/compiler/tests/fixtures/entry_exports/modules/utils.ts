@@ -0,0 +1,5 @@
+export const version = '1.0.0';
+export function add(a: number, b: number) {
+  return a + b;
+}
+export default function noop() {}
@@ -0,0 +1 @@
+console.log(import.meta.env.FARM_APP_TITLE);
@@ -0,0 +1,3 @@
+import { VERSION } from './store';
+
+export const format = (value: string) => `${value}@${VERSION}`;
@@ -0,0 +1,3 @@
+export const noop = () => {};
+
+export const identity = <T>(value: T) => value;
@@ -0,0 +1,14 @@
+import React from 'react';
+import { VERSION } from './store';
+import { format } from './format';
+import './style.css';
+
+export { format };
+export * from './utils';
+export * as helpers from './helpers';
+
+export const loadPlugin = () => import('./plugin');
+
+export default function createApp() {
+  return React.createElement('div', null, format(VERSION));
+}
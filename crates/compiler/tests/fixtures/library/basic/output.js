@@ -0,0 +1,62 @@
+//index.js:
+ import react, { useState } from "react";
+import { VERSION, format, createStore, sum } from "./67127a17.js";
+import "./62f92878.css";
+var helpers_namespace = Object.freeze({
+    __proto__: null,
+    get noop () {
+        return noop;
+    },
+    get identity () {
+        return identity;
+    }
+});
+const noop = ()=>{};
+const identity = (value)=>value;
+const loadPlugin = ()=>import("./9d3b79a7.js").then(function(m) {
+        return m.plugin_namespace;
+    });
+function createApp() {
+    return react.createElement("div", null, format(VERSION));
+}
+export { format, helpers_namespace as helpers, loadPlugin, createApp as default, useState, createStore as store, sum };
+
+
+//62f92878.css:
+ .app {
+  color: red;
+}
+
+//67127a17.js:
+ import "react";
+const VERSION = "1.0.0";
+function createStore(state) {
+    let current = state;
+    return {
+        get: ()=>current,
+        set: (next)=>{
+            current = next;
+        }
+    };
+}
+const format = (value)=>`${value}@${VERSION}`;
+const sum = (a, b)=>a + b;
+export { VERSION, format, createStore, sum };
+
+
+//9d3b79a7.js:
+ import { format, sum } from "./67127a17.js";
+var plugin_namespace = Object.freeze({
+    __proto__: null,
+    get name () {
+        return name;
+    },
+    get default () {
+        return plugin_default;
+    }
+});
+const name = format(String(sum(1, 2)));
+var plugin_default = {
+    name
+};
+export { plugin_namespace };
@@ -0,0 +1,8 @@
+import { format } from './format';
+import { sum } from './utils';
+
+export const name = format(String(sum(1, 2)));
+
+export default {
+  name,
+};
@@ -0,0 +1,12 @@
+export const VERSION = '1.0.0';
+
+export function createStore<T>(state: T) {
+  let current = state;
+
+  return {
+    get: () => current,
+    set: (next: T) => {
+      current = next;
+    },
+  };
+}
@@ -0,0 +1,4 @@
+export { useState } from 'react';
+export { createStore as store } from './store';
+
+export const sum = (a: number, b: number) => a + b;
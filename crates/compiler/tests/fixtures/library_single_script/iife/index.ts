@@ -0,0 +1,9 @@
+import React, { useState } from 'react';
+import { sum } from './utils';
+
+export const loadUtils = () => import('./utils');
+
+export function useCounter() {
+  const state = useState(sum(0, 1));
+  return React.useMemo(() => ({ count: state[0], increment: () => state[1](state[0] + 1) }), [state[0]]);
+}
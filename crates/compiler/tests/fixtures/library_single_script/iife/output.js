@@ -0,0 +1,33 @@
+//index.js:
+ var MyLib = function(react) {
+    var react1 = react && react.__esModule ? react.default : react;
+    var useState = react.useState;
+    var utils_namespace = Object.freeze({
+        __proto__: null,
+        get sum () {
+            return sum;
+        }
+    });
+    const sum = (a, b)=>a + b;
+    const loadUtils = ()=>Promise.resolve().then(function() {
+            return utils_namespace;
+        });
+    function useCounter() {
+        const state = useState(sum(0, 1));
+        return react1.useMemo(()=>({
+                count: state[0],
+                increment: ()=>state[1](state[0] + 1)
+            }), [
+            state[0]
+        ]);
+    }
+    return Object.freeze({
+        __proto__: null,
+        get loadUtils () {
+            return loadUtils;
+        },
+        get useCounter () {
+            return useCounter;
+        }
+    });
+}(React);
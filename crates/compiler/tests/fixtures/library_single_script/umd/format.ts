@@ -0,0 +1 @@
+export const format = (value: number) => value.toFixed(2);
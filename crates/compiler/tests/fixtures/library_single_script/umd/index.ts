@@ -0,0 +1,6 @@
+import * as React from 'react';
+import { format } from './format';
+
+export default function render(value: number) {
+  return React.createElement('span', null, format(value));
+}
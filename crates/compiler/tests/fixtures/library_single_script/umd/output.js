@@ -0,0 +1,13 @@
+//index.js:
+ (function(global, factory) {
+    typeof exports === "object" && typeof module !== "undefined" ? module.exports = factory(require("react")) : typeof define === "function" && define.amd ? define([
+        "react"
+    ], factory) : (global = typeof globalThis !== "undefined" ? globalThis : global || self, global.MyLib = factory(global.React));
+})(this, function(react) {
+    var react1 = react;
+    const format = (value)=>value.toFixed(2);
+    function render(value) {
+        return react1.createElement("span", null, format(value));
+    }
+    return render;
+});
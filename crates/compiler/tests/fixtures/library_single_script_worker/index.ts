@@ -0,0 +1 @@
+export const worker = new Worker(new URL('./worker.ts', import.meta.url));
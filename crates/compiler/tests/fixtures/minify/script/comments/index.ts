@@ -0,0 +1,18 @@
+/*!
+ * comments v1.0.0
+ * Licensed under the MIT License.
+ */
+
+/**
+ * @license MIT
+ */
+function createStore(name: string) {
+  // this comment is removed
+  return { name };
+}
+
+/* unused store is dropped as the call is pure */
+const unused = /*#__PURE__*/ createStore('unused');
+const store = createStore('store');
+
+console.log(store.name);
@@ -0,0 +1,7 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules,entryModule){var cache={};function require(id){if(cache[id])return cache[id].exports;var module={id:id,exports:{}};modules[id](module,module.exports,require);cache[id]=module;return module.exports;}require(entryModule);})({"ec853507":function(module,exports,farmRequire,dynamicRequire){"use strict";console.log("runtime/index.js")(globalThis||window||global||self)[__farm_namespace__].__farm_module_system__.setPlugins([]);}},"ec853507");(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({  });/*!
+ * comments v1.0.0
+ * Licensed under the MIT License.
+ *//**
+ * @license MIT
+ */!function(o){for(var _ in o)(globalThis||window||global||self)[__farm_namespace__].__farm_module_system__.register(_,o[_]);}({b5d64806:function(o,_,e,l){console.log("store");}});var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");
\ No newline at end of file
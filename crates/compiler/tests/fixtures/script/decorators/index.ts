@@ -0,0 +1,16 @@
+import { Logger } from './logger';
+
+function Injectable(): ClassDecorator {
+  return () => {};
+}
+
+@Injectable()
+export class UserService {
+  name = 'users';
+
+  constructor(private logger: Logger) {}
+
+  find(id: number) {
+    this.logger.log(`find ${id}`);
+  }
+}
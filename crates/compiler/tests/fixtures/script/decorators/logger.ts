@@ -0,0 +1,5 @@
+export class Logger {
+  log(msg: string) {
+    console.log(msg);
+  }
+}
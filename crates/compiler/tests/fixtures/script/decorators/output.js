@@ -0,0 +1,127 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({  });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "5d1f6e0b": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "Logger", {
+            enumerable: true,
+            get: function() {
+                return Logger;
+            }
+        });
+        class Logger {
+            log(msg) {
+                console.log(msg);
+            }
+        }
+    },
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "UserService", {
+            enumerable: true,
+            get: function() {
+                return UserService;
+            }
+        });
+        var _ts_decorate = farmRequire("e72efc01");
+        var _ts_metadata = farmRequire("ca2ab9ac");
+        var _logger = farmRequire("5d1f6e0b");
+        function Injectable() {
+            return ()=>{};
+        }
+        let UserService = class UserService {
+            constructor(logger){
+                this.logger = logger;
+                this.name = "users";
+            }
+            find(id) {
+                this.logger.log(`find ${id}`);
+            }
+        };
+        UserService = (0, _ts_decorate._)([
+            Injectable(),
+            (0, _ts_metadata._)("design:type", Function),
+            (0, _ts_metadata._)("design:paramtypes", [
+                typeof _logger.Logger === "undefined" ? Object : _logger.Logger
+            ])
+        ], UserService);
+    },
+    "ca2ab9ac": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            _ts_metadata: function() {
+                return _ts_metadata;
+            },
+            _: function() {
+                return _ts_metadata;
+            }
+        });
+        function _ts_metadata(k, v) {
+            if (typeof Reflect === "object" && typeof Reflect.metadata === "function") return Reflect.metadata(k, v);
+        }
+    },
+    "e72efc01": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            _ts_decorate: function() {
+                return _ts_decorate;
+            },
+            _: function() {
+                return _ts_decorate;
+            }
+        });
+        function _ts_decorate(decorators, target, key, desc) {
+            var c = arguments.length, r = c < 3 ? target : desc === null ? desc = Object.getOwnPropertyDescriptor(target, key) : desc, d;
+            for(var i = decorators.length - 1; i >= 0; i--)if (d = decorators[i]) r = (c < 3 ? d(r) : c > 3 ? d(target, key, r) : d(target, key)) || r;
+            return c > 3 && r && Object.defineProperty(target, key, r), r;
+        }
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");var __farm_entry_export_0__ = entry.UserService;export { __farm_entry_export_0__ as UserService };
\ No newline at end of file
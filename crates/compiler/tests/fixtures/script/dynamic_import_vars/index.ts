@@ -0,0 +1,3 @@
+const lang = navigator.language.split('-')[0];
+
+import(`./locales/${lang}.ts`).then((m) => console.log(m.default));
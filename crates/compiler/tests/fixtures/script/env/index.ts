@@ -0,0 +1,9 @@
+console.log(import.meta.env.FARM_APP_TITLE, import.meta.env.FARM_APP_ENV, import.meta.env.SECRET_KEY);
+
+if (import.meta.env.DEV) {
+  console.log('dev only');
+}
+
+if (import.meta.env.MODE === 'production') {
+  console.log(import.meta.env);
+}
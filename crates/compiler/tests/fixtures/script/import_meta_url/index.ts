@@ -0,0 +1,4 @@
+const logo = new URL('./logo.png', import.meta.url);
+const remote = new URL('https://farmfe.org/logo.png', import.meta.url);
+
+console.log(logo.href, remote.href);
@@ -0,0 +1,10 @@
+import { shared } from './shared';
+
+const loadSettings = () => import(/* webpackChunkName: "settings" */ './settings');
+const loadProfile = () => import(/* farmChunkName: "settings" */ './profile');
+const loadLib = () => import(/* webpackIgnore: true */ 'https://cdn.example.com/lib.js');
+// the chunk names are sanitized and must not take the names of the entries
+const loadHelp = () => import(/* webpackChunkName: "../help" */ './help');
+const loadAbout = () => import(/* webpackChunkName: "index" */ './about');
+
+console.log(shared, loadSettings, loadProfile, loadLib, loadHelp, loadAbout);
@@ -0,0 +1,164 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources(['be2e6518.js']);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({ '196eabb1': [{ path: 'index_1.js', type: 'script' },],'26837d31': [{ path: '___help.js', type: 'script' },],'78057274': [{ path: 'settings.js', type: 'script' },],'0bf0ba25': [{ path: 'settings.js', type: 'script' },], });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        var _shared = farmRequire("ded636f8");
+        const loadSettings = ()=>dynamicRequire(/* webpackChunkName: "settings" */ "0bf0ba25");
+        const loadProfile = ()=>dynamicRequire(/* farmChunkName: "settings" */ "78057274");
+        const loadLib = ()=>import(/* webpackIgnore: true */ "https://cdn.example.com/lib.js");
+        // the chunk names are sanitized and must not take the names of the entries
+        const loadHelp = ()=>dynamicRequire(/* webpackChunkName: "../help" */ "26837d31");
+        const loadAbout = ()=>dynamicRequire(/* webpackChunkName: "index" */ "196eabb1");
+        console.log(_shared.shared, loadSettings, loadProfile, loadLib, loadHelp, loadAbout);
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");
+
+//___help.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "26837d31": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "help", {
+            enumerable: true,
+            get: function() {
+                return help;
+            }
+        });
+        const help = "help";
+    }
+});
+
+
+//be2e6518.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "ded636f8": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "shared", {
+            enumerable: true,
+            get: function() {
+                return shared;
+            }
+        });
+        const shared = "shared";
+    }
+});
+
+
+//index_1.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "196eabb1": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "about", {
+            enumerable: true,
+            get: function() {
+                return about;
+            }
+        });
+        const about = "about";
+    }
+});
+
+
+//settings.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "0bf0ba25": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "default", {
+            enumerable: true,
+            get: function() {
+                return _default;
+            }
+        });
+        var _shared = farmRequire("ded636f8");
+        var _format = farmRequire("ea26609e");
+        var _default = (0, _format.format)(`settings ${_shared.shared}`);
+    },
+    "78057274": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "default", {
+            enumerable: true,
+            get: function() {
+                return _default;
+            }
+        });
+        var _format = farmRequire("ea26609e");
+        var _default = (0, _format.format)("profile");
+    },
+    "ea26609e": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "format", {
+            enumerable: true,
+            get: function() {
+                return format;
+            }
+        });
+        function format(msg) {
+            return `[${msg}]`;
+        }
+    }
+});
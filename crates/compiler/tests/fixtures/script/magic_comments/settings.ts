@@ -0,0 +1,4 @@
+import { shared } from './shared';
+import { format } from './format';
+
+export default format(`settings ${shared}`);
@@ -0,0 +1,4 @@
+import { format } from './format';
+import { render } from './page';
+
+console.log(format('index'), render());
@@ -0,0 +1,4 @@
+export function render() {
+  // format is statically imported by the entry, it's resolved from the loaded module
+  return import('./format').then(({ format }) => format('page'));
+}
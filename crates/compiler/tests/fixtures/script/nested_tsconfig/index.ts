@@ -0,0 +1,7 @@
+import { UserService } from './legacy/service';
+
+export class App {
+  name = 'app';
+
+  service = new UserService();
+}
@@ -0,0 +1,8 @@
+function Injectable(): ClassDecorator {
+  return () => {};
+}
+
+@Injectable()
+export class UserService {
+  name = 'users';
+}
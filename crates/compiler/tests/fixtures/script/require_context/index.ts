@@ -0,0 +1,7 @@
+const locales = require.context('./locales', false, /\.ts$/);
+
+const messages = locales.keys().map((key: string) => locales(key).default);
+console.log(messages, locales.resolve('./en.ts'));
+
+// the requires resolved by users are left untouched
+console.log(require.resolve('./locales/en.ts'), require.resolve());
@@ -0,0 +1,80 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({  });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        const locales = ((m, d)=>{
+            var r = (k)=>{
+                if (!(k in m)) throw new Error("Cannot find module '" + k + "'");
+                return m[k];
+            };
+            var c = (k)=>r(k)[1]();
+            c.keys = ()=>Object.keys(m);
+            c.resolve = (k)=>r(k)[0];
+            c.id = d;
+            return c;
+        })({
+            "./en.ts": [
+                "c44a1d70",
+                ()=>farmRequire("c44a1d70")
+            ],
+            "./fr.ts": [
+                "c158a21f",
+                ()=>farmRequire("c158a21f")
+            ]
+        }, "./locales");
+        const messages = locales.keys().map((key)=>locales(key).default);
+        console.log(messages, locales.resolve("./en.ts"));
+        // the requires resolved by users are left untouched
+        console.log(require.resolve("./locales/en.ts"), require.resolve());
+    },
+    "c158a21f": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "default", {
+            enumerable: true,
+            get: function() {
+                return _default;
+            }
+        });
+        var _default = "bonjour";
+    },
+    "c44a1d70": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "default", {
+            enumerable: true,
+            get: function() {
+                return _default;
+            }
+        });
+        var _default = "hello";
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");
\ No newline at end of file
@@ -0,0 +1,4 @@
+import { wasm } from './wasm';
+import { format } from './format';
+
+console.log(format(wasm));
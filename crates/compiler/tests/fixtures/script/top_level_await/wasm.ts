@@ -0,0 +1,3 @@
+const init = () => Promise.resolve({ add: (a: number, b: number) => a + b });
+
+export const wasm = await init();
@@ -0,0 +1,4 @@
+import { format } from './utils';
+
+const worker = new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' });
+worker.postMessage(format('main'));
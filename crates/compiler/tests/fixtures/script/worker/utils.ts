@@ -0,0 +1,3 @@
+export function format(msg: string) {
+  return `[worker] ${msg}`;
+}
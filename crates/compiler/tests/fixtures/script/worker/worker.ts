@@ -0,0 +1,5 @@
+import { format } from './utils';
+
+self.onmessage = (e: MessageEvent) => {
+  self.postMessage(format(e.data));
+};
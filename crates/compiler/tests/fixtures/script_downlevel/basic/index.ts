@@ -0,0 +1,19 @@
+class Counter {
+  count = 0;
+  step;
+
+  constructor(step?: number) {
+    this.step = step ?? 1;
+  }
+
+  increase() {
+    this.count += this.step;
+    return this;
+  }
+}
+
+const config: { counter?: { step: number } } = {};
+const counter = new Counter(config.counter?.step);
+const values = [1, 2, 3].map((v) => v ** 2);
+
+console.log(counter.increase().count, ...values);
@@ -0,0 +1,182 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({  });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "15a11300": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            _create_class: function() {
+                return _create_class;
+            },
+            _: function() {
+                return _create_class;
+            }
+        });
+        function _defineProperties(target, props) {
+            for(var i = 0; i < props.length; i++){
+                var descriptor = props[i];
+                descriptor.enumerable = descriptor.enumerable || false;
+                descriptor.configurable = true;
+                if ("value" in descriptor) descriptor.writable = true;
+                Object.defineProperty(target, descriptor.key, descriptor);
+            }
+        }
+        function _create_class(Constructor, protoProps, staticProps) {
+            if (protoProps) _defineProperties(Constructor.prototype, protoProps);
+            if (staticProps) _defineProperties(Constructor, staticProps);
+            return Constructor;
+        }
+    },
+    "aa48b89f": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            _class_call_check: function() {
+                return _class_call_check;
+            },
+            _: function() {
+                return _class_call_check;
+            }
+        });
+        function _class_call_check(instance, Constructor) {
+            if (!(instance instanceof Constructor)) throw new TypeError("Cannot call a class as a function");
+        }
+    },
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        var _class_call_check = farmRequire("aa48b89f");
+        var _create_class = farmRequire("15a11300");
+        var _define_property = farmRequire("c29ebff9");
+        var _to_consumable_array = farmRequire("ec188579");
+        var _console;
+        var _config_counter;
+        var Counter = /*#__PURE__*/ function() {
+            "use strict";
+            function Counter(step) {
+                (0, _class_call_check._)(this, Counter);
+                (0, _define_property._)(this, "count", 0);
+                (0, _define_property._)(this, "step", void 0);
+                this.step = step !== null && step !== void 0 ? step : 1;
+            }
+            (0, _create_class._)(Counter, [
+                {
+                    key: "increase",
+                    value: function increase() {
+                        this.count += this.step;
+                        return this;
+                    }
+                }
+            ]);
+            return Counter;
+        }();
+        var config = {};
+        var counter = new Counter((_config_counter = config.counter) === null || _config_counter === void 0 ? void 0 : _config_counter.step);
+        var values = [
+            1,
+            2,
+            3
+        ].map(function(v) {
+            return Math.pow(v, 2);
+        });
+        (_console = console).log.apply(_console, [
+            counter.increase().count
+        ].concat((0, _to_consumable_array._)(values)));
+    },
+    "c29ebff9": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            _define_property: function() {
+                return _define_property;
+            },
+            _: function() {
+                return _define_property;
+            }
+        });
+        function _define_property(obj, key, value) {
+            if (key in obj) {
+                Object.defineProperty(obj, key, {
+                    value: value,
+                    enumerable: true,
+                    configurable: true,
+                    writable: true
+                });
+            } else obj[key] = value;
+            return obj;
+        }
+    },
+    "ec188579": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            _to_consumable_array: function() {
+                return _to_consumable_array;
+            },
+            _: function() {
+                return _to_consumable_array;
+            }
+        });
+        function _to_consumable_array(arr) {
+            return Array.isArray(arr) ? arr.slice() : Array.from(arr);
+        }
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");
\ No newline at end of file
@@ -36,7 +36,7 @@
             }
         });
         var _interop_require_wildcard = farmRequire("@swc/helpers/_/_interop_require_wildcard");
-        var _dep = _interop_require_wildcard._(farmRequire("dep.ts"));
+        var _dep = /*#__PURE__*/ _interop_require_wildcard._(farmRequire("dep.ts"));
         const LazyComp = _dep.default.lazy(()=>Promise.resolve({
                 default: ()=>_dep.default.createElement("div", {
                         __source: {
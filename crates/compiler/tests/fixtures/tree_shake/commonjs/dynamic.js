@@ -0,0 +1,5 @@
+exports.a = 1;
+exports.b = 2;
+
+// exports are accessed dynamically, nothing can be removed
+console.log(Object.keys(exports));
@@ -0,0 +1,3 @@
+import { format } from './utils.js';
+
+console.log(format('hello'));
@@ -0,0 +1,3 @@
+exports.add = (a, b) => a + b;
+exports.sub = (a, b) => a - b;
+exports.mul = exports.div = void 0;
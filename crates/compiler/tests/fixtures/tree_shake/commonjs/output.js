@@ -0,0 +1,57 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({  });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "04fdfe1a": function(module, exports, farmRequire, dynamicRequire) {
+        module.exports = {
+            upperCase: (str)=>str.toUpperCase()
+        };
+    },
+    "2f001a0b": function(module, exports, farmRequire, dynamicRequire) {
+        exports.a = 1;
+        exports.b = 2;
+        // exports are accessed dynamically, nothing can be removed
+        console.log(Object.keys(exports));
+    },
+    "9439aea1": function(module, exports, farmRequire, dynamicRequire) {
+        const math = farmRequire("fd0f8d02");
+        const { upperCase } = farmRequire("04fdfe1a");
+        exports.format = function(str) {
+            return upperCase(str) + math.add(1, 2) + farmRequire("2f001a0b").a;
+        };
+    },
+    "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        var _utils = farmRequire("9439aea1");
+        console.log((0, _utils.format)("hello"));
+    },
+    "fd0f8d02": function(module, exports, farmRequire, dynamicRequire) {
+        exports.add = (a, b)=>a + b;
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");
\ No newline at end of file
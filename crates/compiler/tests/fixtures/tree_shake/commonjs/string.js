@@ -0,0 +1,4 @@
+module.exports = {
+  upperCase: (str) => str.toUpperCase(),
+  lowerCase: (str) => str.toLowerCase(),
+};
@@ -0,0 +1,10 @@
+const math = require('./math.js');
+const { upperCase } = require('./string.js');
+
+exports.format = function (str) {
+  return upperCase(str) + math.add(1, 2) + require('./dynamic.js').a;
+};
+
+exports.unused = function () {
+  return math.sub(1, 2);
+};
@@ -0,0 +1,9 @@
+import { b } from './barrel';
+
+export function a() {
+  return b();
+}
+
+export function unusedA() {
+  return 'unusedA';
+}
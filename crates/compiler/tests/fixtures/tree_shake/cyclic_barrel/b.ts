@@ -0,0 +1,9 @@
+import { a } from './barrel';
+
+export function b() {
+  return 'b';
+}
+
+export function unusedB() {
+  return a();
+}
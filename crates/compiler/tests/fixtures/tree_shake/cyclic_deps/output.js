@@ -29,23 +29,14 @@
         Object.defineProperty(exports, "__esModule", {
             value: true
         });
-        function _export(target, all) {
-            for(var name in all)Object.defineProperty(target, name, {
-                enumerable: true,
-                get: all[name]
-            });
-        }
-        _export(exports, {
-            a: function() {
+        Object.defineProperty(exports, "a", {
+            enumerable: true,
+            get: function() {
                 return a;
-            },
-            c: function() {
-                return c;
             }
         });
         var _index = farmRequire("b5d64806");
         const a = "1";
-        const c = _index.b;
     },
     "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
         "use strict";
@@ -63,4 +54,4 @@
         const b = "2";
     }
 });
-var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");
\ No newline at end of file
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");var __farm_entry_export_0__ = entry.b;export { __farm_entry_export_0__ as b };
\ No newline at end of file
@@ -60,7 +60,7 @@ var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace
             }
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
-        var _dep1 = _interop_require_default._(farmRequire("ef0c4c9d"));
+        var _dep1 = /*#__PURE__*/ _interop_require_default._(farmRequire("ef0c4c9d"));
         const dep = "dep";
         function _default() {
             return (0, _dep1.default)();
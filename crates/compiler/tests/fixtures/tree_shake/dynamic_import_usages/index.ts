@@ -0,0 +1,10 @@
+import('./then').then(({ foo, bar: renamed }) => {
+  console.log(foo, renamed);
+});
+import('./then_ident').then((m) => m.foo());
+
+const { foo } = await import('./await');
+const m = await import('./await_ident');
+console.log(foo, m.foo, (await import('./member')).foo);
+const escaped = await import('./escaped');
+console.log(escaped);
@@ -0,0 +1,199 @@
+//index.js:
+ (globalThis || window || global || self).__farm_namespace__ = '__farm_default_namespace__';(globalThis || window || global || self)[__farm_namespace__] = {__FARM_TARGET_ENV__: 'browser'};(function(modules, entryModule) {
+    var cache = {};
+    function require(id) {
+        if (cache[id]) return cache[id].exports;
+        var module = {
+            id: id,
+            exports: {}
+        };
+        modules[id](module, module.exports, require);
+        cache[id] = module;
+        return module.exports;
+    }
+    require(entryModule);
+})({
+    "d2214aaa": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        console.log("runtime/index.js")(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setPlugins([]);
+    }
+}, "d2214aaa");
+(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setInitialLoadedResources([]);(globalThis || window || global || self)[__farm_namespace__].__farm_module_system__.setDynamicModuleResourcesMap({ 'ba93f059': [{ path: '916b7d59.js', type: 'script' },],'18d9e6b9': [{ path: '3a334955.js', type: 'script' },],'a9acf881': [{ path: '3cdd9395.js', type: 'script' },],'4c246035': [{ path: '57726310.js', type: 'script' },],'ac5c5143': [{ path: '346128ac.js', type: 'script' },],'7b67226b': [{ path: 'aa9700ac.js', type: 'script' },], });(function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "b5d64806": async function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        dynamicRequire("ac5c5143").then(({ foo, bar: renamed })=>{
+            console.log(foo, renamed);
+        });
+        dynamicRequire("7b67226b").then((m)=>m.foo());
+        const { foo } = await dynamicRequire("ba93f059");
+        const m = await dynamicRequire("18d9e6b9");
+        console.log(foo, m.foo, (await dynamicRequire("4c246035")).foo);
+        const escaped = await dynamicRequire("a9acf881");
+        console.log(escaped);
+    }
+});
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = await farmModuleSystem.require("b5d64806", true);
+
+//346128ac.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "ac5c5143": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            foo: function() {
+                return foo;
+            },
+            bar: function() {
+                return bar;
+            }
+        });
+        const foo = ()=>"foo";
+        const bar = "bar";
+    }
+});
+
+
+//3a334955.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "18d9e6b9": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "foo", {
+            enumerable: true,
+            get: function() {
+                return foo;
+            }
+        });
+        const foo = ()=>"foo";
+    }
+});
+
+
+//3cdd9395.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "a9acf881": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        function _export(target, all) {
+            for(var name in all)Object.defineProperty(target, name, {
+                enumerable: true,
+                get: all[name]
+            });
+        }
+        _export(exports, {
+            foo: function() {
+                return foo;
+            },
+            bar: function() {
+                return bar;
+            },
+            unused: function() {
+                return unused;
+            }
+        });
+        const foo = ()=>"foo";
+        const bar = "bar";
+        const unused = "unused";
+    }
+});
+
+
+//57726310.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "4c246035": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "foo", {
+            enumerable: true,
+            get: function() {
+                return foo;
+            }
+        });
+        const foo = ()=>"foo";
+    }
+});
+
+
+//916b7d59.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "ba93f059": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "foo", {
+            enumerable: true,
+            get: function() {
+                return foo;
+            }
+        });
+        const foo = ()=>"foo";
+    }
+});
+
+
+//aa9700ac.js:
+ (function(modules) {
+    for(var key in modules){
+        var __farm_global_this__ = (globalThis || window || global || self)[__farm_namespace__];
+        __farm_global_this__.__farm_module_system__.register(key, modules[key]);
+    }
+})({
+    "7b67226b": function(module, exports, farmRequire, dynamicRequire) {
+        "use strict";
+        Object.defineProperty(exports, "__esModule", {
+            value: true
+        });
+        Object.defineProperty(exports, "foo", {
+            enumerable: true,
+            get: function() {
+                return foo;
+            }
+        });
+        const foo = ()=>"foo";
+    }
+});
@@ -71,4 +71,4 @@
         console.log(a, b);
     }
 });
-var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");export default entry.default;
\ No newline at end of file
+var farmModuleSystem = (globalThis || window || global || self)[__farm_namespace__].__farm_module_system__;farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("b5d64806");var __farm_entry_export_0__ = entry.a;var __farm_entry_export_1__ = entry.b;export { __farm_entry_export_0__ as a, __farm_entry_export_1__ as b };export default entry.default;
\ No newline at end of file
@@ -54,7 +54,7 @@
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
         farmRequire("b3d9bc98");
-        var _comp = _interop_require_default._(farmRequire("6f462555"));
+        var _comp = /*#__PURE__*/ _interop_require_default._(farmRequire("6f462555"));
         console.log(_comp.default);
         var _default = 2;
     }
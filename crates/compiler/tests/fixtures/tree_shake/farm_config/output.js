@@ -53,7 +53,7 @@
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
         var _module = farmRequire("module");
         var _config = farmRequire("edceee38");
-        var _util = _interop_require_default._(farmRequire("052dab48"));
+        var _util = /*#__PURE__*/ _interop_require_default._(farmRequire("052dab48"));
         var _default = (0, _config.defineFarmConfig)({
             compilation: {
                 input: _util.default,
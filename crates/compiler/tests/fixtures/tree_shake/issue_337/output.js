@@ -35,9 +35,27 @@
                 return resolve;
             }
         });
+        // Matches the scheme of a URL, eg "http://"
         const schemeRegex = /^[\w+.-]+:\/\//;
-        const urlRegex = /^([\w+.-]+:)\/\/([^@/#?]*@)?([^:/#?]*)(:\d+)?(\/[^#?]*)?(\?[^#]*)?(#.*)?/;
-        const fileRegex = /^file:(?:\/\/((?![a-z]:)[^/#?]*)?)?(\/?[^#?]*)(\?[^#]*)?(#.*)?/i;
+        /**
+ * Matches the parts of a URL:
+ * 1. Scheme, including ":", guaranteed.
+ * 2. User/password, including "@", optional.
+ * 3. Host, guaranteed.
+ * 4. Port, including ":", optional.
+ * 5. Path, including "/", optional.
+ * 6. Query, including "?", optional.
+ * 7. Hash, including "#", optional.
+ */ const urlRegex = /^([\w+.-]+:)\/\/([^@/#?]*@)?([^:/#?]*)(:\d+)?(\/[^#?]*)?(\?[^#]*)?(#.*)?/;
+        /**
+ * File URLs are weird. They dont' need the regular `//` in the scheme, they may or may not start
+ * with a leading `/`, they can have a domain (but only if they don't start with a Windows drive).
+ *
+ * 1. Host, optional.
+ * 2. Path, which may include "/", guaranteed.
+ * 3. Query, including "?", optional.
+ * 4. Hash, including "#", optional.
+ */ const fileRegex = /^file:(?:\/\/((?![a-z]:)[^/#?]*)?)?(\/?[^#?]*)(\?[^#]*)?(#.*)?/i;
         var UrlType;
         (function(UrlType) {
             UrlType[UrlType["Empty"] = 1] = "Empty";
@@ -107,42 +125,66 @@
             return url;
         }
         function stripPathFilename(path) {
+            // If a path ends with a parent directory "..", then it's a relative path with excess parent
+            // paths. It's not a file, so we can't strip it.
             if (path.endsWith("/..")) return path;
             const index = path.lastIndexOf("/");
             return path.slice(0, index + 1);
         }
         function mergePaths(url, base) {
             normalizePath(base, base.type);
+            // If the path is just a "/", then it was an empty path to begin with (remember, we're a relative
+            // path).
             if (url.path === "/") {
                 url.path = base.path;
             } else {
+                // Resolution happens relative to the base path's directory, not the file.
                 url.path = stripPathFilename(base.path) + url.path;
             }
         }
-        function normalizePath(url, type) {
+        /**
+ * The path can have empty directories "//", unneeded parents "foo/..", or current directory
+ * "foo/.". We need to normalize to a standard representation.
+ */ function normalizePath(url, type) {
             const rel = type <= UrlType.RelativePath;
             const pieces = url.path.split("/");
+            // We need to preserve the first piece always, so that we output a leading slash. The item at
+            // pieces[0] is an empty string.
             let pointer = 1;
+            // Positive is the number of real directories we've output, used for popping a parent directory.
+            // Eg, "foo/bar/.." will have a positive 2, and we can decrement to be left with just "foo".
             let positive = 0;
+            // We need to keep a trailing slash if we encounter an empty directory (eg, splitting "foo/" will
+            // generate `["foo", ""]` pieces). And, if we pop a parent directory. But once we encounter a
+            // real directory, we won't need to append, unless the other conditions happen again.
             let addTrailingSlash = false;
             for(let i = 1; i < pieces.length; i++){
                 const piece = pieces[i];
+                // An empty directory, could be a trailing slash, or just a double "//" in the path.
                 if (!piece) {
                     addTrailingSlash = true;
                     continue;
                 }
+                // If we encounter a real directory, then we don't need to append anymore.
                 addTrailingSlash = false;
+                // A current directory, which we can always drop.
                 if (piece === ".") continue;
+                // A parent directory, we need to see if there are any real directories we can pop. Else, we
+                // have an excess of parents, and we'll need to keep the "..".
                 if (piece === "..") {
                     if (positive) {
                         addTrailingSlash = true;
                         positive--;
                         pointer--;
                     } else if (rel) {
+                        // If we're in a relativePath, then we need to keep the excess parents. Else, in an absolute
+                        // URL, protocol relative URL, or an absolute path, we don't need to keep excess.
                         pieces[pointer++] = piece;
                     }
                     continue;
                 }
+                // We've encountered a real directory. Move it to the next insertion pointer, which accounts for
+                // any popped or dropped directories.
                 pieces[pointer++] = piece;
                 positive++;
             }
@@ -155,7 +197,9 @@
             }
             url.path = path;
         }
-        function resolve(input, base) {
+        /**
+ * Attempts to resolve `input` URL/path relative to `base`.
+ */ function resolve(input, base) {
             if (!input && !base) return "";
             const url = parseUrl(input);
             let inputType = url.type;
@@ -165,16 +209,22 @@
                 switch(inputType){
                     case UrlType.Empty:
                         url.hash = baseUrl.hash;
+                    // fall through
                     case UrlType.Hash:
                         url.query = baseUrl.query;
+                    // fall through
                     case UrlType.Query:
                     case UrlType.RelativePath:
                         mergePaths(url, baseUrl);
+                    // fall through
                     case UrlType.AbsolutePath:
+                        // The host, user, and port are joined, you can't copy one without the others.
                         url.user = baseUrl.user;
                         url.host = baseUrl.host;
                         url.port = baseUrl.port;
+                    // fall through
                     case UrlType.SchemeRelative:
+                        // The input doesn't have a schema at least, so we need to copy at least that over.
                         url.scheme = baseUrl.scheme;
                 }
                 if (baseType > inputType) inputType = baseType;
@@ -182,14 +232,20 @@
             normalizePath(url, inputType);
             const queryHash = url.query + url.hash;
             switch(inputType){
+                // This is impossible, because of the empty checks at the start of the function.
+                // case UrlType.Empty:
                 case UrlType.Hash:
                 case UrlType.Query:
                     return queryHash;
                 case UrlType.RelativePath:
                     {
+                        // The first char is always a "/", and we need it to be relative.
                         const path = url.path.slice(1);
                         if (!path) return queryHash || ".";
                         if (isRelative(base || input) && !isRelative(path)) {
+                            // If base started with a leading ".", or there is no base and input started with a ".",
+                            // then we need to ensure that the relative path starts with a ".". We don't know if
+                            // relative starts with a "..", though, so check before prepending.
                             return "./" + path + queryHash;
                         }
                         return path + queryHash;
@@ -213,12 +269,19 @@
             }
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
-        var _root = _interop_require_default._(farmRequire("b5147996"));
-        var freeExports = typeof exports == "object" && exports && !exports.nodeType && exports;
-        var freeModule = freeExports && typeof module == "object" && module && !module.nodeType && module;
-        var moduleExports = freeModule && freeModule.exports === freeExports;
-        var Buffer = moduleExports ? _root.default.Buffer : undefined, allocUnsafe = Buffer ? Buffer.allocUnsafe : undefined;
-        function cloneBuffer(buffer, isDeep) {
+        var _root = /*#__PURE__*/ _interop_require_default._(farmRequire("b5147996"));
+        /** Detect free variable `exports`. */ var freeExports = typeof exports == "object" && exports && !exports.nodeType && exports;
+        /** Detect free variable `module`. */ var freeModule = freeExports && typeof module == "object" && module && !module.nodeType && module;
+        /** Detect the popular CommonJS extension `module.exports`. */ var moduleExports = freeModule && freeModule.exports === freeExports;
+        /** Built-in value references. */ var Buffer = moduleExports ? _root.default.Buffer : undefined, allocUnsafe = Buffer ? Buffer.allocUnsafe : undefined;
+        /**
+ * Creates a clone of  `buffer`.
+ *
+ * @private
+ * @param {Buffer} buffer The buffer to clone.
+ * @param {boolean} [isDeep] Specify a deep clone.
+ * @returns {Buffer} Returns the cloned buffer.
+ */ function cloneBuffer(buffer, isDeep) {
             if (isDeep) {
                 return buffer.slice();
             }
@@ -247,8 +310,8 @@
             value: true
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
-        var _cloneBuffer = _interop_require_default._(farmRequire("7cd09bc5"));
-        var _resolveuri = _interop_require_default._(farmRequire("066a321b"));
+        var _cloneBuffer = /*#__PURE__*/ _interop_require_default._(farmRequire("7cd09bc5"));
+        var _resolveuri = /*#__PURE__*/ _interop_require_default._(farmRequire("066a321b"));
         console.log((0, _cloneBuffer.default)(Buffer.from("test")));
         console.log((0, _resolveuri.default)("test"));
     }
@@ -76,7 +76,7 @@
             }
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
-        var _command = _interop_require_default._(farmRequire("4ef5be87"));
+        var _command = /*#__PURE__*/ _interop_require_default._(farmRequire("4ef5be87"));
         const { program, createCommand, createArgument, createOption, CommanderError, InvalidArgumentError, InvalidOptionArgumentError, Command, Argument, Option, Help } = _command.default;
     },
     "b5d64806": function(module, exports, farmRequire, dynamicRequire) {
@@ -0,0 +1,2 @@
+export const A = 'a';
+export const B = 'b';
@@ -0,0 +1,6 @@
+import * as utils from './utils';
+import * as constants from './constants';
+import { 'kebab-name' as kebabName } from './string_exports';
+
+console.log(utils.add(1, 2), utils['sub'](3, 1), kebabName);
+console.log(Object.keys(constants));
@@ -0,0 +1,4 @@
+const kebab = 'kebab';
+const unused = 'unused';
+
+export { kebab as 'kebab-name', unused as 'unused-name' };
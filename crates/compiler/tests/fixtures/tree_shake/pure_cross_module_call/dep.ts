@@ -0,0 +1,7 @@
+const b = 'b';
+
+console.log(b);
+
+export default function () {
+  return b;
+}
@@ -0,0 +1,5 @@
+import dep from './dep';
+import pureDep from './pure_dep';
+
+dep();
+pureDep();
@@ -0,0 +1,5 @@
+const c = 'c';
+
+export default function () {
+  return c;
+}
@@ -0,0 +1,11 @@
+import { format, unusedPure, log, createStore } from './utils';
+
+const unusedConst = format('unused');
+const unusedObj = Object.freeze({ a: 1 });
+const annotated = /*#__PURE__*/ createStore();
+const unusedArrow = () => unusedPure();
+const sideEffect = log('kept');
+const registry = new Map();
+registry.set('a', 1);
+
+console.log(format('hello'));
@@ -0,0 +1,19 @@
+let count = 0;
+
+export function format(str: string) {
+  return `[${str}]`;
+}
+
+export function unusedPure() {
+  return 1;
+}
+
+export function log(msg: string) {
+  count++;
+  console.log(msg, count);
+  return count;
+}
+
+export function createStore() {
+  return { count: log('store') };
+}
@@ -51,7 +51,7 @@
             value: true
         });
         var _interop_require_default = farmRequire("@swc/helpers/_/_interop_require_default");
-        var _dep = _interop_require_default._(farmRequire("05ee5ec7"));
+        var _dep = /*#__PURE__*/ _interop_require_default._(farmRequire("05ee5ec7"));
         (0, _dep.default)();
     }
 });
@@ -0,0 +1,3 @@
+import { cycleB } from './cycle_b';
+
+export const cycleA = () => cycleB;
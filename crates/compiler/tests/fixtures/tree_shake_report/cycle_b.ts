@@ -0,0 +1,3 @@
+import { cycleA } from './cycle_a';
+
+export const cycleB = () => cycleA;
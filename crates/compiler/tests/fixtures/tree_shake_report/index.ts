@@ -0,0 +1,8 @@
+import { a } from './a';
+import * as ns from './ns';
+import './side_effects';
+import { cycleA } from './cycle_a';
+import { unused } from './unused';
+
+console.log(a, ns.b, cycleA);
+import('./lazy');
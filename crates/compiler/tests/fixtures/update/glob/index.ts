@@ -0,0 +1,3 @@
+const pages = import.meta.glob('./pages/*.ts', { eager: true });
+
+console.log(pages);
@@ -0,0 +1,2 @@
+const worker = new Worker(new URL('./worker.ts', import.meta.url));
+worker.postMessage('main');
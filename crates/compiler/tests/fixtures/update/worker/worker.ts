@@ -0,0 +1,3 @@
+self.onmessage = (e: MessageEvent) => {
+  self.postMessage(e.data);
+};
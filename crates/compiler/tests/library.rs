@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use farmfe_compiler::Compiler;
+use farmfe_core::{
+  config::{config_regex::ConfigRegex, Config, ModuleFormat, OutputConfig},
+  regex::Regex,
+};
+use farmfe_testing_helpers::fixture;
+
+use crate::common::{assert_compiler_result, create_config};
+
+mod common;
+
+#[test]
+fn library_test() {
+  fixture!("tests/fixtures/library/**/index.ts", |file, crate_path| {
+    let cwd = file.parent().unwrap();
+    println!("testing library: {:?}", cwd);
+
+    let entry_name = "index".to_string();
+    let mut config = create_config(
+      HashMap::from([(entry_name.clone(), "./index.ts".to_string())]),
+      cwd.to_path_buf(),
+      crate_path,
+      false,
+    );
+    config
+      .external
+      .push(ConfigRegex(Regex::new("^react$").unwrap()));
+    let config = Config {
+      output: OutputConfig {
+        library: true,
+        ..config.output.clone()
+      },
+      ..config
+    };
+    let compiler = Compiler::new(config, vec![]).unwrap();
+    compiler.compile().unwrap();
+
+    assert_compiler_result(&compiler, Some(&entry_name));
+  });
+}
+
+#[test]
+fn library_single_script_test() {
+  fixture!(
+    "tests/fixtures/library_single_script/**/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      println!("testing library single script: {:?}", cwd);
+
+      let entry_name = "index".to_string();
+      let mut config = create_config(
+        HashMap::from([(entry_name.clone(), "./index.ts".to_string())]),
+        cwd.to_path_buf(),
+        crate_path,
+        false,
+      );
+      config
+        .external
+        .push(ConfigRegex(Regex::new("^react$").unwrap()));
+      // the fixture directory is named after the format
+      let format = match cwd.file_name().unwrap().to_str().unwrap() {
+        "iife" => ModuleFormat::Iife,
+        "umd" => ModuleFormat::Umd,
+        name => panic!("unknown single script format {}", name),
+      };
+      let config = Config {
+        output: OutputConfig {
+          format,
+          name: Some("MyLib".to_string()),
+          globals: HashMap::from([("react".to_string(), "React".to_string())]),
+          ..config.output.clone()
+        },
+        ..config
+      };
+      let compiler = Compiler::new(config, vec![]).unwrap();
+      compiler.compile().unwrap();
+
+      assert_compiler_result(&compiler, Some(&entry_name));
+    }
+  );
+}
+
+#[test]
+fn library_single_script_worker() {
+  fixture!(
+    "tests/fixtures/library_single_script_worker/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      let config = create_config(
+        HashMap::from([("index".to_string(), "./index.ts".to_string())]),
+        cwd.to_path_buf(),
+        crate_path,
+        false,
+      );
+      let config = Config {
+        output: OutputConfig {
+          format: ModuleFormat::Iife,
+          name: Some("MyLib".to_string()),
+          ..config.output.clone()
+        },
+        ..config
+      };
+      let compiler = Compiler::new(config, vec![]).unwrap();
+      let err = compiler.compile().unwrap_err();
+
+      assert!(err
+        .to_string()
+        .contains("the iife and umd formats do not support web workers"));
+    }
+  );
+}
+
+#[test]
+fn library_namespace_export() {
+  fixture!(
+    "tests/fixtures/library/basic/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      let mut config = create_config(
+        HashMap::from([("index".to_string(), "./index.ts".to_string())]),
+        cwd.to_path_buf(),
+        crate_path,
+        false,
+      );
+      config
+        .external
+        .push(ConfigRegex(Regex::new("^react$").unwrap()));
+      let config = Config {
+        output: OutputConfig {
+          library: true,
+          ..config.output.clone()
+        },
+        ..config
+      };
+      let compiler = Compiler::new(config, vec![]).unwrap();
+      compiler.compile().unwrap();
+
+      // `export * as helpers from './helpers'` is kept as an export of the entry chunk
+      let resources_map = compiler.context().resources_map.lock();
+      let entry_resource = resources_map
+        .values()
+        .find(|resource| resource.name.starts_with("index"))
+        .unwrap();
+      let code = String::from_utf8(entry_resource.bytes.clone()).unwrap();
+      assert!(code.contains("as helpers"));
+    }
+  );
+}
+
+#[test]
+fn library_single_script_top_level_await() {
+  fixture!(
+    "tests/fixtures/library_single_script_tla/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      let config = create_config(
+        HashMap::from([("index".to_string(), "./index.ts".to_string())]),
+        cwd.to_path_buf(),
+        crate_path,
+        false,
+      );
+      let config = Config {
+        output: OutputConfig {
+          format: ModuleFormat::Umd,
+          name: Some("MyLib".to_string()),
+          ..config.output.clone()
+        },
+        ..config
+      };
+      let compiler = Compiler::new(config, vec![]).unwrap();
+      let err = compiler.compile().unwrap_err();
+
+      assert!(err
+        .to_string()
+        .contains("the iife and umd formats do not support top level await"));
+    }
+  );
+}
+
+#[test]
+fn library_single_script_quoted_globals() {
+  fixture!(
+    "tests/fixtures/library_single_script/**/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      let mut config = create_config(
+        HashMap::from([("index".to_string(), "./index.ts".to_string())]),
+        cwd.to_path_buf(),
+        crate_path,
+        false,
+      );
+      config
+        .external
+        .push(ConfigRegex(Regex::new("^react$").unwrap()));
+      let (format, expected) = match cwd.file_name().unwrap().to_str().unwrap() {
+        "iife" => (ModuleFormat::Iife, r#"this["vendor-react"]"#),
+        "umd" => (ModuleFormat::Umd, r#"global["vendor-react"]"#),
+        name => panic!("unknown single script format {}", name),
+      };
+      let config = Config {
+        output: OutputConfig {
+          format,
+          name: Some("MyLib".to_string()),
+          globals: HashMap::from([("react".to_string(), "vendor-react".to_string())]),
+          ..config.output.clone()
+        },
+        ..config
+      };
+      let compiler = Compiler::new(config, vec![]).unwrap();
+      compiler.compile().unwrap();
+
+      let resources_map = compiler.context().resources_map.lock();
+      let entry_resource = resources_map
+        .values()
+        .find(|resource| resource.name.starts_with("index"))
+        .unwrap();
+      let code = String::from_utf8(entry_resource.bytes.clone()).unwrap();
+      assert!(code.contains(expected));
+    }
+  );
+}
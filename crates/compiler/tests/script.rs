@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
+use farmfe_compiler::Compiler;
+use farmfe_core::swc_ecma_ast::EsVersion;
 use farmfe_testing_helpers::fixture;
 
-use crate::common::{assert_compiler_result, create_compiler};
+use crate::common::{assert_compiler_result, create_compiler, create_config};
 
 mod common;
 
@@ -23,3 +25,73 @@ fn script_test() {
     assert_compiler_result(&compiler, Some(&entry_name));
   });
 }
+
+#[test]
+fn script_module_worker_warning() {
+  fixture!(
+    "tests/fixtures/script/worker/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+
+      let compiler = create_compiler(
+        HashMap::from([("index".to_string(), "./index.ts".to_string())]),
+        cwd.to_path_buf(),
+        crate_path,
+        false,
+      );
+      compiler.compile().unwrap();
+
+      let log_store = compiler.context().log_store.read();
+      assert!(log_store
+        .warnings()
+        .iter()
+        .any(|warning| warning.contains("The module worker created in index.ts")));
+    }
+  );
+}
+
+#[test]
+fn script_env_dir() {
+  fixture!("tests/fixtures/env_dir/index.ts", |file, crate_path| {
+    let cwd = file.parent().unwrap();
+
+    let entry_name = "index".to_string();
+    let mut config = create_config(
+      HashMap::from([(entry_name.clone(), "./index.ts".to_string())]),
+      cwd.to_path_buf(),
+      crate_path,
+      false,
+    );
+    config.env_dir = Some("env".to_string());
+
+    let compiler = Compiler::new(config, vec![]).unwrap();
+    compiler.compile().unwrap();
+
+    assert_compiler_result(&compiler, Some(&entry_name));
+  });
+}
+
+#[test]
+fn script_downlevel_test() {
+  fixture!(
+    "tests/fixtures/script_downlevel/**/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+
+      let entry_name = "index".to_string();
+      let mut config = create_config(
+        HashMap::from([(entry_name.clone(), "./index.ts".to_string())]),
+        cwd.to_path_buf(),
+        crate_path,
+        false,
+      );
+      config.script.target = EsVersion::Es5;
+      config.script.downlevel = true;
+
+      let compiler = Compiler::new(config, vec![]).unwrap();
+      compiler.compile().unwrap();
+
+      assert_compiler_result(&compiler, Some(&entry_name));
+    }
+  );
+}
@@ -1,8 +1,10 @@
 use std::{collections::HashMap, sync::Arc};
 
+use farmfe_compiler::Compiler;
 use farmfe_core::{
   module::ModuleType,
   plugin::Plugin,
+  serde_json::json,
   swc_common::{comments::NoopComments, Mark},
 };
 use farmfe_testing_helpers::fixture;
@@ -16,7 +18,9 @@ use farmfe_toolkit::{
   swc_ecma_visit::VisitMutWith,
 };
 
-use crate::common::{assert_compiler_result, create_compiler, create_compiler_with_plugins};
+use crate::common::{
+  assert_compiler_result, create_compiler, create_compiler_with_plugins, create_config,
+};
 
 mod common;
 
@@ -143,3 +147,58 @@ fn tree_shake_changed_ast() {
     }
   );
 }
+
+#[test]
+fn tree_shake_report() {
+  fixture!(
+    "tests/fixtures/tree_shake_report/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap();
+      let mut config = create_config(
+        HashMap::from([("index".to_string(), "./index.ts".to_string())]),
+        cwd.to_path_buf(),
+        crate_path,
+        false,
+      );
+      config.record = true;
+      let compiler = Compiler::new(config, vec![]).unwrap();
+      compiler.compile().unwrap();
+
+      // unused.ts is removed, so it's not in the report
+      let records = compiler.context().record_manager.get_tree_shake_records();
+      assert_eq!(
+        farmfe_core::serde_json::to_value(records).unwrap(),
+        json!([
+          {
+            "moduleId": "a.ts",
+            "reasons": [{ "kind": "usedExports", "importer": "index.ts", "exports": ["a"] }]
+          },
+          {
+            "moduleId": "cycle_a.ts",
+            "reasons": [
+              { "kind": "usedExports", "importer": "index.ts", "exports": ["cycleA"] },
+              { "kind": "usedExports", "importer": "cycle_b.ts", "exports": ["cycleA"] }
+            ]
+          },
+          {
+            "moduleId": "cycle_b.ts",
+            "reasons": [{ "kind": "usedExports", "importer": "cycle_a.ts", "exports": ["cycleB"] }]
+          },
+          { "moduleId": "index.ts", "reasons": [{ "kind": "entry" }] },
+          {
+            "moduleId": "lazy.ts",
+            "reasons": [{ "kind": "sideEffects", "importer": "index.ts" }]
+          },
+          {
+            "moduleId": "ns.ts",
+            "reasons": [{ "kind": "usedExports", "importer": "index.ts", "exports": ["b"] }]
+          },
+          {
+            "moduleId": "side_effects.ts",
+            "reasons": [{ "kind": "sideEffects", "importer": "index.ts" }]
+          }
+        ])
+      );
+    }
+  );
+}
@@ -19,31 +19,34 @@ fn create_update_compiler(
   crate_path: PathBuf,
   minify: bool,
 ) -> Compiler {
-  let compiler = Compiler::new(
-    Config {
-      input,
-      root: cwd.to_string_lossy().to_string(),
-      runtime: generate_runtime(crate_path),
-      output: farmfe_core::config::OutputConfig {
-        filename: "[resourceName].[ext]".to_string(),
-        ..Default::default()
-      },
-      mode: Mode::Development,
-      external: vec![
-        ConfigRegex(Regex::new("^react-refresh$").unwrap()),
-        ConfigRegex(Regex::new("^module$").unwrap()),
-      ],
-      sourcemap: SourcemapConfig::Bool(false),
-      lazy_compilation: false,
-      minify,
-      preset_env: Box::new(PresetEnvConfig::Bool(false)),
+  Compiler::new(create_update_config(input, cwd, crate_path, minify), vec![]).unwrap()
+}
+
+fn create_update_config(
+  input: HashMap<String, String>,
+  cwd: PathBuf,
+  crate_path: PathBuf,
+  minify: bool,
+) -> Config {
+  Config {
+    input,
+    root: cwd.to_string_lossy().to_string(),
+    runtime: generate_runtime(crate_path),
+    output: farmfe_core::config::OutputConfig {
+      filename: "[resourceName].[ext]".to_string(),
       ..Default::default()
     },
-    vec![],
-  )
-  .unwrap();
-
-  compiler
+    mode: Mode::Development,
+    external: vec![
+      ConfigRegex(Regex::new("^react-refresh$").unwrap()),
+      ConfigRegex(Regex::new("^module$").unwrap()),
+    ],
+    sourcemap: SourcemapConfig::Bool(false),
+    lazy_compilation: false,
+    minify,
+    preset_env: Box::new(PresetEnvConfig::Bool(false)),
+    ..Default::default()
+  }
 }
 
 #[test]
@@ -68,11 +71,7 @@ fn update_without_dependencies_change() {
         .to_string_lossy()
         .to_string();
       let result = compiler
-        .update(
-          vec![(update_file, UpdateType::Updated)],
-          || {},
-          true,
-        )
+        .update(vec![(update_file, UpdateType::Updated)], || {}, true)
         .unwrap();
 
       assert_eq!(result.added_module_ids.len(), 0);
@@ -120,11 +119,7 @@ fn update_without_dependencies_change_css() {
       assert_eq!(result.resources, "{\n    \"index.css\": function(module, exports, farmRequire, dynamicRequire) {\n        \"use strict\";\n        const cssCode = `body {\n  color: red;\n}`;\n        const farmId = \"index.css\";\n        const previousStyle = document.querySelector(`style[data-farm-id=\"${farmId}\"]`);\n        const style = document.createElement(\"style\");\n        style.setAttribute(\"data-farm-id\", farmId);\n        style.innerHTML = cssCode;\n        if (previousStyle) {\n            previousStyle.replaceWith(style);\n        } else {\n            document.head.appendChild(style);\n        }\n        module.meta.hot.accept();\n        module.onDispose(()=>{\n            style.remove();\n        });\n    }\n};\n");
 
       let result = compiler
-        .update(
-          vec![(update_file, UpdateType::Updated)],
-          || {},
-          false,
-        )
+        .update(vec![(update_file, UpdateType::Updated)], || {}, false)
         .unwrap();
 
       assert_eq!(result.added_module_ids.len(), 0);
@@ -189,7 +184,7 @@ fn update_with_dependencies_change_css_modules() {
       assert_eq!(result.updated_module_ids, vec!["index.ts".into()]);
       assert_eq!(result.removed_module_ids.len(), 0);
 
-      assert_eq!(result.resources, "{\n    \"index.module.css\": function(module, exports, farmRequire, dynamicRequire) {\n        \"use strict\";\n        Object.defineProperty(exports, \"__esModule\", {\n            value: true\n        });\n        Object.defineProperty(exports, \"default\", {\n            enumerable: true,\n            get: function() {\n                return _default;\n            }\n        });\n        farmRequire(\"index.module.css.FARM_CSS_MODULES?f1d5b6cc\");\n        var _default = {\n            \"className\": `className-477586ce`\n        };\n    },\n    \"index.module.css.FARM_CSS_MODULES?f1d5b6cc\": function(module, exports, farmRequire, dynamicRequire) {\n        \"use strict\";\n        const cssCode = `.className-477586ce {\n  color: red;\n}`;\n        const farmId = \"index.module.css.FARM_CSS_MODULES?f1d5b6cc\";\n        const previousStyle = document.querySelector(`style[data-farm-id=\"${farmId}\"]`);\n        const style = document.createElement(\"style\");\n        style.setAttribute(\"data-farm-id\", farmId);\n        style.innerHTML = cssCode;\n        if (previousStyle) {\n            previousStyle.replaceWith(style);\n        } else {\n            document.head.appendChild(style);\n        }\n        module.meta.hot.accept();\n        module.onDispose(()=>{\n            style.remove();\n        });\n    },\n    \"index.ts\": function(module, exports, farmRequire, dynamicRequire) {\n        \"use strict\";\n        Object.defineProperty(exports, \"__esModule\", {\n            value: true\n        });\n        var _interop_require_default = farmRequire(\"@swc/helpers/_/_interop_require_default\");\n        farmRequire(\"index.css\");\n        var _indexmodulecss = _interop_require_default._(farmRequire(\"index.module.css\"));\n        console.log(_indexmodulecss.default);\n    }\n};\n");
+      assert_eq!(result.resources, "{\n    \"index.module.css\": function(module, exports, farmRequire, dynamicRequire) {\n        \"use strict\";\n        Object.defineProperty(exports, \"__esModule\", {\n            value: true\n        });\n        Object.defineProperty(exports, \"default\", {\n            enumerable: true,\n            get: function() {\n                return _default;\n            }\n        });\n        farmRequire(\"index.module.css.FARM_CSS_MODULES?f1d5b6cc\");\n        var _default = {\n            \"className\": `className-477586ce`\n        };\n    },\n    \"index.module.css.FARM_CSS_MODULES?f1d5b6cc\": function(module, exports, farmRequire, dynamicRequire) {\n        \"use strict\";\n        const cssCode = `.className-477586ce {\n  color: red;\n}`;\n        const farmId = \"index.module.css.FARM_CSS_MODULES?f1d5b6cc\";\n        const previousStyle = document.querySelector(`style[data-farm-id=\"${farmId}\"]`);\n        const style = document.createElement(\"style\");\n        style.setAttribute(\"data-farm-id\", farmId);\n        style.innerHTML = cssCode;\n        if (previousStyle) {\n            previousStyle.replaceWith(style);\n        } else {\n            document.head.appendChild(style);\n        }\n        module.meta.hot.accept();\n        module.onDispose(()=>{\n            style.remove();\n        });\n    },\n    \"index.ts\": function(module, exports, farmRequire, dynamicRequire) {\n        \"use strict\";\n        Object.defineProperty(exports, \"__esModule\", {\n            value: true\n        });\n        var _interop_require_default = farmRequire(\"@swc/helpers/_/_interop_require_default\");\n        farmRequire(\"index.css\");\n        var _indexmodulecss = /*#__PURE__*/ _interop_require_default._(farmRequire(\"index.module.css\"));\n        console.log(_indexmodulecss.default);\n    }\n};\n");
 
       let update_file_css = cwd.join("index.module.css").to_string_lossy().to_string();
       // read original index.module.css
@@ -227,11 +222,7 @@ fn update_with_dependencies_change_css_modules() {
       let mut original_ts_file = File::create(&update_file).unwrap();
       original_ts_file.write_all(original_ts.as_bytes()).unwrap();
       let result = compiler
-        .update(
-          vec![(update_file, UpdateType::Updated)],
-          || {},
-          false,
-        )
+        .update(vec![(update_file, UpdateType::Updated)], || {}, false)
         .unwrap();
 
       assert_eq!(result.added_module_ids.len(), 0);
@@ -248,3 +239,300 @@ fn update_with_dependencies_change_css_modules() {
     }
   );
 }
+
+#[test]
+fn update_env_file() {
+  fixture!(
+    "tests/fixtures/update/env/index.html",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap().to_path_buf();
+      let compiler = create_update_compiler(
+        HashMap::from([("index".to_string(), "./index.html".to_string())]),
+        cwd.clone(),
+        crate_path,
+        false,
+      );
+
+      compiler.compile().unwrap();
+
+      let env_file = cwd.join(".env").to_string_lossy().to_string();
+      let mut original_env = String::new();
+      File::open(&env_file)
+        .unwrap()
+        .read_to_string(&mut original_env)
+        .unwrap();
+      File::create(&env_file)
+        .unwrap()
+        .write_all(original_env.replace("farm", "farm-updated").as_bytes())
+        .unwrap();
+
+      let result = compiler
+        .update(vec![(env_file.clone(), UpdateType::Updated)], || {}, true)
+        .unwrap();
+
+      // restore .env
+      File::create(&env_file)
+        .unwrap()
+        .write_all(original_env.as_bytes())
+        .unwrap();
+
+      assert_eq!(result.added_module_ids.len(), 0);
+      assert_eq!(result.updated_module_ids, vec!["index.ts".into()]);
+      assert_eq!(result.removed_module_ids.len(), 0);
+      assert!(result.resources.contains("console.log(\"farm-updated\");"));
+    }
+  );
+}
+
+#[test]
+fn update_env_file_added() {
+  fixture!(
+    "tests/fixtures/update/env/index.html",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap().to_path_buf();
+      let compiler = create_update_compiler(
+        HashMap::from([("index".to_string(), "./index.html".to_string())]),
+        cwd.clone(),
+        crate_path,
+        false,
+      );
+
+      compiler.compile().unwrap();
+
+      // .env.local is not loaded at startup
+      let env_file = cwd.join(".env.local").to_string_lossy().to_string();
+      File::create(&env_file)
+        .unwrap()
+        .write_all(b"FARM_APP_TITLE=farm-local")
+        .unwrap();
+
+      let result = compiler.update(vec![(env_file.clone(), UpdateType::Added)], || {}, true);
+
+      std::fs::remove_file(&env_file).unwrap();
+
+      let result = result.unwrap();
+      assert_eq!(result.updated_module_ids, vec!["index.ts".into()]);
+      assert!(result.resources.contains("console.log(\"farm-local\");"));
+    }
+  );
+}
+
+#[test]
+fn update_glob_added_file() {
+  fixture!(
+    "tests/fixtures/update/glob/index.html",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap().to_path_buf();
+      let compiler = create_update_compiler(
+        HashMap::from([("index".to_string(), "./index.html".to_string())]),
+        cwd.clone(),
+        crate_path,
+        false,
+      );
+
+      compiler.compile().unwrap();
+
+      let added_file = cwd.join("pages").join("b.ts");
+      File::create(&added_file)
+        .unwrap()
+        .write_all(b"export const title = 'b';")
+        .unwrap();
+
+      let result = compiler.update(
+        vec![(
+          added_file.to_string_lossy().to_string(),
+          UpdateType::Updated,
+        )],
+        || {},
+        true,
+      );
+
+      std::fs::remove_file(&added_file).unwrap();
+
+      let result = result.unwrap();
+      assert_eq!(result.added_module_ids, vec!["pages/b.ts".into()]);
+      assert_eq!(result.updated_module_ids, vec!["index.ts".into()]);
+      assert_eq!(result.removed_module_ids.len(), 0);
+    }
+  );
+}
+
+#[test]
+fn update_remove_worker() {
+  fixture!(
+    "tests/fixtures/update/worker/index.html",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap().to_path_buf();
+      let compiler = create_update_compiler(
+        HashMap::from([("index".to_string(), "./index.html".to_string())]),
+        cwd.clone(),
+        crate_path,
+        false,
+      );
+
+      compiler.compile().unwrap();
+      assert!(compiler
+        .context()
+        .module_graph
+        .read()
+        .entries
+        .contains_key(&"worker.ts".into()));
+
+      let update_file = cwd.join("index.ts").to_string_lossy().to_string();
+      let mut original_code = String::new();
+      File::open(&update_file)
+        .unwrap()
+        .read_to_string(&mut original_code)
+        .unwrap();
+      File::create(&update_file)
+        .unwrap()
+        .write_all(b"console.log('main');")
+        .unwrap();
+
+      let result = compiler.update(
+        vec![(update_file.clone(), UpdateType::Updated)],
+        || {},
+        true,
+      );
+
+      // restore index.ts
+      File::create(&update_file)
+        .unwrap()
+        .write_all(original_code.as_bytes())
+        .unwrap();
+
+      let result = result.unwrap();
+      assert_eq!(result.updated_module_ids, vec!["index.ts".into()]);
+      assert_eq!(result.removed_module_ids, vec!["worker.ts".into()]);
+      assert!(!compiler
+        .context()
+        .module_graph
+        .read()
+        .entries
+        .contains_key(&"worker.ts".into()));
+    }
+  );
+}
+
+#[test]
+fn update_worker_url() {
+  fixture!(
+    "tests/fixtures/update/worker/index.html",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap().to_path_buf();
+      let compiler = create_update_compiler(
+        HashMap::from([("index".to_string(), "./index.html".to_string())]),
+        cwd.clone(),
+        crate_path,
+        false,
+      );
+
+      compiler.compile().unwrap();
+
+      let update_file = cwd.join("index.ts").to_string_lossy().to_string();
+      let mut original_code = String::new();
+      File::open(&update_file)
+        .unwrap()
+        .read_to_string(&mut original_code)
+        .unwrap();
+      File::create(&update_file)
+        .unwrap()
+        .write_all(format!("{}console.log('main');", original_code).as_bytes())
+        .unwrap();
+
+      let result = compiler.update(
+        vec![(update_file.clone(), UpdateType::Updated)],
+        || {},
+        true,
+      );
+
+      // restore index.ts
+      File::create(&update_file)
+        .unwrap()
+        .write_all(original_code.as_bytes())
+        .unwrap();
+
+      let result = result.unwrap();
+      assert_eq!(result.updated_module_ids, vec!["index.ts".into()]);
+      assert!(!result.resources.contains("__FARM_WORKER_URL__"));
+      assert!(result.resources.contains("new URL(\"worker"));
+    }
+  );
+}
+
+#[test]
+fn update_content_hash() {
+  fixture!(
+    "tests/fixtures/update/content_hash/index.ts",
+    |file, crate_path| {
+      let cwd = file.parent().unwrap().to_path_buf();
+      let config = create_update_config(
+        HashMap::from([("index".to_string(), "./index.ts".to_string())]),
+        cwd.clone(),
+        crate_path,
+        false,
+      );
+      let compiler = Compiler::new(
+        Config {
+          output: farmfe_core::config::OutputConfig {
+            filename: "[resourceName].[contentHash].[ext]".to_string(),
+            ..config.output.clone()
+          },
+          ..config
+        },
+        vec![],
+      )
+      .unwrap();
+      compiler.compile().unwrap();
+
+      let dynamic_resource_name = || {
+        compiler
+          .context()
+          .resources_map
+          .lock()
+          .keys()
+          .find(|name| name.ends_with(".js") && !name.starts_with("index"))
+          .cloned()
+          .unwrap()
+      };
+      let name_before_update = dynamic_resource_name();
+
+      let update_file = cwd.join("dynamic.ts").to_string_lossy().to_string();
+      let mut original_code = String::new();
+      File::open(&update_file)
+        .unwrap()
+        .read_to_string(&mut original_code)
+        .unwrap();
+      File::create(&update_file)
+        .unwrap()
+        .write_all(b"export const dynamic = 'updated';")
+        .unwrap();
+
+      let result = compiler.update(
+        vec![(update_file.clone(), UpdateType::Updated)],
+        || {},
+        true,
+      );
+
+      // restore dynamic.ts
+      File::create(&update_file)
+        .unwrap()
+        .write_all(original_code.as_bytes())
+        .unwrap();
+
+      result.unwrap();
+      let name_after_update = dynamic_resource_name();
+      assert_ne!(name_before_update, name_after_update);
+
+      // the entry is not regenerated, but it references the new name of the dynamic resource
+      let resources_map = compiler.context().resources_map.lock();
+      let entry = resources_map
+        .values()
+        .find(|resource| resource.name.starts_with("index"))
+        .unwrap();
+      let code = String::from_utf8(entry.bytes.clone()).unwrap();
+      assert!(code.contains(&name_after_update));
+      assert!(!code.contains(&name_before_update));
+    }
+  );
+}
@@ -27,6 +27,10 @@ pub struct Config {
   pub resolve: ResolveConfig,
   pub external: Vec<ConfigRegex>,
   pub define: HashMap<String, String>,
+  /// The directory to load the `.env` files from, relative to `root`. Defaults to `root`
+  pub env_dir: Option<String>,
+  /// Only the variables starting with these prefixes are loaded from `.env` files and exposed as `import.meta.env.*`
+  pub env_prefix: Vec<String>,
   pub runtime: RuntimeConfig,
   pub script: ScriptConfig,
   pub assets: AssetsConfig,
@@ -39,6 +43,9 @@ pub struct Config {
   pub tree_shaking: bool,
   // TODO: support minify options
   pub minify: bool,
+  /// Concatenate the es modules that are only imported by one module of the same resource pot into the scope of the importer (scope hoisting).
+  /// Only works in production mode, the concatenated modules are not registered in the module system so they can not be updated by HMR.
+  pub concatenate_modules: bool,
   // TODO: support preset env options
   pub preset_env: Box<PresetEnvConfig>,
   pub record: bool,
@@ -56,6 +63,8 @@ impl Default for Config {
       mode: Mode::Development,
       resolve: ResolveConfig::default(),
       define: HashMap::new(),
+      env_dir: None,
+      env_prefix: vec![String::from("FARM_")],
       external: vec![],
       runtime: Default::default(),
       script: Default::default(),
@@ -68,8 +77,9 @@ impl Default for Config {
       core_lib_path: None,
       tree_shaking: true,
       minify: true,
+      concatenate_modules: false,
       preset_env: Box::<PresetEnvConfig>::default(),
-      record: false
+      record: false,
     }
   }
 }
@@ -84,6 +94,16 @@ pub struct OutputConfig {
   pub assets_filename: String,
   pub target_env: TargetEnv,
   pub format: ModuleFormat,
+  /// Render the resource pots as native es modules that import and export each other, for publishing packages.
+  /// The external modules are kept as bare imports and the farm runtime is not emitted.
+  pub library: bool,
+  /// The global variable that the exports of the entry are assigned to, only used by the `iife` and `umd` formats
+  pub name: Option<String>,
+  /// The global variables of the external modules, e.g. `{ "react": "React" }`, only used by the `iife` and `umd` formats
+  pub globals: HashMap<String, String>,
+  /// Emit a `manifest.json` that maps the entries, the dynamic imported modules and the assets to their resource names,
+  /// for the backends that render the html themselves
+  pub manifest: bool,
 }
 
 impl Default for OutputConfig {
@@ -98,6 +118,10 @@ impl Default for OutputConfig {
       path: "dist".to_string(),
       target_env: TargetEnv::default(),
       format: ModuleFormat::default(),
+      library: false,
+      name: None,
+      globals: HashMap::new(),
+      manifest: false,
     }
   }
 }
@@ -118,6 +142,19 @@ pub enum ModuleFormat {
   EsModule,
   #[serde(rename = "cjs")]
   CommonJs,
+  /// a single script that can be loaded by a `<script>` tag, built on top of `output.library`
+  #[serde(rename = "iife")]
+  Iife,
+  /// a single script that works as commonjs, amd and `<script>`, built on top of `output.library`
+  #[serde(rename = "umd")]
+  Umd,
+}
+
+impl ModuleFormat {
+  /// the iife and umd outputs are single scripts that can not load other chunks
+  pub fn is_single_script(&self) -> bool {
+    matches!(self, ModuleFormat::Iife | ModuleFormat::Umd)
+  }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -172,8 +209,30 @@ impl Default for ScriptConfigPlugin {
 #[serde(rename_all = "camelCase", default)]
 pub struct ScriptConfig {
   pub target: EsVersion,
+  /// Transform the syntax newer than `target` down to `target`, e.g. class fields and optional chaining.
+  /// Disabled by default, `target` only affects the code generation then
+  pub downlevel: bool,
   pub parser: ScriptParserConfig,
   pub plugins: Vec<ScriptConfigPlugin>,
+  pub typescript: ScriptTypescriptConfig,
+}
+
+/// Options of transforming typescript, the options that are not specified are read from `compilerOptions` of the `tsconfig.json` closest to each module.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScriptTypescriptConfig {
+  /// transform decorators using the legacy(stage 1) semantics
+  pub experimental_decorators: Option<bool>,
+  /// emit the design type metadata of the decorated declarations, only works with `experimental_decorators`
+  pub emit_decorator_metadata: Option<bool>,
+  /// false to transform class fields to assignments in the constructor
+  pub use_define_for_class_fields: Option<bool>,
+  pub jsx_factory: Option<String>,
+  pub jsx_fragment_factory: Option<String>,
+  /// `remove`, `preserve` or `error`. The imports that are only used as types are kept if it's not `remove`
+  pub imports_not_used_as_values: Option<String>,
+  /// true to keep all the imports that are not marked as `type`
+  pub verbatim_module_syntax: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
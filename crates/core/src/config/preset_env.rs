@@ -16,6 +16,20 @@ impl PresetEnvConfig {
       PresetEnvConfig::Obj(_) => true,
     }
   }
+
+  /// (include, exclude) of the script modules to transform, the modules matched by `exclude` and not matched by `include` are not transformed.
+  /// Shared by the polyfill and the syntax downleveling of `script.target`
+  pub fn include_exclude(&self) -> (Vec<ConfigRegex>, Vec<ConfigRegex>) {
+    match self {
+      PresetEnvConfig::Bool(_) => {
+        let PresetEnvConfigObj {
+          include, exclude, ..
+        } = PresetEnvConfigObj::default();
+        (include, exclude)
+      }
+      PresetEnvConfig::Obj(obj) => (obj.include.clone(), obj.exclude.clone()),
+    }
+  }
 }
 
 impl Default for PresetEnvConfig {
@@ -0,0 +1,177 @@
+use std::path::{Path, PathBuf};
+
+use hashbrown::HashMap;
+
+use crate::config::Config;
+
+/// Env variables loaded from `.env`, `.env.local`, `.env.[mode]` and `.env.[mode].local` under `config.env_dir`(defaults to `config.root`),
+/// later files take precedence. Only the variables starting with `config.env_prefix` are kept,
+/// they are exposed as `import.meta.env.*` in script modules.
+#[derive(Debug, Default)]
+pub struct EnvStore {
+  vars: HashMap<String, String>,
+  /// the directory the env files are loaded from
+  dir: PathBuf,
+  /// the env files of current mode whether they exist or not, watched so the env variables are reloaded when any of them is added, changed or removed
+  files: Vec<String>,
+}
+
+impl EnvStore {
+  pub fn new() -> Self {
+    Self {
+      vars: HashMap::new(),
+      dir: PathBuf::new(),
+      files: vec![],
+    }
+  }
+
+  pub fn load(config: &Config) -> Self {
+    let mode = config.mode.to_string();
+    let mut store = Self::new();
+    let mut parsed = HashMap::new();
+
+    store.dir = match &config.env_dir {
+      Some(dir) => Path::new(&config.root).join(dir),
+      None => PathBuf::from(&config.root),
+    };
+
+    for name in [
+      ".env".to_string(),
+      ".env.local".to_string(),
+      format!(".env.{}", mode),
+      format!(".env.{}.local", mode),
+    ] {
+      let path = store.dir.join(name);
+
+      if let Ok(content) = std::fs::read_to_string(&path) {
+        parse_env(&content, &mut parsed);
+      }
+
+      store.files.push(path.to_string_lossy().to_string());
+    }
+
+    store.vars = parsed
+      .into_iter()
+      .filter(|(key, _)| {
+        config
+          .env_prefix
+          .iter()
+          .any(|prefix| key.starts_with(prefix))
+      })
+      .collect();
+
+    store
+  }
+
+  pub fn vars(&self) -> &HashMap<String, String> {
+    &self.vars
+  }
+
+  pub fn files(&self) -> &Vec<String> {
+    &self.files
+  }
+
+  /// Whether the path is a `.env` or `.env.*` file under the env dir, the files that are not loaded at startup are matched too
+  pub fn is_env_file(&self, path: &str) -> bool {
+    let path = Path::new(path);
+    let is_env_name = path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .is_some_and(|name| name == ".env" || name.starts_with(".env."));
+
+    is_env_name && path.parent() == Some(self.dir.as_path())
+  }
+}
+
+/// Parse the content of a `.env` file and insert the variables into `vars`. Supported syntax:
+/// ```sh
+/// # comment
+/// export FARM_A=a # inline comment
+/// FARM_B="b\n${FARM_A}"
+/// FARM_C='${FARM_A}'
+/// ```
+/// `${NAME}` is expanded using the variables parsed before, except in single quoted values.
+fn parse_env(content: &str, vars: &mut HashMap<String, String>) {
+  for line in content.lines() {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    let line = line.strip_prefix("export ").unwrap_or(line);
+
+    let Some((key, value)) = line.split_once('=') else {
+      continue;
+    };
+    let key = key.trim();
+    let value = value.trim();
+
+    let value = if let Some(v) = unquote(value, '\'') {
+      v.to_string()
+    } else if let Some(v) = unquote(value, '"') {
+      expand(&v.replace("\\n", "\n"), vars)
+    } else {
+      // strip inline comments of unquoted values
+      let v = value.split(" #").next().unwrap_or_default().trim_end();
+      expand(v, vars)
+    };
+
+    vars.insert(key.to_string(), value);
+  }
+}
+
+fn unquote(value: &str, quote: char) -> Option<&str> {
+  let value = value.strip_prefix(quote)?;
+  let end = value.rfind(quote)?;
+
+  Some(&value[..end])
+}
+
+fn expand(value: &str, vars: &HashMap<String, String>) -> String {
+  let mut result = String::new();
+  let mut rest = value;
+
+  while let Some(start) = rest.find("${") {
+    let Some(end) = rest[start..].find('}') else {
+      break;
+    };
+
+    result.push_str(&rest[..start]);
+    let name = &rest[start + 2..start + end];
+    result.push_str(vars.get(name).map(|v| v.as_str()).unwrap_or_default());
+    rest = &rest[start + end + 1..];
+  }
+
+  result.push_str(rest);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use hashbrown::HashMap;
+
+  use super::parse_env;
+
+  #[test]
+  fn parse() {
+    let mut vars = HashMap::new();
+    parse_env(
+      r#"
+# comment
+FARM_A=a # inline comment
+export FARM_B="b\n${FARM_A}"
+FARM_C='${FARM_A}'
+FARM_D = ${FARM_A}-${UNKNOWN}-d
+INVALID
+"#,
+      &mut vars,
+    );
+
+    assert_eq!(vars.len(), 4);
+    assert_eq!(vars["FARM_A"], "a");
+    assert_eq!(vars["FARM_B"], "b\na");
+    assert_eq!(vars["FARM_C"], "${FARM_A}");
+    assert_eq!(vars["FARM_D"], "a--d");
+  }
+}
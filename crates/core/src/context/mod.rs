@@ -18,8 +18,9 @@ use crate::{
   resource::{resource_pot_map::ResourcePotMap, Resource, ResourceOrigin, ResourceType},
 };
 
-use self::log_store::LogStore;
+use self::{env_store::EnvStore, log_store::LogStore};
 
+pub mod env_store;
 pub mod log_store;
 
 /// Shared context through the whole compilation.
@@ -35,6 +36,7 @@ pub struct CompilationContext {
   pub meta: Box<ContextMetaData>,
   pub record_manager: Box<RecordManager>,
   pub log_store: Box<RwLock<LogStore>>,
+  pub env_store: Box<RwLock<EnvStore>>,
 }
 
 impl CompilationContext {
@@ -46,6 +48,7 @@ impl CompilationContext {
       resource_pot_map: Box::new(RwLock::new(ResourcePotMap::new())),
       resources_map: Box::new(Mutex::new(HashMap::new())),
       plugin_driver: Box::new(PluginDriver::new(plugins, config.record)),
+      env_store: Box::new(RwLock::new(EnvStore::load(&config))),
       config: Box::new(config),
       cache_manager: Box::new(CacheManager::new()),
       meta: Box::new(ContextMetaData::new()),
@@ -70,6 +73,11 @@ impl CompilationContext {
     Ok(())
   }
 
+  /// Reload the env variables from the `.env` files, called when the config or the env files changed
+  pub fn reload_env(&self) {
+    *self.env_store.write() = EnvStore::load(&self.config);
+  }
+
   pub fn emit_file(&self, params: EmitFileParams) {
     let mut resources_map = self.resources_map.lock();
 
@@ -13,7 +13,10 @@ use relative_path::RelativePath;
 use rkyv::{Archive, Archived, Deserialize, Serialize};
 use rkyv_dyn::archive_dyn;
 use rkyv_typename::TypeName;
-use swc_common::DUMMY_SP;
+use swc_common::{
+  comments::{Comment, SingleThreadedComments},
+  BytePos, DUMMY_SP,
+};
 use swc_css_ast::Stylesheet;
 use swc_ecma_ast::Module as SwcModule;
 use swc_html_ast::Document;
@@ -52,6 +55,9 @@ pub struct Module {
   /// Execution order of this module in the module graph
   /// updated after the module graph is built
   pub execution_order: usize,
+  /// whether this module is an async module, that is, it uses top level await or it imports an async module statically.
+  /// updated after the module graph is built
+  pub is_async: bool,
 }
 
 impl Module {
@@ -67,6 +73,7 @@ impl Module {
       external: false,
       immutable: false,
       execution_order: 0,
+      is_async: false,
     }
   }
 }
@@ -185,6 +192,12 @@ pub struct ScriptModuleMetaData {
   pub module_system: ModuleSystem,
   /// true if this module calls `import.meta.hot.accept`
   pub hmr_accepted: bool,
+  /// the comments of this module, for example, `/*#__PURE__*/` and `/*! license */`
+  pub comments: CommentsMetaData,
+  /// the magic comments of the dynamic imports, for example, `import(/* webpackChunkName: "settings" */ './settings')`
+  pub dynamic_import_magic_comments: Vec<DynamicImportMagicComments>,
+  /// true if this module uses top level await, for example, `await init()` or `for await (const a of b)` in the module scope
+  pub top_level_await: bool,
 }
 
 impl Default for ScriptModuleMetaData {
@@ -199,6 +212,9 @@ impl Default for ScriptModuleMetaData {
       unresolved_mark: 0,
       module_system: ModuleSystem::EsModule,
       hmr_accepted: false,
+      comments: Default::default(),
+      dynamic_import_magic_comments: vec![],
+      top_level_await: false,
     }
   }
 }
@@ -218,6 +234,102 @@ impl ScriptModuleMetaData {
   pub fn set_ast(&mut self, ast: SwcModule) {
     self.ast = ast;
   }
+
+  pub fn take_comments(&mut self) -> CommentsMetaData {
+    std::mem::take(&mut self.comments)
+  }
+
+  pub fn set_comments(&mut self, comments: CommentsMetaData) {
+    self.comments = comments;
+  }
+
+  pub fn dynamic_import_magic_comments(&self, source: &str) -> Option<&DynamicImportMagicComments> {
+    self
+      .dynamic_import_magic_comments
+      .iter()
+      .find(|item| item.source == source)
+  }
+}
+
+/// Magic comments of a dynamic import, `webpackChunkName`/`farmChunkName` names the dynamic chunk
+/// and `webpackIgnore`/`farmIgnore` leaves the dynamic import untouched.
+#[cache_item]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DynamicImportMagicComments {
+  /// the source of the dynamic import, e.g. `./settings`
+  pub source: String,
+  pub chunk_name: Option<String>,
+  pub ignore: bool,
+}
+
+/// Serializable comments of a script module or resource pot, keyed by the [BytePos] of the node they are attached to.
+/// Convert it to [SingleThreadedComments] when a swc transform or the codegen needs the comments.
+#[cache_item]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommentsMetaData {
+  pub leading: Vec<CommentsMetaDataItem>,
+  pub trailing: Vec<CommentsMetaDataItem>,
+}
+
+#[cache_item]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentsMetaDataItem {
+  pub byte_pos: BytePos,
+  pub comment: Vec<Comment>,
+}
+
+impl CommentsMetaData {
+  /// Merge the comments of other modules, the [BytePos] of different modules never overlap as they are parsed by the same [swc_common::SourceMap]
+  pub fn extend(&mut self, other: CommentsMetaData) {
+    self.leading.extend(other.leading);
+    self.trailing.extend(other.trailing);
+  }
+}
+
+impl From<SingleThreadedComments> for CommentsMetaData {
+  fn from(value: SingleThreadedComments) -> Self {
+    let (leading, trailing) = value.take_all();
+    let to_items = |map: std::collections::HashMap<BytePos, Vec<Comment>, _>| {
+      let mut items = map
+        .into_iter()
+        .map(|(byte_pos, comment)| CommentsMetaDataItem { byte_pos, comment })
+        .collect::<Vec<_>>();
+      // make sure the order is stable, the map is not ordered
+      items.sort_by_key(|item| item.byte_pos);
+      items
+    };
+
+    Self {
+      leading: to_items(leading.take()),
+      trailing: to_items(trailing.take()),
+    }
+  }
+}
+
+impl From<CommentsMetaData> for SingleThreadedComments {
+  fn from(value: CommentsMetaData) -> Self {
+    let comments = SingleThreadedComments::default();
+
+    {
+      let (mut leading, mut trailing) = comments.borrow_all_mut();
+
+      for item in value.leading {
+        leading
+          .entry(item.byte_pos)
+          .or_default()
+          .extend(item.comment);
+      }
+
+      for item in value.trailing {
+        trailing
+          .entry(item.byte_pos)
+          .or_default()
+          .extend(item.comment);
+      }
+    }
+
+    comments
+  }
 }
 
 #[cache_item]
@@ -1,9 +1,10 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, path::Path};
 
 use hashbrown::{HashMap, HashSet};
 
 use petgraph::{
-  graph::{DefaultIx, NodeIndex},
+  algo::tarjan_scc,
+  graph::{DefaultIx, DiGraph, NodeIndex},
   stable_graph::StableDiGraph,
   visit::{Bfs, Dfs},
   EdgeDirection,
@@ -14,7 +15,7 @@ use crate::{
   plugin::ResolveKind,
 };
 
-use super::{Module, ModuleId};
+use super::{Module, ModuleId, ModuleMetaData};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct ModuleGraphEdgeDataItem {
@@ -63,6 +64,11 @@ impl ModuleGraphEdge {
     self.0.iter().all(|item| item.kind.is_dynamic())
   }
 
+  // true if any of the edge data items creates a web worker
+  pub fn is_worker(&self) -> bool {
+    self.0.iter().any(|item| item.kind.is_worker())
+  }
+
   pub fn is_empty(&self) -> bool {
     self.0.is_empty()
   }
@@ -234,6 +240,41 @@ impl ModuleGraph {
     Ok(())
   }
 
+  /// Mark the module as the entry of a web worker, so it's bundled as a separate module group with its own runtime.
+  /// The entry name is derived from the file name and the module id, e.g. `worker_1a2b3c4d`
+  pub fn add_worker_entry(&mut self, module_id: &ModuleId) {
+    if self.entries.contains_key(module_id) {
+      return;
+    }
+
+    self
+      .entries
+      .insert(module_id.clone(), worker_entry_name(module_id));
+  }
+
+  /// true if the module is marked as an entry by [ModuleGraph::add_worker_entry] rather than configured by the user
+  pub fn is_worker_entry(&self, module_id: &ModuleId) -> bool {
+    self.entries.get(module_id) == Some(&worker_entry_name(module_id))
+  }
+
+  /// Unmark the worker entry when the module is removed or no module creates the worker any more
+  pub fn remove_worker_entry(&mut self, module_id: &ModuleId) {
+    if !self.is_worker_entry(module_id) {
+      return;
+    }
+
+    if self.has_module(module_id)
+      && self
+        .dependents(module_id)
+        .iter()
+        .any(|(_, edge)| edge.is_worker())
+    {
+      return;
+    }
+
+    self.entries.remove(module_id);
+  }
+
   pub fn remove_edge(&mut self, from: &ModuleId, to: &ModuleId) -> Result<()> {
     let from_index = self.id_index_map.get(from).ok_or_else(|| {
       CompilationError::GenericError(format!(
@@ -440,6 +481,99 @@ impl ModuleGraph {
       });
   }
 
+  /// mark the modules that use top level await and their transitive static importers as async modules.
+  /// the dynamic imports are always asynchronous, so the importers of dynamic imported async modules are not marked.
+  pub fn update_async_modules(&mut self) {
+    let mut stack = vec![];
+
+    for module in self.g.node_weights_mut() {
+      module.is_async = false;
+
+      if let ModuleMetaData::Script(meta) = &module.meta {
+        if meta.top_level_await {
+          stack.push(module.id.clone());
+        }
+      }
+    }
+
+    while let Some(module_id) = stack.pop() {
+      let module = self.module_mut(&module_id).unwrap();
+
+      if module.is_async {
+        continue;
+      }
+
+      module.is_async = true;
+
+      for (dependent, edge) in self.dependents(&module_id) {
+        if !edge.is_dynamic() {
+          stack.push(dependent);
+        }
+      }
+    }
+  }
+
+  /// Group the async modules by the cycles of their static dependencies, the modules in the same cycle get the same index.
+  /// The static importers of an async module are async too, so a cycle that contains an async module only contains async modules.
+  /// Computed once when rendering, the async modules in the same cycle must not await each other.
+  pub fn async_module_cycles(&self) -> HashMap<ModuleId, usize> {
+    let mut graph = DiGraph::<ModuleId, ()>::new();
+    let indexes = self
+      .g
+      .node_weights()
+      .filter(|module| module.is_async)
+      .map(|module| (module.id.clone(), graph.add_node(module.id.clone())))
+      .collect::<HashMap<_, _>>();
+
+    for (module_id, from) in &indexes {
+      for (dep, edge) in self.dependencies(module_id) {
+        if edge.is_dynamic() {
+          continue;
+        }
+
+        if let Some(to) = indexes.get(&dep) {
+          graph.add_edge(*from, *to, ());
+        }
+      }
+    }
+
+    let graph = &graph;
+
+    tarjan_scc(graph)
+      .into_iter()
+      .enumerate()
+      .flat_map(|(i, component)| {
+        component
+          .into_iter()
+          .map(move |node| (graph[node].clone(), i))
+      })
+      .collect()
+  }
+
+  /// return true if `to` can be reached from `from` through the static dependencies
+  pub fn is_statically_reachable(&self, from: &ModuleId, to: &ModuleId) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![from.clone()];
+
+    while let Some(module_id) = stack.pop() {
+      if &module_id == to {
+        return true;
+      }
+
+      if !visited.insert(module_id.clone()) {
+        continue;
+      }
+
+      for (dep, edge) in self.dependencies(&module_id) {
+        if !edge.is_dynamic() {
+          stack.push(dep);
+        }
+      }
+    }
+
+    false
+  }
+
   pub fn internal_graph(&self) -> &StableDiGraph<Module, ModuleGraphEdge> {
     &self.g
   }
@@ -534,12 +668,21 @@ impl Default for ModuleGraph {
   }
 }
 
+fn worker_entry_name(module_id: &ModuleId) -> String {
+  let stem = Path::new(module_id.relative_path())
+    .file_stem()
+    .map(|s| s.to_string_lossy().to_string())
+    .unwrap_or_default();
+
+  format!("{}_{}", stem, module_id.hash())
+}
+
 #[cfg(test)]
 mod tests {
   use hashbrown::HashMap;
 
   use crate::{
-    module::{Module, ModuleId},
+    module::{Module, ModuleId, ModuleMetaData, ScriptModuleMetaData},
     plugin::ResolveKind,
   };
 
@@ -686,4 +829,64 @@ mod tests {
     assert!(!graph.has_edge(&"A".into(), &"D".into()));
     assert!(graph.has_edge(&"B".into(), &"D".into()));
   }
+
+  #[test]
+  fn update_async_modules() {
+    let mut graph = construct_test_module_graph();
+    graph.module_mut(&"C".into()).unwrap().meta = ModuleMetaData::Script(ScriptModuleMetaData {
+      top_level_await: true,
+      ..Default::default()
+    });
+
+    graph.update_async_modules();
+
+    let mut async_modules = graph
+      .modules()
+      .into_iter()
+      .filter(|m| m.is_async)
+      .map(|m| m.id.clone())
+      .collect::<Vec<_>>();
+    async_modules.sort();
+    // A imports C and F imports A statically, D only imports F dynamically so it is not async
+    assert_eq!(
+      async_modules,
+      vec!["A".into(), "C".into(), "F".into()] as Vec<ModuleId>
+    );
+  }
+
+  #[test]
+  fn async_module_cycles() {
+    let mut graph = construct_test_module_graph();
+    graph.module_mut(&"C".into()).unwrap().meta = ModuleMetaData::Script(ScriptModuleMetaData {
+      top_level_await: true,
+      ..Default::default()
+    });
+    graph
+      .add_edge_item(
+        &"C".into(),
+        &"A".into(),
+        ModuleGraphEdgeDataItem {
+          source: "./A".to_string(),
+          kind: ResolveKind::Import,
+          order: 1,
+        },
+      )
+      .unwrap();
+    graph.update_async_modules();
+
+    let cycles = graph.async_module_cycles();
+    // A -> C -> A is a cycle, F imports A but is not in the cycle
+    assert_eq!(cycles.len(), 3);
+    assert_eq!(cycles[&"A".into()], cycles[&"C".into()]);
+    assert_ne!(cycles[&"A".into()], cycles[&"F".into()]);
+  }
+
+  #[test]
+  fn is_statically_reachable() {
+    let graph = construct_test_module_graph();
+
+    assert!(graph.is_statically_reachable(&"F".into(), &"C".into()));
+    assert!(!graph.is_statically_reachable(&"C".into(), &"F".into()));
+    assert!(!graph.is_statically_reachable(&"B".into(), &"G".into()));
+  }
 }
@@ -1,4 +1,4 @@
-use std::hash::Hash;
+use std::{hash::Hash, path::Path};
 
 use hashbrown::{HashMap, HashSet};
 use petgraph::{
@@ -137,6 +137,17 @@ impl WatchGraph {
     result.into_iter().collect()
   }
 
+  /// the watched directories containing `path`, e.g. the base directories of `import.meta.glob` patterns
+  pub fn ancestor_dirs(&self, path: &str) -> Vec<&String> {
+    let path = Path::new(path);
+
+    self
+      .id_index_map
+      .keys()
+      .filter(|node| path != Path::new(node) && path.starts_with(node))
+      .collect()
+  }
+
   pub fn has_module(&self, module_id: &String) -> bool {
     self.id_index_map.contains_key(module_id)
   }
@@ -211,4 +222,18 @@ mod tests {
     r.sort();
     assert_eq!(r, [&"a".to_string(), &"v_c".to_string()])
   }
+
+  #[test]
+  fn ancestor_dirs() {
+    let mut watch_graph = WatchGraph::new();
+    let a: String = "/root/a.ts".into();
+    let pages: String = "/root/pages".into();
+    watch_graph.add_node(a.clone());
+    watch_graph.add_node(pages.clone());
+    watch_graph.add_edge(&a, &pages).unwrap();
+
+    assert_eq!(watch_graph.ancestor_dirs("/root/pages/b.ts"), vec![&pages]);
+    assert!(watch_graph.ancestor_dirs("/root/pages").is_empty());
+    assert!(watch_graph.ancestor_dirs("/root/pages-b.ts").is_empty());
+  }
 }
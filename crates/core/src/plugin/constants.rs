@@ -1 +1,4 @@
 pub const PLUGIN_BUILD_STAGE_META_RESOLVE_KIND: &str = "__PLUGIN_BUILD_STAGE_META_RESOLVE_KIND";
+/// [super::ResolveKind::Custom] of web workers, e.g. `new Worker(new URL('./worker.js', import.meta.url))`.
+/// It's dynamic so the worker is bundled as a separate module group.
+pub const WORKER_RESOLVE_KIND: &str = "dynamic:worker";
@@ -213,6 +213,8 @@ pub enum ResolveKind {
   DynamicImport,
   /// cjs require, e.g. `require('./a')`
   Require,
+  /// asset referenced by url, e.g. `new URL('./a.png', import.meta.url)`
+  ImportMetaUrl,
   /// @import of css, e.g. @import './a.css'
   CssAtImport,
   /// url() of css, e.g. url('./a.png')
@@ -223,7 +225,7 @@ pub enum ResolveKind {
   LinkHref,
   /// Hmr update
   HmrUpdate,
-  /// Custom ResolveKind, e.g. `const worker = new Worker(new Url("worker.js"))` of a web worker
+  /// Custom ResolveKind, e.g. `const worker = new Worker(new Url("worker.js"))` of a web worker, see [constants::WORKER_RESOLVE_KIND]
   Custom(String),
 }
 
@@ -234,6 +236,11 @@ impl ResolveKind {
     matches!(self, ResolveKind::DynamicImport)
       || matches!(self, ResolveKind::Custom(c) if c.starts_with("dynamic:"))
   }
+
+  /// true if self is a web worker, e.g. `new Worker(new URL('./worker.js', import.meta.url))`
+  pub fn is_worker(&self) -> bool {
+    matches!(self, ResolveKind::Custom(c) if c == constants::WORKER_RESOLVE_KIND)
+  }
 }
 
 impl From<&str> for ResolveKind {
@@ -1,6 +1,7 @@
 use std::sync::{Arc, RwLock};
 
 use hashbrown::{HashMap, HashSet};
+use serde::Serialize;
 
 
 use crate::{plugin::PluginAnalyzeDepsHookResultEntry, module::ModuleId};
@@ -12,6 +13,7 @@ pub struct RecordManager {
   process_map: Arc<RwLock<HashMap<String, Vec<ModuleRecord>>>>,
   analyze_deps_map: Arc<RwLock<HashMap<String, Vec<AnalyzeDepsRecord>>>>,
   resource_pot_map: Arc<RwLock<HashMap<String, Vec<ResourcePotRecord>>>>,
+  tree_shake_records: Arc<RwLock<Vec<TreeShakeRecord>>>,
 }
 
 impl RecordManager {
@@ -22,6 +24,7 @@ impl RecordManager {
       process_map: Arc::new(RwLock::new(HashMap::new())),
       analyze_deps_map: Arc::new(RwLock::new(HashMap::new())),
       resource_pot_map: Arc::new(RwLock::new(HashMap::new())),
+      tree_shake_records: Arc::new(RwLock::new(Vec::new())),
     }
   }
 
@@ -80,6 +83,12 @@ impl RecordManager {
     }
   }
 
+  /// the records of the previous tree shaking are replaced
+  pub fn set_tree_shake_records(&self, records: Vec<TreeShakeRecord>) {
+    let mut tree_shake_records = self.tree_shake_records.write().unwrap();
+    *tree_shake_records = records;
+  }
+
   pub fn get_resolve_records(&self) -> Vec<String> {
     let resolve_id_map = self.resolve_id_map.read().unwrap();
     let mut resolve_id_set = HashSet::new();
@@ -124,6 +133,11 @@ impl RecordManager {
       None => Vec::new(),
     }
   }
+
+  pub fn get_tree_shake_records(&self) -> Vec<TreeShakeRecord> {
+    let tree_shake_records = self.tree_shake_records.read().unwrap();
+    tree_shake_records.clone()
+  }
 }
 
 impl Default for RecordManager {
@@ -162,4 +176,26 @@ pub struct ResourcePotRecord {
   pub hook: String,
   pub modules: Vec<ModuleId>,
   pub resources: Vec<String>
-}
\ No newline at end of file
+}
+
+/// Why a module is retained after tree shaking
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeShakeRecord {
+  pub module_id: String,
+  pub reasons: Vec<TreeShakeRetainedReason>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum TreeShakeRetainedReason {
+  /// the module is an entry module
+  Entry,
+  /// the module has side effects itself when `importer` is None, otherwise it's imported by `importer` for
+  /// side effects, e.g. `import './a'`, `import('./a')` or imported by a non script module
+  SideEffects { importer: Option<String> },
+  /// the module is used by the commonjs `importer` or required as a whole, so all its exports are preserved
+  CommonJsBailout { importer: String },
+  /// `exports` of the module are used by `importer`, `*` means all exports are used
+  UsedExports { importer: String, exports: Vec<String> },
+}
@@ -96,19 +96,25 @@ pub enum ResourceOrigin {
   ResourcePot(ResourcePotId),
   // The resource is generated by a Module. Usually by static assets like images.
   Module(ModuleId),
+  // The resource is generated by the compiler itself, like the manifest.
+  Compiler,
 }
 
 impl ResourceOrigin {
   pub fn as_resource_pot(&self) -> &ResourcePotId {
     match self {
       ResourceOrigin::ResourcePot(id) => id,
-      ResourceOrigin::Module(_) => panic!("ResourceOrigin is not ResourceOrigin::ResourcePot"),
+      ResourceOrigin::Module(_) | ResourceOrigin::Compiler => {
+        panic!("ResourceOrigin is not ResourceOrigin::ResourcePot")
+      }
     }
   }
 
   pub fn as_module(&self) -> &ModuleId {
     match self {
-      ResourceOrigin::ResourcePot(_) => panic!("ResourceOrigin is not ResourceOrigin::Module"),
+      ResourceOrigin::ResourcePot(_) | ResourceOrigin::Compiler => {
+        panic!("ResourceOrigin is not ResourceOrigin::Module")
+      }
       ResourceOrigin::Module(id) => id,
     }
   }
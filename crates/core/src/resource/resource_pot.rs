@@ -10,7 +10,7 @@ use swc_css_ast::Stylesheet;
 use swc_ecma_ast::Module as SwcModule;
 use swc_html_ast::Document;
 
-use crate::module::{module_group::ModuleGroupId, ModuleId, ModuleType};
+use crate::module::{module_group::ModuleGroupId, CommentsMetaData, ModuleId, ModuleType};
 
 #[cache_item]
 pub struct ResourcePot {
@@ -221,6 +221,8 @@ impl ResourcePotMetaData {
 #[cache_item]
 pub struct JsResourcePotMetaData {
   pub ast: SwcModule,
+  /// the merged comments of the modules in this resource pot
+  pub comments: CommentsMetaData,
 }
 
 #[cache_item]
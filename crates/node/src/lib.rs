@@ -293,7 +293,9 @@ impl JsCompiler {
     let watch_graph = context.watch_graph.read();
     let module_id = ModuleId::new(&resolved_path, "", &context.config.root);
 
-    module_graph.has_module(&module_id) || watch_graph.has_module(&resolved_path)
+    module_graph.has_module(&module_id)
+      || watch_graph.has_module(&resolved_path)
+      || !watch_graph.ancestor_dirs(&resolved_path).is_empty()
   }
 
   #[napi]
@@ -435,6 +437,15 @@ impl JsCompiler {
       .collect();
     js_resource_pot_records
   }
+
+  /// the reasons why the modules are retained after tree shaking, serialized as json
+  #[napi]
+  pub fn get_tree_shake_report(&self) -> String {
+    let context = self.compiler.context();
+    let record_manager = &context.record_manager;
+    let tree_shake_records = record_manager.get_tree_shake_records();
+    farmfe_core::serde_json::to_string(&tree_shake_records).unwrap()
+  }
 }
 
 pub struct FsWatcher {
@@ -16,5 +16,5 @@ pub fn farm_swc_parse_module(
   target: EsVersion,
   cm: Arc<SourceMap>,
 ) -> Result<SwcModule> {
-  parse_module(id, content, syntax, target, cm)
+  parse_module(id, content, syntax, target, cm).map(|result| result.ast)
 }
@@ -14,7 +14,7 @@ use farmfe_core::{
 };
 use farmfe_toolkit::{
   css::codegen_css_stylesheet,
-  script::{parse_module, swc_try_with::try_with},
+  script::{parse_module, swc_try_with::try_with, ParseScriptModuleResult},
   swc_ecma_transforms_base::resolver,
   swc_ecma_visit::VisitMutWith,
 };
@@ -56,7 +56,7 @@ pub fn transform_css_resource_pot(
       &context.meta.script.globals,
       || {
         let css_code = wrapper_style_load(&css_code, module.id.to_string(), &css_deps);
-        let mut ast = parse_module(
+        let ParseScriptModuleResult { mut ast, comments } = parse_module(
           &module.id.to_string(),
           &css_code,
           Syntax::default(),
@@ -75,6 +75,9 @@ pub fn transform_css_resource_pot(
           unresolved_mark: unresolved_mark.as_u32(),
           module_system: ModuleSystem::EsModule,
           hmr_accepted: true,
+          comments: comments.into(),
+          dynamic_import_magic_comments: vec![],
+          top_level_await: false,
         });
 
         module.module_type = ModuleType::Js;
@@ -4,11 +4,15 @@ use farmfe_core::{
   config::Config,
   context::CompilationContext,
   error::Result,
+  module::{CommentsMetaData, CommentsMetaDataItem},
   plugin::Plugin,
   resource::resource_pot::{
     JsResourcePotMetaData, ResourcePot, ResourcePotMetaData, ResourcePotType,
   },
-  swc_common::Mark,
+  swc_common::{
+    comments::{Comment, SingleThreadedComments},
+    Mark, Spanned,
+  },
   swc_ecma_ast::Program,
 };
 use farmfe_toolkit::{
@@ -40,17 +44,20 @@ impl FarmPluginMinify {
       context.meta.script.cm.clone(),
       &context.meta.script.globals,
       || {
-        let meta = resource_pot.take_meta();
+        let JsResourcePotMetaData { ast, comments } = resource_pot.take_meta().take_js();
+        // the license comments are collected before minifying, as the nodes they are attached to may be removed by the minifier
+        let license_comments = collect_license_comments(&comments);
+        let comments: SingleThreadedComments = comments.into();
         let unresolved_mark = Mark::new();
         let top_level_mark = Mark::new();
 
-        let mut program = Program::Module(meta.take_js().ast);
+        let mut program = Program::Module(ast);
         program = program.fold_with(&mut resolver(unresolved_mark, top_level_mark, false));
 
         let mut program = optimize(
           program,
           context.meta.script.cm.clone(),
-          None,
+          Some(&comments),
           None,
           &MinifyOptions {
             // TODO: make it configurable
@@ -63,15 +70,25 @@ impl FarmPluginMinify {
             top_level_mark,
           },
         );
-        // TODO support comments
-        program = program.fold_with(&mut fixer(None));
+        program = program.fold_with(&mut fixer(Some(&comments)));
 
         let ast = match program {
           Program::Module(ast) => ast,
           _ => unreachable!(),
         };
+        // only the license comments are preserved in the minified output, they are moved to the top of the resource pot
+        let comments = match ast.body.first() {
+          Some(item) if !license_comments.is_empty() => CommentsMetaData {
+            leading: vec![CommentsMetaDataItem {
+              byte_pos: item.span().lo,
+              comment: license_comments,
+            }],
+            trailing: vec![],
+          },
+          _ => CommentsMetaData::default(),
+        };
 
-        resource_pot.meta = ResourcePotMetaData::Js(JsResourcePotMetaData { ast });
+        resource_pot.meta = ResourcePotMetaData::Js(JsResourcePotMetaData { ast, comments });
       },
     )
   }
@@ -109,6 +126,24 @@ impl FarmPluginMinify {
   }
 }
 
+/// `/*! ... */`, `@license` and `@preserve` comments must be kept for license attribution
+fn is_license_comment(comment: &Comment) -> bool {
+  comment.text.starts_with('!')
+    || comment.text.contains("@license")
+    || comment.text.contains("@preserve")
+}
+
+fn collect_license_comments(comments: &CommentsMetaData) -> Vec<Comment> {
+  comments
+    .leading
+    .iter()
+    .chain(comments.trailing.iter())
+    .flat_map(|item| item.comment.iter())
+    .filter(|comment| is_license_comment(comment))
+    .cloned()
+    .collect()
+}
+
 impl Plugin for FarmPluginMinify {
   fn name(&self) -> &'static str {
     "FarmPluginMinify"
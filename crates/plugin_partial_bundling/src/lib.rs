@@ -3,13 +3,14 @@ use std::{collections::VecDeque, sync::Arc};
 use farmfe_core::{
   config::Config,
   context::CompilationContext,
+  error::CompilationError,
   hashbrown::{HashMap, HashSet},
   module::{
     module_graph::ModuleGraph,
-    module_group::{ModuleGroup, ModuleGroupGraph},
-    ModuleId, ModuleType,
+    module_group::{ModuleGroup, ModuleGroupGraph, ModuleGroupId},
+    ModuleId, ModuleMetaData, ModuleType,
   },
-  plugin::{Plugin, PluginHookContext},
+  plugin::{Plugin, PluginHookContext, ResolveKind},
   resource::resource_pot::{ResourcePot, ResourcePotId},
 };
 use farmfe_toolkit::hash::sha256;
@@ -60,6 +61,36 @@ impl Plugin for FarmPluginPartialBundling {
 
     // First, generate ModuleBucket
     let mut module_bucket_map = HashMap::<ModuleBucketId, ModuleBucket>::new();
+    let mut module_group_chunk_names = HashMap::<ModuleGroupId, Option<String>>::new();
+    let mut redundant_module_groups = HashMap::<ModuleGroupId, bool>::new();
+    // the iife and umd outputs are single scripts that can not load other chunks, all modules are bundled into the chunk of the entry.
+    // The entry is read from the config as the worker entries are also in `module_graph.entries`
+    let single_script_name = if context.config.output.format.is_single_script() {
+      if let Some(worker) = module_graph
+        .entries
+        .keys()
+        .find(|entry| module_graph.is_worker_entry(entry))
+      {
+        return Err(CompilationError::GenericError(format!(
+          "the iife and umd formats do not support web workers, but {} is used as a worker",
+          worker.to_string()
+        )));
+      }
+
+      // the factory of the single script is a synchronous function
+      if let Some(module) = module_graph.modules().into_iter().find(|module| {
+        matches!(&module.meta, ModuleMetaData::Script(meta) if meta.top_level_await)
+      }) {
+        return Err(CompilationError::GenericError(format!(
+          "the iife and umd formats do not support top level await, but it's used in {}",
+          module.id.to_string()
+        )));
+      }
+
+      context.config.input.keys().next().cloned()
+    } else {
+      None
+    };
 
     for module_id in modules {
       let module = module_graph.module(module_id).unwrap();
@@ -68,6 +99,11 @@ impl Plugin for FarmPluginPartialBundling {
         continue;
       }
 
+      // Skip the assets only referenced by `new URL('./a.png', import.meta.url)`, the url is replaced by the emitted asset
+      if is_import_meta_url_only(module_id, &module_graph) {
+        continue;
+      }
+
       if module.resource_pot.is_some() {
         panic!(
           "Module {:?} has already been assigned to a resource pot: {:?}.",
@@ -78,18 +114,34 @@ impl Plugin for FarmPluginPartialBundling {
 
       let add_module = |module_bucket_id: ModuleBucketId,
                         module_bucket_map: &mut HashMap<ModuleBucketId, ModuleBucket>,
-                        customized_by_user: bool| {
+                        customized_by_user: bool,
+                        chunk_name: Option<String>| {
         if module_bucket_map.contains_key(&module_bucket_id) {
           let module_bucket = module_bucket_map.get_mut(&module_bucket_id).unwrap();
           module_bucket.add_module(module_id.clone());
         } else {
-          let mut module_bucket =
-            ModuleBucket::new(module_bucket_id.clone(), HashSet::new(), customized_by_user);
+          let mut module_bucket = ModuleBucket::new(
+            module_bucket_id.clone(),
+            HashSet::new(),
+            customized_by_user,
+            chunk_name,
+          );
           module_bucket.add_module(module_id.clone());
           module_bucket_map.insert(module_bucket_id, module_bucket);
         }
       };
 
+      if let Some(entry_name) = &single_script_name {
+        let bucket_id: ModuleBucketId = format!("chunkName:{}", entry_name).into();
+        add_module(
+          bucket_id,
+          &mut module_bucket_map,
+          false,
+          Some(entry_name.clone()),
+        );
+        continue;
+      }
+
       let mut module_in_custom_buckets = false;
 
       for bucket_config in &context.config.partial_bundling.module_buckets {
@@ -101,7 +153,7 @@ impl Plugin for FarmPluginPartialBundling {
           module_in_custom_buckets = true;
 
           let bucket_id: ModuleBucketId = bucket_name.clone().into();
-          add_module(bucket_id, &mut module_bucket_map, true);
+          add_module(bucket_id, &mut module_bucket_map, true, None);
         }
       }
 
@@ -110,9 +162,39 @@ impl Plugin for FarmPluginPartialBundling {
         continue;
       }
 
-      let module_group_ids = module.module_groups.clone();
+      // The module group of a module that is both statically and dynamically imported is ignored, see [is_redundant_module_group]
+      let module_group_ids = module
+        .module_groups
+        .iter()
+        .filter(|module_group_id| {
+          !*redundant_module_groups
+            .entry((*module_group_id).clone())
+            .or_insert_with(|| is_redundant_module_group(module_group_id, &module_graph))
+        })
+        .cloned()
+        .collect::<HashSet<_>>();
+
+      // The module is only in the dynamic module groups named by the same chunk name, e.g. `import(/* webpackChunkName: "settings" */ './settings')`
+      let chunk_names = module_group_ids
+        .iter()
+        .map(|module_group_id| {
+          module_group_chunk_names
+            .entry(module_group_id.clone())
+            .or_insert_with(|| module_group_chunk_name(module_group_id, &module_graph))
+            .clone()
+        })
+        .collect::<HashSet<_>>();
+
+      if chunk_names.len() == 1 {
+        if let Some(Some(chunk_name)) = chunk_names.into_iter().next() {
+          let bucket_id: ModuleBucketId = format!("chunkName:{}", chunk_name).into();
+          add_module(bucket_id, &mut module_bucket_map, false, Some(chunk_name));
+          continue;
+        }
+      }
+
       let module_bucket_id = ModuleBucketId::new(module_group_ids);
-      add_module(module_bucket_id, &mut module_bucket_map, false);
+      add_module(module_bucket_id, &mut module_bucket_map, false, None);
     }
 
     // Second, for each ModuleBucket, generate ResourcePot.
@@ -123,13 +205,19 @@ impl Plugin for FarmPluginPartialBundling {
     // 4. Every Module should be only in a ResourcePot.
     // 5. TODO: auto detected the resource pot's size and split it into multiple resource pots.
 
+    // (id, named by the magic comments, module type, immutable, modules)
+    let mut pending_resource_pots = vec![];
+
     for module_bucket in module_bucket_map.values() {
       let mut rules_map = HashMap::<(ModuleType, bool), Vec<ModuleId>>::new();
 
       for module_id in module_bucket.modules() {
         let module = module_graph.module(module_id).unwrap();
 
-        let rule = if module_bucket.customized_by_user {
+        let rule = if module_bucket.chunk_name.is_some() && module.module_type.is_script() {
+          // all script modules of a named chunk are in the same resource pot
+          (ModuleType::Js, false)
+        } else if module_bucket.customized_by_user || module_bucket.chunk_name.is_some() {
           (module.module_type.clone(), false)
         } else {
           (module.module_type.clone(), module.immutable)
@@ -148,33 +236,77 @@ impl Plugin for FarmPluginPartialBundling {
         let mut sorted_module_ids = module_ids.iter().map(|m| m.to_string()).collect::<Vec<_>>();
         sorted_module_ids.sort();
 
-        let id = sha256(
-          format!(
-            "{}-{}-{}-{}",
-            module_bucket.id.to_string(),
-            module_type.to_string(),
-            sorted_module_ids.join("_"),
-            immutable
-          )
-          .as_bytes(),
-          8,
-        );
-        let mut resource_pot = ResourcePot::new(ResourcePotId::new(id), module_type.into());
+        let id = match &module_bucket.chunk_name {
+          // the resource pot of a named chunk keeps the chunk name, e.g. `settings.js`
+          Some(chunk_name) if module_type == ModuleType::Js => chunk_name.clone(),
+          Some(chunk_name) => format!("{}_{}", chunk_name, module_type.to_string()),
+          None => sha256(
+            format!(
+              "{}-{}-{}-{}",
+              module_bucket.id.to_string(),
+              module_type.to_string(),
+              sorted_module_ids.join("_"),
+              immutable
+            )
+            .as_bytes(),
+            8,
+          ),
+        };
+        pending_resource_pots.push((
+          id,
+          module_bucket.chunk_name.is_some(),
+          module_type,
+          immutable,
+          module_ids,
+        ));
+      }
+    }
 
-        resource_pot.immutable = immutable;
+    // The named resource pots must not take the names of the entries or the other resource pots, e.g. `import(/* webpackChunkName: "index" */ './about')`
+    // is renamed to `index_1`. The entry of a single script is named after itself.
+    let mut taken_names = pending_resource_pots
+      .iter()
+      .filter(|(_, named, ..)| !named)
+      .map(|(id, ..)| id.clone())
+      .chain(module_graph.entries.values().cloned())
+      .collect::<HashSet<_>>();
+    pending_resource_pots.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (id, named, _, _, module_ids) in &mut pending_resource_pots {
+      if !*named {
+        continue;
+      }
 
-        for module_id in module_ids {
-          let module = module_graph.module_mut(&module_id).unwrap();
-          module.resource_pot = Some(resource_pot.id.clone());
-          resource_pot.add_module(module_id.clone());
+      let entry_name = module_ids
+        .iter()
+        .find_map(|module_id| module_graph.entries.get(module_id));
+      let name = id.clone();
+      let mut i = 1;
 
-          if module_graph.entries.contains_key(&module_id) {
-            resource_pot.entry_module = Some(module_id.clone());
-          }
-        }
+      while taken_names.contains(id) && entry_name != Some(&*id) {
+        *id = format!("{}_{}", name, i);
+        i += 1;
+      }
+
+      taken_names.insert(id.clone());
+    }
+
+    for (id, _, module_type, immutable, module_ids) in pending_resource_pots {
+      let mut resource_pot = ResourcePot::new(ResourcePotId::new(id), module_type.into());
 
-        resource_pots.push(resource_pot);
+      resource_pot.immutable = immutable;
+
+      for module_id in module_ids {
+        let module = module_graph.module_mut(&module_id).unwrap();
+        module.resource_pot = Some(resource_pot.id.clone());
+        resource_pot.add_module(module_id.clone());
+
+        if module_graph.entries.contains_key(&module_id) {
+          resource_pot.entry_module = Some(module_id.clone());
+        }
       }
+
+      resource_pots.push(resource_pot);
     }
 
     Ok(Some(resource_pots))
@@ -196,6 +328,11 @@ pub fn module_group_graph_from_entries(
   let mut visited = HashSet::new();
 
   for entry in entries.clone() {
+    // the entry may be a dynamic dependency of other entries too, e.g. a web worker
+    if visited.contains(&entry) {
+      continue;
+    }
+
     let (group, dynamic_dependencies) = module_group_from_entry(&entry, module_graph);
     edges.extend(
       dynamic_dependencies
@@ -238,6 +375,106 @@ pub fn module_group_graph_from_entries(
   module_group_graph
 }
 
+/// the module is not an entry and all of its dependents only reference it by `new URL('./xxx', import.meta.url)`
+fn is_import_meta_url_only(module_id: &ModuleId, module_graph: &ModuleGraph) -> bool {
+  if module_graph.entries.contains_key(module_id) {
+    return false;
+  }
+
+  let dependents = module_graph.dependents(module_id);
+
+  !dependents.is_empty()
+    && dependents.iter().all(|(_, edge)| {
+      edge
+        .iter()
+        .all(|item| item.kind == ResolveKind::ImportMetaUrl)
+    })
+}
+
+/// get the chunk name of a dynamic module group from the magic comments of its dynamic importers, e.g. `import(/* webpackChunkName: "settings" */ './settings')`.
+/// The smallest one is used when the dynamic importers specify different chunk names, so the result is stable.
+fn module_group_chunk_name(
+  module_group_id: &ModuleGroupId,
+  module_graph: &ModuleGraph,
+) -> Option<String> {
+  let mut chunk_names = vec![];
+
+  for (dependent, edge) in module_graph.dependents(module_group_id) {
+    if !edge.is_dynamic() {
+      continue;
+    }
+
+    if let ModuleMetaData::Script(meta) = &module_graph.module(&dependent).unwrap().meta {
+      chunk_names.extend(edge.iter().filter_map(|item| {
+        meta
+          .dynamic_import_magic_comments(&item.source)
+          .and_then(|magic_comments| magic_comments.chunk_name.as_deref())
+          .map(sanitize_chunk_name)
+      }));
+    }
+  }
+
+  chunk_names.sort();
+  chunk_names.into_iter().next()
+}
+
+/// The chunk name is used as the name of the resource pot and the output file, the characters other than `[a-zA-Z0-9_-]` are replaced by `_`,
+/// e.g. `../settings` => `___settings`, so it can't escape the output dir
+fn sanitize_chunk_name(chunk_name: &str) -> String {
+  let name = chunk_name
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect::<String>();
+
+  if name.is_empty() {
+    "_".to_string()
+  } else {
+    name
+  }
+}
+
+/// A dynamic module group is redundant when its entry module is also statically imported by all the module groups that import it dynamically, for example:
+/// ```js
+/// // index.js
+/// import { format } from './format';
+/// import('./format').then(({ format }) => {});
+/// ```
+/// The module is already loaded together with its importers, so no dynamic resource is generated for it,
+/// and the runtime resolves the dynamic import from the loaded module.
+fn is_redundant_module_group(module_group_id: &ModuleGroupId, module_graph: &ModuleGraph) -> bool {
+  if module_graph.entries.contains_key(module_group_id) {
+    return false;
+  }
+
+  let entry = module_graph.module(module_group_id).unwrap();
+  let other_module_groups = entry
+    .module_groups
+    .iter()
+    .filter(|id| *id != module_group_id)
+    .collect::<HashSet<_>>();
+
+  !other_module_groups.is_empty()
+    && module_graph
+      .dependents(module_group_id)
+      .into_iter()
+      .filter(|(_, edge)| edge.is_dynamic())
+      .all(|(dependent, _)| {
+        module_graph
+          .module(&dependent)
+          .unwrap()
+          .module_groups
+          .iter()
+          .filter(|id| *id != module_group_id)
+          .all(|id| other_module_groups.contains(id))
+      })
+}
+
 /// get module group start from a entry. return (module group, dynamic dependencies)
 /// traverse the module graph using bfs, stop when reach a dynamic dependency
 fn module_group_from_entry(
@@ -7,19 +7,29 @@ use farmfe_core::{
 /// By default, a ModuleBucket is generated by following rule:
 /// The modules which are in the same ModuleGroups are in the same ModuleBucket. For example, if there are two ModuleGroups A and B. if module c is in ModuleGroup A and ModuleGroup B, module d is only in ModuleGroup A, then c and d are in the different ModuleBucket.
 ///
+/// The modules which are only in the dynamic ModuleGroups named by the same `webpackChunkName` are in the same ModuleBucket too.
+///
 /// A ModuleBucket can generate multiple ResourcePots.
 #[derive(Debug)]
 pub struct ModuleBucket {
   pub id: ModuleBucketId,
   pub customized_by_user: bool,
+  /// the chunk name specified by the magic comments of the dynamic imports, e.g. `import(/* webpackChunkName: "settings" */ './settings')`
+  pub chunk_name: Option<String>,
   modules: HashSet<ModuleId>,
 }
 
 impl ModuleBucket {
-  pub fn new(id: ModuleBucketId, modules: HashSet<ModuleId>, customized_by_user: bool) -> Self {
+  pub fn new(
+    id: ModuleBucketId,
+    modules: HashSet<ModuleId>,
+    customized_by_user: bool,
+    chunk_name: Option<String>,
+  ) -> Self {
     Self {
       id,
       customized_by_user,
+      chunk_name,
       modules,
     }
   }
@@ -1,12 +1,8 @@
 use farmfe_core::{
-  config::{
-    config_regex::ConfigRegex,
-    preset_env::{PresetEnvConfig, PresetEnvConfigObj},
-    Config,
-  },
+  config::{config_regex::ConfigRegex, preset_env::PresetEnvConfig, Config},
   plugin::Plugin,
   serde_json,
-  swc_common::{comments::NoopComments, Mark},
+  swc_common::{comments::SingleThreadedComments, Mark},
 };
 use farmfe_toolkit::{
   preset_env_base::query::Query,
@@ -26,33 +22,23 @@ pub struct FarmPluginPolyfill {
 
 impl FarmPluginPolyfill {
   pub fn new(config: &Config) -> Self {
-    let (config, include, exclude, assumptions) = match &*config.preset_env {
-      PresetEnvConfig::Bool(_) => {
-        let PresetEnvConfigObj {
-          include,
-          exclude,
-          options: _,
-          assumptions: _,
-        } = PresetEnvConfigObj::default();
-
-        (
-          swc_ecma_preset_env::Config {
-            mode: Some(Mode::Usage),
-            core_js: Some(Version {
-              major: 3,
-              minor: 30,
-              patch: 1,
-            }),
-            targets: Some(Targets::Query(Query::Single(
-              "> 0.25%, not dead".to_string(),
-            ))),
-            ..Default::default()
-          },
-          include,
-          exclude,
-          Default::default(),
-        )
-      }
+    let (include, exclude) = config.preset_env.include_exclude();
+    let (config, assumptions) = match &*config.preset_env {
+      PresetEnvConfig::Bool(_) => (
+        swc_ecma_preset_env::Config {
+          mode: Some(Mode::Usage),
+          core_js: Some(Version {
+            major: 3,
+            minor: 30,
+            patch: 1,
+          }),
+          targets: Some(Targets::Query(Query::Single(
+            "> 0.25%, not dead".to_string(),
+          ))),
+          ..Default::default()
+        },
+        Default::default(),
+      ),
       PresetEnvConfig::Obj(obj) => {
         let options = &obj.options;
 
@@ -69,12 +55,7 @@ impl FarmPluginPolyfill {
         ))));
         let user_assumption: Assumptions =
           serde_json::from_value(*obj.assumptions.clone()).unwrap();
-        (
-          user_config,
-          obj.include.clone(),
-          obj.exclude.clone(),
-          user_assumption,
-        )
+        (user_config, user_assumption)
       }
     };
 
@@ -116,13 +97,13 @@ impl Plugin for FarmPluginPolyfill {
       || {
         let unresolved_mark = Mark::from_u32(param.meta.as_script().unresolved_mark);
         let mut ast = param.meta.as_script_mut().take_ast();
+        let comments: SingleThreadedComments = param.meta.as_script_mut().take_comments().into();
         // TODO: store feature flags in module meta and use them when transform the module system
         let mut feature_flag = FeatureFlag::empty();
 
         ast = ast.fold_with(&mut preset_env(
           unresolved_mark,
-          // TODO: support comments
-          None as Option<NoopComments>,
+          Some(&comments),
           self.config.clone(),
           self.assumptions,
           &mut feature_flag,
@@ -130,6 +111,7 @@ impl Plugin for FarmPluginPolyfill {
         ast.visit_mut_with(&mut inject_helpers(unresolved_mark));
 
         param.meta.as_script_mut().set_ast(ast);
+        param.meta.as_script_mut().set_comments(comments.into());
       },
     )?;
 
@@ -3,10 +3,11 @@
 use std::{collections::HashMap, sync::Arc};
 
 use farmfe_core::{
-  config::{config_regex::ConfigRegex, Config, PartialBundlingModuleBucketsConfig},
+  config::{config_regex::ConfigRegex, Config, ModuleFormat, PartialBundlingModuleBucketsConfig},
   context::CompilationContext,
   error::CompilationError,
   module::{ModuleMetaData, ModuleSystem, ModuleType},
+  parking_lot::RwLock,
   plugin::{
     Plugin, PluginAnalyzeDepsHookParam, PluginAnalyzeDepsHookResultEntry, PluginHookContext,
     PluginLoadHookParam, PluginLoadHookResult, PluginResolveHookParam, PluginResolveHookResult,
@@ -25,12 +26,18 @@ use farmfe_core::{
 };
 use farmfe_toolkit::{
   fs::read_file_utf8,
-  script::{codegen_module, module_system_from_deps, module_type_from_id, parse_module},
+  script::{
+    codegen_module, module_system_from_deps, module_type_from_id, parse_module,
+    swc_try_with::try_with,
+  },
   swc_ecma_parser::Syntax,
 };
 
 use insert_runtime_plugins::insert_runtime_plugins;
-use render_resource_pot::*;
+use render_resource_pot::{
+  library::{render_library_resource_pot, LibraryLinker},
+  *,
+};
 
 const RUNTIME_SUFFIX: &str = ".farm-runtime";
 
@@ -45,8 +52,13 @@ pub mod render_resource_pot;
 /// when entry is script, the runtime will be injected into the entry module's head, makes sure the runtime execute before all other code.
 ///
 /// All runtime module (including the runtime core and its plugins) will be suffixed as `.farm-runtime` to distinguish with normal script modules.
+///
+/// When `output.library` is enabled, the runtime is not injected and the resource pots are rendered as native es modules, see [render_resource_pot::library].
 /// ```
-pub struct FarmPluginRuntime {}
+pub struct FarmPluginRuntime {
+  /// the imports and exports between the modules of the library, resolved in `process_resource_pots`
+  library_linker: RwLock<Option<Arc<LibraryLinker>>>,
+}
 
 impl Plugin for FarmPluginRuntime {
   fn name(&self) -> &str {
@@ -54,15 +66,38 @@ impl Plugin for FarmPluginRuntime {
   }
 
   fn config(&self, config: &mut Config) -> farmfe_core::error::Result<Option<()>> {
+    config.resolve.alias.insert(
+      "@swc/helpers".to_string(),
+      config.runtime.swc_helpers_path.clone(),
+    );
+
+    // the iife and umd outputs wrap the library chunk of the entry
+    if config.output.format.is_single_script() {
+      if config.input.len() != 1 {
+        return Err(CompilationError::GenericError(
+          "the iife and umd formats only support a single entry".to_string(),
+        ));
+      }
+
+      config.output.library = true;
+    }
+
+    // the library is rendered as native es modules, the runtime is not needed
+    if config.output.library {
+      if config.output.format == ModuleFormat::CommonJs {
+        return Err(CompilationError::GenericError(
+          "output.library does not support the cjs format".to_string(),
+        ));
+      }
+
+      return Ok(Some(()));
+    }
+
     // runtime package entry file
     config.input.insert(
       "runtime".to_string(),
       format!("{}{}", config.runtime.path, RUNTIME_SUFFIX),
     );
-    config.resolve.alias.insert(
-      "@swc/helpers".to_string(),
-      config.runtime.swc_helpers_path.clone(),
-    );
 
     config.partial_bundling.module_buckets.insert(
       0,
@@ -255,6 +290,21 @@ impl Plugin for FarmPluginRuntime {
     resource_pots: &mut Vec<&mut ResourcePot>,
     context: &Arc<CompilationContext>,
   ) -> farmfe_core::error::Result<Option<()>> {
+    if context.config.output.library {
+      // the imports and exports between the resource pots are resolved before rendering them in parallel
+      let module_graph = context.module_graph.read();
+      let mut linker = None;
+
+      try_with(
+        context.meta.script.cm.clone(),
+        &context.meta.script.globals,
+        || linker = Some(LibraryLinker::new(&module_graph)),
+      )?;
+
+      *self.library_linker.write() = Some(Arc::new(linker.unwrap()?));
+      return Ok(Some(()));
+    }
+
     if context.meta.script.runtime_ast.read().is_some() {
       return Ok(None);
     }
@@ -263,7 +313,7 @@ impl Plugin for FarmPluginRuntime {
 
     for resource_pot in resource_pots {
       if matches!(resource_pot.resource_pot_type, ResourcePotType::Runtime) {
-        let rendered_resource_pot_ast =
+        let (rendered_resource_pot_ast, _) =
           resource_pot_to_runtime_object_lit(resource_pot, &module_graph, context)?;
 
         #[cfg(not(windows))]
@@ -277,7 +327,8 @@ impl Plugin for FarmPluginRuntime {
           Syntax::Es(context.config.script.parser.es_config),
           context.config.script.target,
           context.meta.script.cm.clone(),
-        )?;
+        )?
+        .ast;
 
         if let ModuleItem::Stmt(Stmt::Expr(ExprStmt {
           expr: box Expr::Call(CallExpr { args, .. }),
@@ -321,10 +372,21 @@ impl Plugin for FarmPluginRuntime {
     resource_pot: &mut ResourcePot,
     context: &Arc<CompilationContext>,
   ) -> farmfe_core::error::Result<Option<()>> {
+    let library_linker = self.library_linker.read().clone();
+
+    if let (Some(linker), ResourcePotType::Js) = (library_linker, &resource_pot.resource_pot_type) {
+      let module_graph = context.module_graph.read();
+      let (ast, comments) =
+        render_library_resource_pot(resource_pot, &linker, &module_graph, context)?;
+      resource_pot.meta = ResourcePotMetaData::Js(JsResourcePotMetaData { ast, comments });
+
+      return Ok(Some(()));
+    }
+
     // the runtime module and its plugins should be in the same resource pot
     if matches!(resource_pot.resource_pot_type, ResourcePotType::Js) {
       let module_graph = context.module_graph.read();
-      let rendered_resource_pot_ast =
+      let (rendered_resource_pot_ast, comments) =
         resource_pot_to_runtime_object_lit(resource_pot, &module_graph, context)?;
 
       #[cfg(not(windows))]
@@ -338,7 +400,8 @@ impl Plugin for FarmPluginRuntime {
         Syntax::Es(context.config.script.parser.es_config),
         context.config.script.target,
         context.meta.script.cm.clone(),
-      )?;
+      )?
+      .ast;
 
       if let ModuleItem::Stmt(Stmt::Expr(ExprStmt {
         expr: box Expr::Call(CallExpr { args, .. }),
@@ -351,7 +414,10 @@ impl Plugin for FarmPluginRuntime {
         };
       }
 
-      resource_pot.meta = ResourcePotMetaData::Js(JsResourcePotMetaData { ast: wrapper_ast });
+      resource_pot.meta = ResourcePotMetaData::Js(JsResourcePotMetaData {
+        ast: wrapper_ast,
+        comments,
+      });
 
       Ok(Some(()))
     } else {
@@ -379,6 +445,7 @@ impl Plugin for FarmPluginRuntime {
         context.meta.script.cm.clone(),
         None,
         context.config.minify,
+        None,
       )
       .map_err(|e| CompilationError::GenerateResourcesError {
         name: resource_pot.id.to_string(),
@@ -402,6 +469,8 @@ impl Plugin for FarmPluginRuntime {
 
 impl FarmPluginRuntime {
   pub fn new(_: &Config) -> Self {
-    Self {}
+    Self {
+      library_linker: RwLock::new(None),
+    }
   }
 }
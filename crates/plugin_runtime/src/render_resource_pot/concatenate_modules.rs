@@ -0,0 +1,863 @@
+//! Concatenate the es modules of a [ResourcePot] into the scope of their importers (scope hoisting). For example:
+//! ```js
+//! // a.js
+//! import { b } from './b';
+//! console.log(b);
+//! // b.js
+//! const value = 1;
+//! export const b = value;
+//! ```
+//! b.js is only imported by a.js, so it's concatenated into a.js:
+//! ```js
+//! const value = 1;
+//! const b = value;
+//! console.log(b);
+//! ```
+//! The concatenated modules are not wrapped or registered in the module system anymore, the imports of them are replaced by
+//! the local bindings directly. The conflicting top level bindings of the concatenated modules are renamed by hygiene later.
+
+use farmfe_core::{
+  config::{Config, Mode},
+  hashbrown::{HashMap, HashSet},
+  module::{module_graph::ModuleGraph, CommentsMetaData, ModuleId, ModuleSystem, ModuleType},
+  plugin::ResolveKind,
+  resource::resource_pot::{ResourcePot, ResourcePotType},
+  swc_common::{Mark, SyntaxContext, DUMMY_SP},
+  swc_ecma_ast::{
+    ClassDecl, Decl, DefaultDecl, ExportNamedSpecifier, ExportSpecifier, Expr, FnDecl, Id, Ident,
+    ImportDecl, ImportNamedSpecifier, ImportSpecifier, KeyValueProp, MemberProp,
+    Module as SwcModule, ModuleDecl, ModuleExportName, ModuleItem, NamedExport, Prop, PropName,
+    Stmt, Str, VarDecl, VarDeclKind, VarDeclarator,
+  },
+};
+use farmfe_toolkit::{
+  swc_ecma_utils::find_pat_ids,
+  swc_ecma_visit::{VisitMut, VisitMutWith},
+};
+
+/// The modules of a resource pot that are concatenated into the scope of their importers.
+pub struct ConcatenatedModules {
+  /// the concatenated module -> its only importer
+  importers: HashMap<ModuleId, ModuleId>,
+}
+
+/// The result of [ConcatenatedModules::concatenate]
+pub struct ConcatenatedModule {
+  pub ast: SwcModule,
+  pub comments: CommentsMetaData,
+  /// the sources of the imports that are hoisted from the concatenated modules, they are rewritten to avoid conflicts with the sources of the root module.
+  /// The value is the module id that the source resolves to.
+  pub hoisted_sources: HashMap<String, ModuleId>,
+}
+
+impl ConcatenatedModules {
+  pub fn new(resource_pot: &ResourcePot, module_graph: &ModuleGraph, config: &Config) -> Self {
+    let mut importers = HashMap::new();
+
+    // the concatenated modules are not registered in the module system, so they can not be updated by HMR
+    if !config.concatenate_modules
+      || !matches!(config.mode, Mode::Production)
+      || !matches!(resource_pot.resource_pot_type, ResourcePotType::Js)
+    {
+      return Self { importers };
+    }
+
+    let modules = resource_pot.modules().into_iter().collect::<HashSet<_>>();
+
+    for module_id in &modules {
+      if let Some(importer) = concatenated_importer(module_id, &modules, module_graph) {
+        importers.insert((*module_id).clone(), importer);
+      }
+    }
+
+    let mut concatenated_modules = Self { importers };
+
+    // a module that imports its root module is executed in the middle of the root module, and a module that should be executed before
+    // the import of a module that is not concatenated is executed after it, then they can not be concatenated
+    loop {
+      let mut unconcatenated = concatenated_modules
+        .importers
+        .keys()
+        .filter(|module_id| {
+          module_graph.is_statically_reachable(module_id, &concatenated_modules.root_of(module_id))
+        })
+        .cloned()
+        .collect::<HashSet<_>>();
+      unconcatenated.extend(concatenated_modules.executed_before_hoisted_imports(module_graph));
+
+      if unconcatenated.is_empty() {
+        break;
+      }
+
+      for module_id in unconcatenated {
+        concatenated_modules.importers.remove(&module_id);
+      }
+    }
+
+    concatenated_modules
+  }
+
+  /// true if the module is concatenated into its importer and should not be rendered separately
+  pub fn is_concatenated(&self, module_id: &ModuleId) -> bool {
+    self.importers.contains_key(module_id)
+  }
+
+  /// Concatenate the modules that are concatenated into `root` directly or indirectly with `root`.
+  /// Return None if no module is concatenated into `root`.
+  pub fn concatenate(
+    &self,
+    root: &ModuleId,
+    module_graph: &ModuleGraph,
+    config: &Config,
+  ) -> Option<ConcatenatedModule> {
+    if !self.importers.values().any(|importer| importer == root) {
+      return None;
+    }
+
+    let root_meta = module_graph.module(root).unwrap().meta.as_script();
+    let root_unresolved_mark = Mark::from_u32(root_meta.unresolved_mark);
+
+    let mut concatenator = ModuleConcatenator {
+      module_graph,
+      config,
+      concatenated_modules: self,
+      root_unresolved_ctxt: SyntaxContext::empty().apply_mark(root_unresolved_mark),
+      exports: HashMap::new(),
+      hoisted_sources: HashMap::new(),
+      comments: CommentsMetaData::default(),
+    };
+    let body = concatenator.concatenate(root, root);
+
+    Some(ConcatenatedModule {
+      ast: SwcModule {
+        span: DUMMY_SP,
+        shebang: None,
+        body,
+      },
+      comments: concatenator.comments,
+      hoisted_sources: concatenator.hoisted_sources,
+    })
+  }
+
+  fn root_of(&self, module_id: &ModuleId) -> ModuleId {
+    let mut root = module_id;
+
+    while let Some(importer) = self.importers.get(root) {
+      if importer == module_id {
+        break;
+      }
+
+      root = importer;
+    }
+
+    root.clone()
+  }
+
+  /// The imports of the modules that are not concatenated are hoisted to the top of the root module, so they are executed before all
+  /// concatenated modules. Return the concatenated modules that should be executed before such an import in es module order,
+  /// the modules concatenated into them are not returned as they are still executed in order after their importers are unconcatenated.
+  fn executed_before_hoisted_imports(&self, module_graph: &ModuleGraph) -> HashSet<ModuleId> {
+    let mut result = HashSet::new();
+
+    for root in self.importers.values().collect::<HashSet<_>>() {
+      if self.importers.contains_key(root) {
+        continue;
+      }
+
+      let mut executed = vec![];
+      let mut imported = HashSet::new();
+      self.visit_execution_order(
+        root,
+        module_graph,
+        &mut executed,
+        &mut imported,
+        &mut result,
+      );
+    }
+
+    let too_early = result.clone();
+    result.retain(|module_id| !too_early.contains(&self.importers[module_id]));
+    result
+  }
+
+  /// Visit the static dependencies of `module_id` in es module execution order, `executed` are the concatenated modules executed so far
+  /// and `imported` are the modules that are not concatenated but imported so far
+  fn visit_execution_order(
+    &self,
+    module_id: &ModuleId,
+    module_graph: &ModuleGraph,
+    executed: &mut Vec<ModuleId>,
+    imported: &mut HashSet<ModuleId>,
+    too_early: &mut HashSet<ModuleId>,
+  ) {
+    let mut deps = module_graph
+      .dependencies(module_id)
+      .into_iter()
+      .filter_map(|(dep, edge)| {
+        edge
+          .iter()
+          .filter(|item| matches!(item.kind, ResolveKind::Import | ResolveKind::ExportFrom))
+          .map(|item| item.order)
+          .min()
+          .map(|order| (order, dep))
+      })
+      .collect::<Vec<_>>();
+    deps.sort();
+
+    for (_, dep) in deps {
+      if self.importers.get(&dep) == Some(module_id) {
+        self.visit_execution_order(&dep, module_graph, executed, imported, too_early);
+        executed.push(dep);
+      } else if imported.insert(dep) {
+        too_early.extend(executed.iter().cloned());
+      }
+    }
+  }
+}
+
+/// Return the only importer of the module if the module can be concatenated into it. A module can be concatenated when:
+/// * it's a non-async es module that is not an entry and only imports other modules statically
+/// * it's imported by only one es module of the same resource pot statically, and the importer does not use it as a namespace, e.g. `import * as ns` or `export *`
+/// * it does not re-export all exports of other modules, e.g. `export * from './a'`
+/// * all the exports imported by the importer exist
+fn concatenated_importer(
+  module_id: &ModuleId,
+  resource_pot_modules: &HashSet<&ModuleId>,
+  module_graph: &ModuleGraph,
+) -> Option<ModuleId> {
+  let is_static =
+    |kind: &ResolveKind| matches!(kind, ResolveKind::Import | ResolveKind::ExportFrom);
+  let is_es_module = |module_id: &ModuleId| {
+    module_graph.module(module_id).map_or(false, |module| {
+      module.module_type.is_script()
+        && !module.external
+        && matches!(
+          module.meta.as_script().module_system,
+          ModuleSystem::EsModule | ModuleSystem::Hybrid
+        )
+    })
+  };
+
+  let module = module_graph.module(module_id)?;
+
+  if module.is_async
+    || module.module_type == ModuleType::Runtime
+    || module_graph.entries.contains_key(module_id)
+    || !is_es_module(module_id)
+    || module.meta.as_script().module_system != ModuleSystem::EsModule
+  {
+    return None;
+  }
+
+  if module_graph
+    .dependencies(module_id)
+    .iter()
+    .any(|(_, edge)| edge.iter().any(|item| !is_static(&item.kind)))
+  {
+    return None;
+  }
+
+  let re_exports_namespace = module
+    .meta
+    .as_script()
+    .ast
+    .body
+    .iter()
+    .any(|item| match item {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportAll(_)) => true,
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport { specifiers, .. })) => specifiers
+        .iter()
+        .any(|sp| matches!(sp, ExportSpecifier::Namespace(_))),
+      _ => false,
+    });
+
+  if re_exports_namespace {
+    return None;
+  }
+
+  let dependents = module_graph.dependents(module_id);
+
+  let [(importer, edge)] = &dependents[..] else {
+    return None;
+  };
+
+  if !resource_pot_modules.contains(importer)
+    || !is_es_module(importer)
+    || edge.iter().any(|item| !is_static(&item.kind))
+  {
+    return None;
+  }
+
+  let sources = edge
+    .iter()
+    .map(|item| item.source.as_str())
+    .collect::<HashSet<_>>();
+  let used_as_namespace = module_graph
+    .module(importer)?
+    .meta
+    .as_script()
+    .ast
+    .body
+    .iter()
+    .any(|item| match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+        sources.contains(&*import_decl.src.value)
+          && import_decl
+            .specifiers
+            .iter()
+            .any(|sp| matches!(sp, ImportSpecifier::Namespace(_)))
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all)) => {
+        sources.contains(&*export_all.src.value)
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+        src: Some(src),
+        specifiers,
+        ..
+      })) => {
+        sources.contains(&*src.value)
+          && specifiers
+            .iter()
+            .any(|sp| matches!(sp, ExportSpecifier::Namespace(_)))
+      }
+      _ => false,
+    });
+
+  if used_as_namespace {
+    return None;
+  }
+
+  // the module is not concatenated if the importer imports an export that does not exist, it's `undefined` in the module system
+  let export_names = export_names(&module.meta.as_script().ast);
+
+  if imported_names(
+    &module_graph.module(importer)?.meta.as_script().ast,
+    &sources,
+  )
+  .iter()
+  .any(|name| !export_names.contains(name))
+  {
+    return None;
+  }
+
+  Some(importer.clone())
+}
+
+/// The export names of a module that does not re-export all exports of other modules
+fn export_names(ast: &SwcModule) -> HashSet<String> {
+  let mut names = HashSet::new();
+
+  for item in &ast.body {
+    match item {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => match &export_decl.decl {
+        Decl::Class(class) => {
+          names.insert(class.ident.sym.to_string());
+        }
+        Decl::Fn(func) => {
+          names.insert(func.ident.sym.to_string());
+        }
+        Decl::Var(var_decl) => {
+          names.extend(
+            find_pat_ids::<_, Ident>(&var_decl.decls)
+              .into_iter()
+              .map(|ident| ident.sym.to_string()),
+          );
+        }
+        _ => {}
+      },
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport { specifiers, .. })) => {
+        for sp in specifiers {
+          if let ExportSpecifier::Named(named) = sp {
+            names.insert(export_name(named.exported.as_ref().unwrap_or(&named.orig)));
+          }
+        }
+      }
+      ModuleItem::ModuleDecl(
+        ModuleDecl::ExportDefaultDecl(_) | ModuleDecl::ExportDefaultExpr(_),
+      ) => {
+        names.insert("default".to_string());
+      }
+      _ => {}
+    }
+  }
+
+  names
+}
+
+/// The names imported or re-exported by the module from `sources`
+fn imported_names(ast: &SwcModule, sources: &HashSet<&str>) -> Vec<String> {
+  let mut names = vec![];
+
+  for item in &ast.body {
+    match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))
+        if sources.contains(&*import_decl.src.value) =>
+      {
+        for sp in &import_decl.specifiers {
+          match sp {
+            ImportSpecifier::Named(named) => names.push(
+              named
+                .imported
+                .as_ref()
+                .map_or(named.local.sym.to_string(), export_name),
+            ),
+            ImportSpecifier::Default(_) => names.push("default".to_string()),
+            ImportSpecifier::Namespace(_) => {}
+          }
+        }
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+        src: Some(src),
+        specifiers,
+        ..
+      }))
+        if sources.contains(&*src.value) =>
+      {
+        for sp in specifiers {
+          if let ExportSpecifier::Named(named) = sp {
+            names.push(export_name(&named.orig));
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+
+  names
+}
+
+struct ModuleConcatenator<'a> {
+  module_graph: &'a ModuleGraph,
+  config: &'a Config,
+  concatenated_modules: &'a ConcatenatedModules,
+  root_unresolved_ctxt: SyntaxContext,
+  /// the exports of the concatenated modules, export name -> local binding
+  exports: HashMap<ModuleId, HashMap<String, Ident>>,
+  hoisted_sources: HashMap<String, ModuleId>,
+  comments: CommentsMetaData,
+}
+
+impl<'a> ModuleConcatenator<'a> {
+  /// Return the items of `module_id` with the items of the modules concatenated into it, the items of a concatenated dependency are
+  /// placed at the position of its first import, or before the first statement if the import follows statements, as the imports
+  /// are executed before all statements, to keep the execution order of es modules
+  fn concatenate(&mut self, module_id: &ModuleId, root: &ModuleId) -> Vec<ModuleItem> {
+    let module = self.module_graph.module(module_id).unwrap();
+    let script = module.meta.as_script();
+    let is_root = module_id == root;
+    let mut items = script.ast.body.clone();
+    self.comments.extend(script.comments.clone());
+
+    if !is_root {
+      // the unresolved references of all concatenated modules are resolved in the root module's scope
+      let unresolved_ctxt =
+        SyntaxContext::empty().apply_mark(Mark::from_u32(script.unresolved_mark));
+      items.visit_mut_with(&mut UnresolvedCtxtReplacer {
+        from: unresolved_ctxt,
+        to: self.root_unresolved_ctxt,
+      });
+    }
+
+    // replace the imports of the concatenated deps with their local bindings
+    let mut bindings = HashMap::new();
+    let mut rendered_items = vec![];
+    // the items of the concatenated deps and the index of the rendered item they are placed before
+    let mut spliced_items = vec![];
+    // the index of the first rendered item that is not an import or a re-export
+    let mut first_stmt_index = None;
+
+    for item in items {
+      let is_import = matches!(
+        &item,
+        ModuleItem::ModuleDecl(
+          ModuleDecl::Import(_)
+            | ModuleDecl::ExportAll(_)
+            | ModuleDecl::ExportNamed(NamedExport { src: Some(_), .. })
+        )
+      );
+
+      if !is_import && first_stmt_index.is_none() {
+        first_stmt_index = Some(rendered_items.len());
+      }
+
+      let splice_index = first_stmt_index.unwrap_or(rendered_items.len());
+
+      match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(mut import_decl)) => {
+          let dep = self
+            .module_graph
+            .get_dep_by_source_optional(module_id, &import_decl.src.value);
+
+          match dep {
+            Some(dep) if self.concatenated_modules.is_concatenated(&dep) => {
+              self.splice_dep(&dep, root, splice_index, &mut spliced_items);
+
+              for sp in &import_decl.specifiers {
+                let (local, imported) = match sp {
+                  ImportSpecifier::Named(named) => (
+                    &named.local,
+                    named
+                      .imported
+                      .as_ref()
+                      .map_or(named.local.sym.to_string(), export_name),
+                  ),
+                  ImportSpecifier::Default(default) => (&default.local, "default".to_string()),
+                  ImportSpecifier::Namespace(_) => unreachable!(),
+                };
+                bindings.insert(local.to_id(), self.export_binding(&dep, &imported));
+              }
+            }
+            Some(dep) if !is_root => {
+              self.hoist_source(&dep, &mut import_decl.src);
+              rendered_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)));
+            }
+            _ => rendered_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))),
+          }
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+          src: Some(mut src),
+          specifiers,
+          span,
+          type_only,
+          asserts,
+        })) => {
+          let dep = self.module_graph.get_dep_by_source(module_id, &src.value);
+
+          if self.concatenated_modules.is_concatenated(&dep) {
+            self.splice_dep(&dep, root, splice_index, &mut spliced_items);
+
+            // export { a as b } from './dep' => export { dep_a as b }
+            let specifiers = specifiers
+              .into_iter()
+              .map(|sp| match sp {
+                ExportSpecifier::Named(named) => {
+                  let exported = named.exported.unwrap_or_else(|| named.orig.clone());
+                  let binding = self.export_binding(&dep, &export_name(&named.orig));
+
+                  ExportSpecifier::Named(ExportNamedSpecifier {
+                    span: named.span,
+                    orig: ModuleExportName::Ident(binding),
+                    exported: Some(exported),
+                    is_type_only: false,
+                  })
+                }
+                _ => unreachable!(),
+              })
+              .collect();
+
+            rendered_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+              NamedExport {
+                span,
+                specifiers,
+                src: None,
+                type_only,
+                asserts: None,
+              },
+            )));
+          } else if is_root {
+            rendered_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+              NamedExport {
+                span,
+                specifiers,
+                src: Some(src),
+                type_only,
+                asserts,
+              },
+            )));
+          } else {
+            // export { a as b } from './dep' => import { a as _b } from './dep'; export { _b as b }
+            self.hoist_source(&dep, &mut src);
+            let mut import_specifiers = vec![];
+            let mut export_specifiers = vec![];
+
+            for sp in specifiers {
+              if let ExportSpecifier::Named(named) = sp {
+                let local = private_ident(&format!("_{}", export_name(&named.orig)));
+                import_specifiers.push(ImportSpecifier::Named(ImportNamedSpecifier {
+                  span: DUMMY_SP,
+                  local: local.clone(),
+                  imported: Some(named.orig.clone()),
+                  is_type_only: false,
+                }));
+                export_specifiers.push(ExportSpecifier::Named(ExportNamedSpecifier {
+                  span: named.span,
+                  orig: ModuleExportName::Ident(local),
+                  exported: Some(named.exported.unwrap_or(named.orig)),
+                  is_type_only: false,
+                }));
+              }
+            }
+
+            rendered_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+              span,
+              specifiers: import_specifiers,
+              src,
+              type_only: false,
+              asserts,
+            })));
+            rendered_items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+              NamedExport {
+                span: DUMMY_SP,
+                specifiers: export_specifiers,
+                src: None,
+                type_only: false,
+                asserts: None,
+              },
+            )));
+          }
+        }
+        item => rendered_items.push(item),
+      }
+    }
+
+    rendered_items.visit_mut_with(&mut BindingsReplacer {
+      bindings: &bindings,
+    });
+
+    let mut body = vec![];
+    let mut exports = HashMap::new();
+    let mut spliced_items = spliced_items.into_iter().peekable();
+
+    for (index, item) in rendered_items.into_iter().enumerate() {
+      while let Some((_, items)) = spliced_items.next_if(|(i, _)| *i == index) {
+        body.extend(items);
+      }
+
+      if is_root {
+        body.push(item);
+      } else {
+        strip_export(item, &mut exports, &mut body);
+      }
+    }
+
+    for (_, items) in spliced_items {
+      body.extend(items);
+    }
+
+    if !is_root {
+      self.exports.insert(module_id.clone(), exports);
+    }
+
+    body
+  }
+
+  /// Concatenate `dep` if it's not concatenated yet, its items are placed before the rendered item at `index`
+  fn splice_dep(
+    &mut self,
+    dep: &ModuleId,
+    root: &ModuleId,
+    index: usize,
+    spliced_items: &mut Vec<(usize, Vec<ModuleItem>)>,
+  ) {
+    if !self.exports.contains_key(dep) {
+      let items = self.concatenate(dep, root);
+      spliced_items.push((index, items));
+    }
+  }
+
+  /// the local binding of the export of a concatenated module, the imported exports of a concatenated module always exist, see [concatenated_importer]
+  fn export_binding(&self, module_id: &ModuleId, export: &str) -> Ident {
+    self
+      .exports
+      .get(module_id)
+      .and_then(|exports| exports.get(export))
+      .cloned()
+      .unwrap_or_else(|| {
+        panic!(
+          "the export {} of the concatenated module {:?} is not found",
+          export, module_id
+        )
+      })
+  }
+
+  /// rewrite the source of the imports hoisted from the concatenated modules to the id of the dep,
+  /// the source may be resolved to another module in the root module. The source of the external module is kept.
+  fn hoist_source(&mut self, dep: &ModuleId, src: &mut Box<Str>) {
+    let external = self
+      .module_graph
+      .module(dep)
+      .map_or(false, |module| module.external);
+    let source = if external {
+      src.value.to_string()
+    } else {
+      dep.id(self.config.mode.clone())
+    };
+
+    self.hoisted_sources.insert(source.clone(), dep.clone());
+    *src = Box::new(Str {
+      span: src.span,
+      value: source.into(),
+      raw: None,
+    });
+  }
+}
+
+/// Strip the export declaration of an item of a concatenated module, the item is appended to the body
+/// and the exports are inserted to `exports`.
+fn strip_export(
+  item: ModuleItem,
+  exports: &mut HashMap<String, Ident>,
+  body: &mut Vec<ModuleItem>,
+) {
+  match item {
+    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+      match &export_decl.decl {
+        Decl::Class(class) => {
+          exports.insert(class.ident.sym.to_string(), class.ident.clone());
+        }
+        Decl::Fn(func) => {
+          exports.insert(func.ident.sym.to_string(), func.ident.clone());
+        }
+        Decl::Var(var_decl) => {
+          for ident in find_pat_ids::<_, Ident>(&var_decl.decls) {
+            exports.insert(ident.sym.to_string(), ident);
+          }
+        }
+        _ => {}
+      }
+
+      body.push(ModuleItem::Stmt(Stmt::Decl(export_decl.decl)));
+    }
+    ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport { specifiers, .. })) => {
+      for sp in specifiers {
+        if let ExportSpecifier::Named(ExportNamedSpecifier {
+          orig: ModuleExportName::Ident(orig),
+          exported,
+          ..
+        }) = sp
+        {
+          let exported = exported.as_ref().map_or(orig.sym.to_string(), export_name);
+          exports.insert(exported, orig);
+        }
+      }
+    }
+    ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_default)) => {
+      let decl = match export_default.decl {
+        DefaultDecl::Class(class) => {
+          let ident = class.ident.unwrap_or_else(|| private_ident("_default"));
+          exports.insert("default".to_string(), ident.clone());
+          Decl::Class(ClassDecl {
+            ident,
+            declare: false,
+            class: class.class,
+          })
+        }
+        DefaultDecl::Fn(func) => {
+          let ident = func.ident.unwrap_or_else(|| private_ident("_default"));
+          exports.insert("default".to_string(), ident.clone());
+          Decl::Fn(FnDecl {
+            ident,
+            declare: false,
+            function: func.function,
+          })
+        }
+        DefaultDecl::TsInterfaceDecl(_) => return,
+      };
+
+      body.push(ModuleItem::Stmt(Stmt::Decl(decl)));
+    }
+    ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export_default)) => {
+      // export default a + b => var _default = a + b
+      let ident = private_ident("_default");
+      exports.insert("default".to_string(), ident.clone());
+
+      body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+        span: export_default.span,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+          span: DUMMY_SP,
+          name: ident.into(),
+          init: Some(export_default.expr),
+          definite: false,
+        }],
+      })))));
+    }
+    item => body.push(item),
+  }
+}
+
+pub(super) fn export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
+}
+
+/// an ident that never conflicts with other idents, it's renamed by hygiene if the name is used
+pub(super) fn private_ident(name: &str) -> Ident {
+  Ident::new(
+    name.into(),
+    DUMMY_SP.with_ctxt(SyntaxContext::empty().apply_mark(Mark::new())),
+  )
+}
+
+pub(super) struct UnresolvedCtxtReplacer {
+  pub from: SyntaxContext,
+  pub to: SyntaxContext,
+}
+
+impl VisitMut for UnresolvedCtxtReplacer {
+  fn visit_mut_ident(&mut self, ident: &mut Ident) {
+    if ident.span.ctxt == self.from {
+      ident.span.ctxt = self.to;
+    }
+  }
+}
+
+/// replace the references of the imports of the concatenated modules with their local bindings
+pub(super) struct BindingsReplacer<'a> {
+  pub bindings: &'a HashMap<Id, Ident>,
+}
+
+impl<'a> VisitMut for BindingsReplacer<'a> {
+  fn visit_mut_ident(&mut self, ident: &mut Ident) {
+    if let Some(binding) = self.bindings.get(&ident.to_id()) {
+      *ident = binding.clone();
+    }
+  }
+
+  fn visit_mut_prop(&mut self, prop: &mut Prop) {
+    // { a } => { a: dep_a }
+    if let Prop::Shorthand(ident) = prop {
+      if let Some(binding) = self.bindings.get(&ident.to_id()) {
+        *prop = Prop::KeyValue(KeyValueProp {
+          key: PropName::Ident(Ident::new(ident.sym.clone(), ident.span)),
+          value: Box::new(Expr::Ident(binding.clone())),
+        });
+        return;
+      }
+    }
+
+    prop.visit_mut_children_with(self);
+  }
+
+  // the keys are not references, e.g. `a` of `{ a: 1 }` and `obj.a`
+  fn visit_mut_prop_name(&mut self, prop_name: &mut PropName) {
+    if let PropName::Computed(computed) = prop_name {
+      computed.visit_mut_with(self);
+    }
+  }
+
+  fn visit_mut_member_prop(&mut self, member_prop: &mut MemberProp) {
+    if let MemberProp::Computed(computed) = member_prop {
+      computed.visit_mut_with(self);
+    }
+  }
+
+  fn visit_mut_export_named_specifier(&mut self, specifier: &mut ExportNamedSpecifier) {
+    // export { a } => export { dep_a as a }
+    if let ModuleExportName::Ident(orig) = &specifier.orig {
+      if let Some(binding) = self.bindings.get(&orig.to_id()) {
+        if specifier.exported.is_none() {
+          specifier.exported = Some(ModuleExportName::Ident(Ident::new(
+            orig.sym.clone(),
+            DUMMY_SP,
+          )));
+        }
+
+        specifier.orig = ModuleExportName::Ident(binding.clone());
+      }
+    }
+  }
+}
@@ -0,0 +1,601 @@
+use farmfe_core::{
+  error::{CompilationError, Result},
+  hashbrown::{HashMap, HashSet},
+  module::{module_graph::ModuleGraph, ModuleId, ModuleSystem, ModuleType},
+  resource::resource_pot::ResourcePotId,
+  swc_common::{Mark, SyntaxContext},
+  swc_ecma_ast::{
+    Decl, DefaultDecl, ExportSpecifier, Id, Ident, ImportSpecifier, ModuleDecl, ModuleItem,
+  },
+};
+use farmfe_toolkit::{
+  swc_ecma_utils::find_pat_ids,
+  swc_ecma_visit::{Visit, VisitWith},
+};
+
+use super::{
+  super::concatenate_modules::{export_name, private_ident},
+  legal_ident_name,
+};
+
+/// What an import or a re-export of a module refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DepRef {
+  Module(ModuleId),
+  /// the source of the external module, e.g. `react`
+  External(String),
+  /// the css module, the resource pot that contains it is imported by the chunk to keep the side effect
+  Css(ResourcePotId),
+  /// the dependency that is not a script or css module, e.g. assets, it's not rendered in the library
+  Ignored,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportedName {
+  Named(String),
+  Namespace,
+}
+
+/// The binding that an import finally refers to, after resolving the re-exports
+#[derive(Debug, Clone)]
+pub enum Binding {
+  /// a top level binding declared by the module
+  Local(ModuleId, Ident),
+  /// an export of an external module
+  External(String, ImportedName),
+  /// the export does not exist
+  Missing,
+}
+
+#[derive(Debug, Clone)]
+enum ExportTarget {
+  Local(Ident),
+  ReExport(DepRef, ImportedName),
+}
+
+struct ModuleLinkInfo {
+  resource_pot: ResourcePotId,
+  /// the dependencies of the module, keyed by the source
+  deps: HashMap<String, DepRef>,
+  /// the local of the import -> what it imports
+  imports: HashMap<Id, (DepRef, ImportedName)>,
+  /// export name -> what it exports, in the declaration order
+  exports: Vec<(String, ExportTarget)>,
+  /// `export * from './dep'`
+  export_all: Vec<DepRef>,
+  /// the binding of `export default 1` and the anonymous default declarations
+  default_ident: Ident,
+  /// the binding of the namespace object of the module, e.g. `import * as ns from './a'`
+  namespace_ident: Ident,
+}
+
+/// Resolve the imports and exports between the modules across the resource pots of a library.
+/// All the top level bindings of the modules are unique as the modules are resolved with different marks,
+/// so the bindings are shared directly between the modules of the same resource pot, and the bindings used by other resource pots
+/// are exported by the resource pot that declares them.
+pub struct LibraryLinker {
+  modules: HashMap<ModuleId, ModuleLinkInfo>,
+  /// the modules whose namespace objects are used, e.g. by `import * as ns` or `import('./a')`
+  namespaces: HashSet<ModuleId>,
+  /// the bindings exported by each resource pot for other resource pots, with the export names
+  chunk_exports: HashMap<ResourcePotId, Vec<(Ident, String)>>,
+  chunk_export_names: HashMap<Id, String>,
+}
+
+impl LibraryLinker {
+  /// Must be called in the globals of the compilation, the marks of the generated bindings are created here.
+  pub fn new(module_graph: &ModuleGraph) -> Result<Self> {
+    let mut modules = HashMap::new();
+
+    for module in module_graph.modules() {
+      let Some(resource_pot) = &module.resource_pot else {
+        continue;
+      };
+
+      if !module.module_type.is_script() || module.external {
+        continue;
+      }
+
+      let script = module.meta.as_script();
+
+      if script.module_system != ModuleSystem::EsModule
+        && uses_commonjs(&script.ast.body, Mark::from_u32(script.unresolved_mark))
+      {
+        return Err(CompilationError::GenericError(format!(
+          "commonjs module `{}` is not supported when rendering a library, make it external or convert it to es module",
+          module.id.to_string()
+        )));
+      }
+
+      let mut deps = HashMap::new();
+
+      for (dep_id, edge) in module_graph.dependencies(&module.id) {
+        let dep = module_graph.module(&dep_id).unwrap();
+        let dep_ref = if dep.external {
+          None
+        } else if dep.module_type.is_script() {
+          Some(DepRef::Module(dep_id.clone()))
+        } else if let (ModuleType::Css, Some(resource_pot)) = (&dep.module_type, &dep.resource_pot)
+        {
+          Some(DepRef::Css(resource_pot.clone()))
+        } else {
+          Some(DepRef::Ignored)
+        };
+
+        for item in edge.iter() {
+          deps.insert(
+            item.source.clone(),
+            dep_ref
+              .clone()
+              .unwrap_or_else(|| DepRef::External(item.source.clone())),
+          );
+        }
+      }
+
+      let name = module_name(&module.id);
+      let mut info = ModuleLinkInfo {
+        resource_pot: resource_pot.clone(),
+        deps,
+        imports: HashMap::new(),
+        exports: vec![],
+        export_all: vec![],
+        default_ident: private_ident(&format!("{}_default", name)),
+        namespace_ident: private_ident(&format!("{}_namespace", name)),
+      };
+      info.collect(&script.ast.body);
+
+      modules.insert(module.id.clone(), info);
+    }
+
+    let mut linker = Self {
+      modules,
+      namespaces: HashSet::new(),
+      chunk_exports: HashMap::new(),
+      chunk_export_names: HashMap::new(),
+    };
+    linker.collect_namespaces(module_graph);
+    linker.collect_chunk_exports(module_graph);
+
+    Ok(linker)
+  }
+
+  pub fn contains(&self, module_id: &ModuleId) -> bool {
+    self.modules.contains_key(module_id)
+  }
+
+  pub fn resource_pot_of(&self, module_id: &ModuleId) -> Option<&ResourcePotId> {
+    self.modules.get(module_id).map(|info| &info.resource_pot)
+  }
+
+  pub fn dep_ref(&self, module_id: &ModuleId, source: &str) -> Option<&DepRef> {
+    self.modules.get(module_id)?.deps.get(source)
+  }
+
+  pub fn default_ident(&self, module_id: &ModuleId) -> &Ident {
+    &self.modules[module_id].default_ident
+  }
+
+  pub fn namespace_ident(&self, module_id: &ModuleId) -> &Ident {
+    &self.modules[module_id].namespace_ident
+  }
+
+  pub fn has_namespace(&self, module_id: &ModuleId) -> bool {
+    self.namespaces.contains(module_id)
+  }
+
+  /// the bindings that `resource_pot` exports for other resource pots, the bindings that are exported by the entry are not included
+  pub fn chunk_exports(&self, resource_pot: &ResourcePotId) -> &[(Ident, String)] {
+    self
+      .chunk_exports
+      .get(resource_pot)
+      .map_or(&[], |exports| exports.as_slice())
+  }
+
+  pub fn chunk_export_name(&self, ident: &Ident) -> &str {
+    &self.chunk_export_names[&ident.to_id()]
+  }
+
+  /// the binding of the import whose local is `local`, None if `local` is not an import
+  pub fn import_binding(&self, module_id: &ModuleId, local: &Id) -> Option<Binding> {
+    let (dep, imported) = self.modules.get(module_id)?.imports.get(local)?;
+
+    Some(self.dep_binding(dep, imported, &mut HashSet::new()))
+  }
+
+  pub fn resolve_export(&self, module_id: &ModuleId, name: &str) -> Binding {
+    self
+      .resolve_export_inner(module_id, name, &mut HashSet::new())
+      .unwrap_or(Binding::Missing)
+  }
+
+  /// The export names of the module, and the sources of the external modules that are re-exported by `export *`.
+  /// The export names of the external modules are unknown.
+  pub fn export_names(&self, module_id: &ModuleId) -> (Vec<String>, Vec<String>) {
+    let mut names = vec![];
+    let mut external_sources = vec![];
+    self.collect_export_names(
+      module_id,
+      true,
+      &mut HashSet::new(),
+      &mut names,
+      &mut external_sources,
+    );
+
+    (names, external_sources)
+  }
+
+  fn collect_export_names(
+    &self,
+    module_id: &ModuleId,
+    include_default: bool,
+    visited: &mut HashSet<ModuleId>,
+    names: &mut Vec<String>,
+    external_sources: &mut Vec<String>,
+  ) {
+    if !visited.insert(module_id.clone()) {
+      return;
+    }
+
+    let Some(info) = self.modules.get(module_id) else {
+      return;
+    };
+
+    for (name, _) in &info.exports {
+      // `export *` does not re-export the default export
+      if (include_default || name != "default") && !names.contains(name) {
+        names.push(name.clone());
+      }
+    }
+
+    for dep in &info.export_all {
+      match dep {
+        DepRef::Module(dep) => {
+          self.collect_export_names(dep, false, visited, names, external_sources)
+        }
+        DepRef::External(source) if !external_sources.contains(source) => {
+          external_sources.push(source.clone())
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn dep_binding(
+    &self,
+    dep: &DepRef,
+    imported: &ImportedName,
+    visited: &mut HashSet<(ModuleId, String)>,
+  ) -> Binding {
+    match (dep, imported) {
+      (DepRef::Module(dep), ImportedName::Namespace) if self.modules.contains_key(dep) => {
+        Binding::Local(dep.clone(), self.namespace_ident(dep).clone())
+      }
+      (DepRef::Module(dep), ImportedName::Named(name)) => self
+        .resolve_export_inner(dep, name, visited)
+        .unwrap_or(Binding::Missing),
+      (DepRef::External(source), imported) => Binding::External(source.clone(), imported.clone()),
+      _ => Binding::Missing,
+    }
+  }
+
+  fn resolve_export_inner(
+    &self,
+    module_id: &ModuleId,
+    name: &str,
+    visited: &mut HashSet<(ModuleId, String)>,
+  ) -> Option<Binding> {
+    if !visited.insert((module_id.clone(), name.to_string())) {
+      return None;
+    }
+
+    let info = self.modules.get(module_id)?;
+
+    if let Some((_, target)) = info.exports.iter().find(|(export, _)| export == name) {
+      return Some(match target {
+        ExportTarget::Local(ident) => match info.imports.get(&ident.to_id()) {
+          // import { a } from './dep'; export { a };
+          Some((dep, imported)) => self.dep_binding(dep, imported, visited),
+          None => Binding::Local(module_id.clone(), ident.clone()),
+        },
+        ExportTarget::ReExport(dep, imported) => self.dep_binding(dep, imported, visited),
+      });
+    }
+
+    if name == "default" {
+      return None;
+    }
+
+    // the explicit exports of the internal modules take precedence over the unknown exports of the external modules
+    let mut external = None;
+
+    for dep in &info.export_all {
+      match dep {
+        DepRef::Module(dep) => {
+          if let Some(binding) = self.resolve_export_inner(dep, name, visited) {
+            return Some(binding);
+          }
+        }
+        DepRef::External(source) if external.is_none() => {
+          external = Some(Binding::External(
+            source.clone(),
+            ImportedName::Named(name.to_string()),
+          ));
+        }
+        _ => {}
+      }
+    }
+
+    external
+  }
+
+  fn collect_namespaces(&mut self, module_graph: &ModuleGraph) {
+    let mut namespaces = HashSet::new();
+
+    for (module_id, info) in &self.modules {
+      let imported_namespaces = info
+        .imports
+        .values()
+        .map(|(dep, imported)| (dep, imported))
+        .chain(info.exports.iter().filter_map(|(_, target)| match target {
+          ExportTarget::ReExport(dep, imported) => Some((dep, imported)),
+          ExportTarget::Local(_) => None,
+        }));
+
+      for (dep, imported) in imported_namespaces {
+        if let (DepRef::Module(dep), ImportedName::Namespace) = (dep, imported) {
+          namespaces.insert(dep.clone());
+        }
+      }
+
+      // the dynamic imported modules are resolved to their namespace objects
+      for (dep, edge) in module_graph.dependencies(module_id) {
+        // a module may be imported both statically and dynamically
+        if edge.iter().any(|item| item.kind.is_dynamic()) && self.modules.contains_key(&dep) {
+          namespaces.insert(dep);
+        }
+      }
+    }
+
+    self.namespaces = namespaces;
+  }
+
+  /// Collect the bindings that are used by other resource pots, they are exported by the resource pot that declares them.
+  /// The bindings that are exported by the entries already reuse the public export names.
+  fn collect_chunk_exports(&mut self, module_graph: &ModuleGraph) {
+    let mut module_ids = self.modules.keys().cloned().collect::<Vec<_>>();
+    module_ids.sort_by_key(|module_id| module_id.to_string());
+
+    let mut chunk_export_names = HashMap::new();
+    let mut reserved_names: HashMap<ResourcePotId, HashSet<String>> = HashMap::new();
+
+    for module_id in &module_ids {
+      if !module_graph.entries.contains_key(module_id) {
+        continue;
+      }
+
+      let resource_pot = &self.modules[module_id].resource_pot;
+
+      for name in self.export_names(module_id).0 {
+        if let Binding::Local(declared_by, ident) = self.resolve_export(module_id, &name) {
+          if &self.modules[&declared_by].resource_pot == resource_pot {
+            chunk_export_names
+              .entry(ident.to_id())
+              .or_insert_with(|| name.clone());
+          }
+        }
+
+        reserved_names
+          .entry(resource_pot.clone())
+          .or_default()
+          .insert(name);
+      }
+    }
+
+    let mut used = vec![];
+
+    for module_id in &module_ids {
+      let info = &self.modules[module_id];
+      let mut imports = info.imports.keys().collect::<Vec<_>>();
+      imports.sort_by_key(|local| local.0.to_string());
+
+      for local in imports {
+        let binding = self.import_binding(module_id, local).unwrap();
+        used.push((info.resource_pot.clone(), binding));
+      }
+
+      // the getters of the namespace objects and the exports of the entries
+      if self.has_namespace(module_id) || module_graph.entries.contains_key(module_id) {
+        for name in self.export_names(module_id).0 {
+          used.push((
+            info.resource_pot.clone(),
+            self.resolve_export(module_id, &name),
+          ));
+        }
+      }
+
+      // import('./dep') is resolved to the namespace object of the dep
+      for (dep, edge) in module_graph.dependencies(module_id) {
+        // a module may be imported both statically and dynamically
+        if edge.iter().any(|item| item.kind.is_dynamic()) && self.modules.contains_key(&dep) {
+          used.push((
+            info.resource_pot.clone(),
+            Binding::Local(dep.clone(), self.namespace_ident(&dep).clone()),
+          ));
+        }
+      }
+    }
+
+    let mut chunk_exports: HashMap<ResourcePotId, Vec<(Ident, String)>> = HashMap::new();
+
+    for (resource_pot, binding) in used {
+      let Binding::Local(declared_by, ident) = binding else {
+        continue;
+      };
+      let declared_in = &self.modules[&declared_by].resource_pot;
+
+      if declared_in == &resource_pot || chunk_export_names.contains_key(&ident.to_id()) {
+        continue;
+      }
+
+      let reserved = reserved_names.get(declared_in);
+      let exports = chunk_exports.entry(declared_in.clone()).or_default();
+      let mut name = ident.sym.to_string();
+      let mut i = 1;
+
+      while exports.iter().any(|(_, export)| export == &name)
+        || reserved.map_or(false, |reserved| reserved.contains(&name))
+      {
+        name = format!("{}${}", ident.sym, i);
+        i += 1;
+      }
+
+      chunk_export_names.insert(ident.to_id(), name.clone());
+      exports.push((ident, name));
+    }
+
+    self.chunk_exports = chunk_exports;
+    self.chunk_export_names = chunk_export_names;
+  }
+}
+
+impl ModuleLinkInfo {
+  fn dep(&self, source: &str) -> DepRef {
+    self.deps.get(source).cloned().unwrap_or(DepRef::Ignored)
+  }
+
+  fn collect(&mut self, items: &[ModuleItem]) {
+    for item in items {
+      let ModuleItem::ModuleDecl(module_decl) = item else {
+        continue;
+      };
+
+      match module_decl {
+        ModuleDecl::Import(import_decl) => {
+          let dep = self.dep(&import_decl.src.value);
+
+          for sp in &import_decl.specifiers {
+            let (local, imported) = match sp {
+              ImportSpecifier::Named(named) => (
+                &named.local,
+                ImportedName::Named(
+                  named
+                    .imported
+                    .as_ref()
+                    .map_or(named.local.sym.to_string(), export_name),
+                ),
+              ),
+              ImportSpecifier::Default(default) => {
+                (&default.local, ImportedName::Named("default".to_string()))
+              }
+              ImportSpecifier::Namespace(namespace) => (&namespace.local, ImportedName::Namespace),
+            };
+
+            self.imports.insert(local.to_id(), (dep.clone(), imported));
+          }
+        }
+        ModuleDecl::ExportDecl(export_decl) => match &export_decl.decl {
+          Decl::Class(class) => self.add_local_export(&class.ident.sym, &class.ident),
+          Decl::Fn(func) => self.add_local_export(&func.ident.sym, &func.ident),
+          Decl::Var(var_decl) => {
+            for ident in find_pat_ids::<_, Ident>(&var_decl.decls) {
+              self.add_local_export(&ident.sym, &ident);
+            }
+          }
+          _ => {}
+        },
+        ModuleDecl::ExportNamed(named_export) => {
+          let dep = named_export.src.as_ref().map(|src| self.dep(&src.value));
+
+          for sp in &named_export.specifiers {
+            match (sp, &dep) {
+              (ExportSpecifier::Named(named), None) => {
+                let exported = named.exported.as_ref().unwrap_or(&named.orig);
+
+                if let farmfe_core::swc_ecma_ast::ModuleExportName::Ident(orig) = &named.orig {
+                  self.add_local_export(&export_name(exported), orig);
+                }
+              }
+              (ExportSpecifier::Named(named), Some(dep)) => {
+                let exported = named.exported.as_ref().unwrap_or(&named.orig);
+                self.exports.push((
+                  export_name(exported),
+                  ExportTarget::ReExport(
+                    dep.clone(),
+                    ImportedName::Named(export_name(&named.orig)),
+                  ),
+                ));
+              }
+              (ExportSpecifier::Namespace(namespace), Some(dep)) => self.exports.push((
+                export_name(&namespace.name),
+                ExportTarget::ReExport(dep.clone(), ImportedName::Namespace),
+              )),
+              (ExportSpecifier::Default(default), Some(dep)) => self.exports.push((
+                default.exported.sym.to_string(),
+                ExportTarget::ReExport(dep.clone(), ImportedName::Named("default".to_string())),
+              )),
+              _ => {}
+            }
+          }
+        }
+        ModuleDecl::ExportDefaultDecl(export_default) => {
+          let ident = match &export_default.decl {
+            DefaultDecl::Class(class) => class.ident.clone(),
+            DefaultDecl::Fn(func) => func.ident.clone(),
+            DefaultDecl::TsInterfaceDecl(_) => continue,
+          };
+          let ident = ident.unwrap_or_else(|| self.default_ident.clone());
+          self.add_local_export("default", &ident);
+        }
+        ModuleDecl::ExportDefaultExpr(_) => {
+          let ident = self.default_ident.clone();
+          self.add_local_export("default", &ident);
+        }
+        ModuleDecl::ExportAll(export_all) => {
+          let dep = self.dep(&export_all.src.value);
+          self.export_all.push(dep);
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn add_local_export(&mut self, name: &str, ident: &Ident) {
+    self
+      .exports
+      .push((name.to_string(), ExportTarget::Local(ident.clone())));
+  }
+}
+
+/// `src/utils/index.ts` => `index`, used to name the generated bindings of the module
+fn module_name(module_id: &ModuleId) -> String {
+  let path = module_id.relative_path();
+  let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+
+  legal_ident_name(file_name.split('.').next().unwrap_or(file_name))
+}
+
+/// true if the module references `module`, `exports` or `require` that are not declared
+fn uses_commonjs(items: &[ModuleItem], unresolved_mark: Mark) -> bool {
+  let mut visitor = CommonJsUsageVisitor {
+    unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+    uses_commonjs: false,
+  };
+  items.visit_with(&mut visitor);
+
+  visitor.uses_commonjs
+}
+
+struct CommonJsUsageVisitor {
+  unresolved_ctxt: SyntaxContext,
+  uses_commonjs: bool,
+}
+
+impl Visit for CommonJsUsageVisitor {
+  fn visit_ident(&mut self, ident: &Ident) {
+    if ident.span.ctxt == self.unresolved_ctxt
+      && matches!(&*ident.sym, "module" | "exports" | "require")
+    {
+      self.uses_commonjs = true;
+    }
+  }
+}
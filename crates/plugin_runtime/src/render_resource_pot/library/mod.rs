@@ -0,0 +1,817 @@
+//! Render the resource pots as native es modules (library mode), the modules are not registered in the farm module system,
+//! the modules of a resource pot are concatenated into one scope and the resource pots import and export each other. For example:
+//! ```js
+//! // index.ts, the entry
+//! import React from 'react';
+//! import { a } from './a';
+//! import './style.css';
+//! export const b = () => import('./b');
+//! // a.ts
+//! export const a = 1;
+//! // b.ts, dynamic imported
+//! import { a } from './a';
+//! export const c = a;
+//! ```
+//! is rendered to
+//! ```js
+//! // index.js
+//! import React from 'react';
+//! import { a } from './a.js';
+//! import './style.css';
+//! var b = () => import('./b.js').then(function (m) { return m.b_namespace; });
+//! export { a, b };
+//! // a.js, shared by index.js and b.js
+//! var a = 1;
+//! export { a };
+//! // b.js
+//! import { a } from './a.js';
+//! var b_namespace = Object.freeze({ __proto__: null, get c() { return c; } });
+//! var c = a;
+//! export { b_namespace };
+//! ```
+//! The imports and exports between the modules are resolved by [LibraryLinker]. The urls of the resource pots are unknown when rendering,
+//! so the placeholders returned by [library_chunk_placeholder] are used and replaced after all resources are generated.
+
+use std::sync::Arc;
+
+use farmfe_core::{
+  context::CompilationContext,
+  error::Result,
+  hashbrown::HashMap,
+  module::{module_graph::ModuleGraph, CommentsMetaData, ModuleId},
+  resource::resource_pot::{ResourcePot, ResourcePotId},
+  swc_common::{comments::SingleThreadedComments, Mark, SyntaxContext, DUMMY_SP},
+  swc_ecma_ast::{
+    BlockStmt, CallExpr, Callee, ClassDecl, Decl, DefaultDecl, ExportAll, ExportNamedSpecifier,
+    ExportSpecifier, Expr, ExprOrSpread, FnDecl, FnExpr, Function, GetterProp, Ident, ImportDecl,
+    ImportDefaultSpecifier, ImportNamedSpecifier, ImportSpecifier, ImportStarAsSpecifier,
+    KeyValueProp, Lit, MemberExpr, MemberProp, Module as SwcModule, ModuleDecl, ModuleExportName,
+    ModuleItem, NamedExport, NewExpr, Null, ObjectLit, Param, Pat, Prop, PropName, PropOrSpread,
+    ReturnStmt, SpreadElement, Stmt, Str, VarDecl, VarDeclKind, VarDeclarator,
+  },
+};
+use farmfe_toolkit::{
+  script::{
+    is_dynamic_import, is_import_meta_url, is_worker, library_chunk_placeholder,
+    swc_try_with::try_with,
+  },
+  swc_ecma_transforms::{
+    fixer,
+    hygiene::{hygiene_with_config, Config as HygieneConfig},
+  },
+  swc_ecma_visit::{VisitMut, VisitMutWith},
+};
+
+pub use linker::LibraryLinker;
+use linker::{Binding, DepRef, ImportedName};
+use single_script::wrap_single_script;
+
+use super::concatenate_modules::{private_ident, BindingsReplacer, UnresolvedCtxtReplacer};
+
+mod linker;
+mod single_script;
+
+/// Render the script modules of `resource_pot` to a native es module, the comments of all modules are merged and returned together
+pub fn render_library_resource_pot(
+  resource_pot: &ResourcePot,
+  linker: &LibraryLinker,
+  module_graph: &ModuleGraph,
+  context: &Arc<CompilationContext>,
+) -> Result<(SwcModule, CommentsMetaData)> {
+  // the emitted assets are used to replace `new URL('./a.png', import.meta.url)`
+  let asset_names = super::asset_names(context);
+  let mut modules = resource_pot
+    .modules()
+    .into_iter()
+    .filter(|module_id| linker.contains(module_id))
+    .map(|module_id| module_graph.module(module_id).unwrap())
+    .collect::<Vec<_>>();
+  modules.sort_by_key(|module| module.execution_order);
+
+  let mut ast = SwcModule {
+    span: DUMMY_SP,
+    body: vec![],
+    shebang: None,
+  };
+  let mut merged_comments = CommentsMetaData::default();
+  let mut result = Ok(());
+
+  try_with(
+    context.meta.script.cm.clone(),
+    &context.meta.script.globals,
+    || {
+      let unresolved_mark = Mark::new();
+      let mut renderer = ChunkRenderer {
+        resource_pot: resource_pot.id.clone(),
+        linker,
+        unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+        imports: vec![],
+        imported_bindings: HashMap::new(),
+      };
+      let mut namespaces = vec![];
+      let mut body = vec![];
+
+      for module in &modules {
+        let script = module.meta.as_script();
+        let mut items = script.ast.body.clone();
+        merged_comments.extend(script.comments.clone());
+
+        // the unresolved references of all modules are resolved in the scope of the chunk
+        items.visit_mut_with(&mut UnresolvedCtxtReplacer {
+          from: SyntaxContext::empty().apply_mark(Mark::from_u32(script.unresolved_mark)),
+          to: renderer.unresolved_ctxt,
+        });
+        items.visit_mut_with(&mut LibrarySourceReplacer {
+          module_id: &module.id,
+          module_graph,
+          resource_pot: &resource_pot.id,
+          linker,
+          asset_names: &asset_names,
+          unresolved_mark,
+          unresolved_ctxt: renderer.unresolved_ctxt,
+        });
+        body.extend(renderer.render_module(&module.id, items));
+
+        if linker.has_namespace(&module.id) {
+          namespaces.push(renderer.namespace_decl(&module.id));
+        }
+      }
+
+      let (exports, export_all) = renderer.collect_exports(resource_pot.entry_module.as_ref());
+      namespaces.extend(body);
+      let chunk = LibraryChunk {
+        imports: renderer.imports,
+        body: namespaces,
+        exports,
+        export_all,
+      };
+
+      if context.config.output.format.is_single_script() {
+        match wrap_single_script(chunk, context, renderer.unresolved_ctxt) {
+          Ok(body) => ast.body = body,
+          Err(err) => {
+            result = Err(err);
+            return;
+          }
+        }
+      } else {
+        ast.body = chunk.into_es_module();
+      }
+
+      let comments: SingleThreadedComments = std::mem::take(&mut merged_comments).into();
+      ast.visit_mut_with(&mut hygiene_with_config(HygieneConfig::default()));
+      ast.visit_mut_with(&mut fixer(Some(&comments)));
+      merged_comments = comments.into();
+    },
+  )?;
+
+  result?;
+
+  merged_comments.leading.sort_by_key(|item| item.byte_pos);
+  merged_comments.trailing.sort_by_key(|item| item.byte_pos);
+
+  Ok((ast, merged_comments))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ImportSource {
+  External(String),
+  /// another resource pot of the library
+  Chunk(ResourcePotId),
+  /// the css resource pot imported for the side effect, e.g. `import './style.css'`
+  Css(ResourcePotId),
+}
+
+struct ChunkRenderer<'a> {
+  resource_pot: ResourcePotId,
+  linker: &'a LibraryLinker,
+  unresolved_ctxt: SyntaxContext,
+  /// the imports of the chunk in the order of the first usage, the specifiers of the same source are merged
+  imports: Vec<(ImportSource, Vec<ImportSpecifier>)>,
+  /// (source, imported name) -> local, `*` is the name of the namespace import
+  imported_bindings: HashMap<(ImportSource, String), Ident>,
+}
+
+impl<'a> ChunkRenderer<'a> {
+  /// Strip the import and export declarations of the module and replace the references of the imports with the bindings they refer to
+  fn render_module(&mut self, module_id: &ModuleId, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+    let mut bindings = HashMap::new();
+    let mut rendered_items = vec![];
+
+    for item in items {
+      match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+          self.import_dep(module_id, &import_decl.src.value);
+
+          for sp in &import_decl.specifiers {
+            let local = match sp {
+              ImportSpecifier::Named(named) => &named.local,
+              ImportSpecifier::Default(default) => &default.local,
+              ImportSpecifier::Namespace(namespace) => &namespace.local,
+            };
+            let binding = self
+              .linker
+              .import_binding(module_id, &local.to_id())
+              .unwrap_or(Binding::Missing);
+            bindings.insert(local.to_id(), self.binding_ident(binding));
+          }
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport { src: Some(src), .. }))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll { src, .. })) => {
+          // the re-exports are resolved by the linker, only the side effects of the dep are kept
+          self.import_dep(module_id, &src.value);
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(_)) => {}
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+          rendered_items.push(ModuleItem::Stmt(Stmt::Decl(export_decl.decl)))
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_default)) => {
+          let default_ident = self.linker.default_ident(module_id).clone();
+          let decl = match export_default.decl {
+            DefaultDecl::Class(class) => Decl::Class(ClassDecl {
+              ident: class.ident.unwrap_or(default_ident),
+              declare: false,
+              class: class.class,
+            }),
+            DefaultDecl::Fn(func) => Decl::Fn(FnDecl {
+              ident: func.ident.unwrap_or(default_ident),
+              declare: false,
+              function: func.function,
+            }),
+            DefaultDecl::TsInterfaceDecl(_) => continue,
+          };
+
+          rendered_items.push(ModuleItem::Stmt(Stmt::Decl(decl)));
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export_default)) => {
+          // export default a + b => var index_default = a + b
+          rendered_items.push(var_decl(
+            self.linker.default_ident(module_id).clone(),
+            export_default.expr,
+          ));
+        }
+        item => rendered_items.push(item),
+      }
+    }
+
+    rendered_items.visit_mut_with(&mut BindingsReplacer {
+      bindings: &bindings,
+    });
+
+    rendered_items
+  }
+
+  /// Keep the side effects of the dep of `source`: the external modules and the resource pots are imported by the chunk
+  fn import_dep(&mut self, module_id: &ModuleId, source: &str) {
+    match self.linker.dep_ref(module_id, source) {
+      Some(DepRef::External(source)) => {
+        self.add_import(ImportSource::External(source.clone()));
+      }
+      Some(DepRef::Module(dep)) => {
+        let resource_pot = self.linker.resource_pot_of(dep).unwrap();
+
+        if resource_pot != &self.resource_pot {
+          self.add_import(ImportSource::Chunk(resource_pot.clone()));
+        }
+      }
+      Some(DepRef::Css(resource_pot)) => {
+        self.add_import(ImportSource::Css(resource_pot.clone()));
+      }
+      _ => {}
+    }
+  }
+
+  fn add_import(&mut self, source: ImportSource) -> &mut Vec<ImportSpecifier> {
+    let index = match self.imports.iter().position(|(s, _)| s == &source) {
+      Some(index) => index,
+      None => {
+        self.imports.push((source, vec![]));
+        self.imports.len() - 1
+      }
+    };
+
+    &mut self.imports[index].1
+  }
+
+  /// The ident that refers to `binding` in the chunk, the bindings of the external modules and other resource pots are imported
+  fn binding_ident(&mut self, binding: Binding) -> Ident {
+    match binding {
+      Binding::Local(declared_by, ident) => {
+        let resource_pot = self.linker.resource_pot_of(&declared_by).unwrap();
+
+        if resource_pot == &self.resource_pot {
+          return ident;
+        }
+
+        let name = self.linker.chunk_export_name(&ident).to_string();
+        self.import_binding(
+          ImportSource::Chunk(resource_pot.clone()),
+          ImportedName::Named(name),
+          ident,
+        )
+      }
+      Binding::External(source, imported) => {
+        let local = match &imported {
+          ImportedName::Named(name) if name != "default" => private_ident(&legal_ident_name(name)),
+          _ => private_ident(&legal_ident_name(&source)),
+        };
+        self.import_binding(ImportSource::External(source), imported, local)
+      }
+      Binding::Missing => Ident::new("undefined".into(), DUMMY_SP.with_ctxt(self.unresolved_ctxt)),
+    }
+  }
+
+  /// import `imported` from `source` as `local`, the existing local is returned if it's imported already
+  fn import_binding(
+    &mut self,
+    source: ImportSource,
+    imported: ImportedName,
+    local: Ident,
+  ) -> Ident {
+    let key = match &imported {
+      ImportedName::Named(name) => (source.clone(), name.clone()),
+      ImportedName::Namespace => (source.clone(), "*".to_string()),
+    };
+
+    if let Some(local) = self.imported_bindings.get(&key) {
+      return local.clone();
+    }
+
+    let specifier = match imported {
+      ImportedName::Named(name) if name == "default" => {
+        ImportSpecifier::Default(ImportDefaultSpecifier {
+          span: DUMMY_SP,
+          local: local.clone(),
+        })
+      }
+      ImportedName::Named(name) => ImportSpecifier::Named(ImportNamedSpecifier {
+        span: DUMMY_SP,
+        local: local.clone(),
+        imported: if name == *local.sym {
+          None
+        } else {
+          Some(module_export_name(&name))
+        },
+        is_type_only: false,
+      }),
+      ImportedName::Namespace => ImportSpecifier::Namespace(ImportStarAsSpecifier {
+        span: DUMMY_SP,
+        local: local.clone(),
+      }),
+    };
+
+    self.add_import(source).push(specifier);
+    self.imported_bindings.insert(key, local.clone());
+
+    local
+  }
+
+  /// `var a_namespace = Object.freeze({ __proto__: null, ...external, get a() { return a; } })`
+  fn namespace_decl(&mut self, module_id: &ModuleId) -> ModuleItem {
+    let (names, external_sources) = self.linker.export_names(module_id);
+    // the export names of the external modules are unknown, their exports are copied
+    let spreads = external_sources
+      .into_iter()
+      .map(|source| self.binding_ident(Binding::External(source, ImportedName::Namespace)))
+      .collect();
+    let getters = names
+      .into_iter()
+      .map(|name| {
+        let binding = self.linker.resolve_export(module_id, &name);
+        (name, self.binding_ident(binding))
+      })
+      .collect();
+
+    var_decl(
+      self.linker.namespace_ident(module_id).clone(),
+      Box::new(namespace_object(spreads, getters, self.unresolved_ctxt)),
+    )
+  }
+
+  /// The bindings used by other resource pots and the exports of the entry module if the resource pot is an entry,
+  /// and the sources of the external modules that are re-exported by `export *` of the entry
+  fn collect_exports(
+    &mut self,
+    entry_module: Option<&ModuleId>,
+  ) -> (Vec<(Ident, String)>, Vec<String>) {
+    let mut exports = vec![];
+    let mut export_all = vec![];
+
+    if let Some(entry_module) = entry_module.filter(|entry| self.linker.contains(entry)) {
+      let (names, external_sources) = self.linker.export_names(entry_module);
+
+      for name in names {
+        let binding = self.linker.resolve_export(entry_module, &name);
+
+        if matches!(binding, Binding::Missing) {
+          continue;
+        }
+
+        exports.push((self.binding_ident(binding), name));
+      }
+
+      export_all = external_sources;
+    }
+
+    exports.extend(
+      self
+        .linker
+        .chunk_exports(&self.resource_pot)
+        .iter()
+        .cloned(),
+    );
+
+    (exports, export_all)
+  }
+}
+
+/// The rendered resource pot, it's output as a es module or wrapped as a single script, see [single_script]
+struct LibraryChunk {
+  imports: Vec<(ImportSource, Vec<ImportSpecifier>)>,
+  body: Vec<ModuleItem>,
+  /// (local, export name)
+  exports: Vec<(Ident, String)>,
+  /// the sources of the external modules that are re-exported by `export *`
+  export_all: Vec<String>,
+}
+
+impl LibraryChunk {
+  fn into_es_module(self) -> Vec<ModuleItem> {
+    let mut items = vec![];
+
+    for (source, specifiers) in self.imports {
+      let src = match source {
+        ImportSource::External(source) => source,
+        ImportSource::Chunk(resource_pot) | ImportSource::Css(resource_pot) => {
+          library_chunk_placeholder(&resource_pot.to_string())
+        }
+      };
+      let (namespaces, mut named): (Vec<_>, Vec<_>) = specifiers
+        .into_iter()
+        .partition(|sp| matches!(sp, ImportSpecifier::Namespace(_)));
+      // the namespace imports can not be merged with the named imports, `import a, * as b from 'a'` is valid but keep the output simple
+      let mut decls = namespaces
+        .into_iter()
+        .map(|sp| vec![sp])
+        .collect::<Vec<_>>();
+
+      if !named.is_empty() || decls.is_empty() {
+        // the default specifier must be the first one
+        named.sort_by_key(|sp| !matches!(sp, ImportSpecifier::Default(_)));
+        decls.insert(0, named);
+      }
+
+      for specifiers in decls {
+        items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+          span: DUMMY_SP,
+          specifiers,
+          src: Box::new(src.as_str().into()),
+          type_only: false,
+          asserts: None,
+        })));
+      }
+    }
+
+    items.extend(self.body);
+
+    if !self.exports.is_empty() {
+      items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+        NamedExport {
+          span: DUMMY_SP,
+          specifiers: self
+            .exports
+            .into_iter()
+            .map(|(ident, name)| {
+              ExportSpecifier::Named(ExportNamedSpecifier {
+                span: DUMMY_SP,
+                exported: if name == *ident.sym {
+                  None
+                } else {
+                  Some(module_export_name(&name))
+                },
+                orig: ModuleExportName::Ident(ident),
+                is_type_only: false,
+              })
+            })
+            .collect(),
+          src: None,
+          type_only: false,
+          asserts: None,
+        },
+      )));
+    }
+
+    for source in self.export_all {
+      items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll {
+        span: DUMMY_SP,
+        src: Box::new(source.as_str().into()),
+        type_only: false,
+        asserts: None,
+      })));
+    }
+
+    items
+  }
+}
+
+/// Replace the dynamic imports and the urls of the assets and web workers:
+/// * `import('./a')` => `import("./a.js").then(function (m) { return m.a_namespace; })`, or `Promise.resolve().then(function () { return a_namespace; })`
+///   if `./a` is in the same chunk. The functions are not arrows as the glue is injected after the modules are downleveled
+/// * `new URL('./a.png', import.meta.url)` => `new URL("./a-xxx.png", import.meta.url)`
+/// * `new Worker(new URL('./worker.ts', import.meta.url))` => `new Worker(new URL("./worker.js", import.meta.url))`
+struct LibrarySourceReplacer<'a> {
+  module_id: &'a ModuleId,
+  module_graph: &'a ModuleGraph,
+  resource_pot: &'a ResourcePotId,
+  linker: &'a LibraryLinker,
+  asset_names: &'a HashMap<ModuleId, String>,
+  unresolved_mark: Mark,
+  unresolved_ctxt: SyntaxContext,
+}
+
+impl<'a> VisitMut for LibrarySourceReplacer<'a> {
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    expr.visit_mut_children_with(self);
+
+    match expr {
+      Expr::Call(call) if is_dynamic_import(call) => {
+        if let Some(replaced) = self.replace_dynamic_import(call) {
+          *expr = replaced;
+        }
+      }
+      Expr::New(new_expr) if is_worker(self.unresolved_mark, new_expr) => {
+        if let Some(ExprOrSpread {
+          expr: box Expr::New(url),
+          ..
+        }) = new_expr.args.as_mut().and_then(|args| args.first_mut())
+        {
+          self.replace_url(url, true);
+        }
+      }
+      Expr::New(new_expr) if is_import_meta_url(self.unresolved_mark, new_expr) => {
+        self.replace_url(new_expr, false);
+      }
+      _ => {}
+    }
+  }
+}
+
+impl<'a> LibrarySourceReplacer<'a> {
+  fn replace_dynamic_import(&self, call: &CallExpr) -> Option<Expr> {
+    let Some(ExprOrSpread {
+      spread: None,
+      expr: box Expr::Lit(Lit::Str(source)),
+    }) = call.args.first() else {
+      return None;
+    };
+
+    match self.linker.dep_ref(self.module_id, &source.value)? {
+      DepRef::Module(dep) => {
+        let namespace = self.linker.namespace_ident(dep).clone();
+        let resource_pot = self.linker.resource_pot_of(dep).unwrap();
+
+        if resource_pot == self.resource_pot {
+          // Promise.resolve().then(function () { return a_namespace; })
+          let resolved = call_expr(self.promise_member("resolve"), vec![]);
+          return Some(then_expr(resolved, vec![], Expr::Ident(namespace)));
+        }
+
+        // import("./a.js").then(function (m) { return m.a_namespace; })
+        let mut import = call.clone();
+        import.args[0] = ExprOrSpread {
+          spread: None,
+          expr: Box::new(Expr::Lit(Lit::Str(
+            library_chunk_placeholder(&resource_pot.to_string())
+              .as_str()
+              .into(),
+          ))),
+        };
+        let m = private_ident("m");
+        let name = self.linker.chunk_export_name(&namespace).to_string();
+
+        Some(then_expr(
+          Expr::Call(import),
+          vec![Pat::Ident(m.clone().into())],
+          member_expr(Expr::Ident(m), &name),
+        ))
+      }
+      // the non-script modules like css are not rendered in the library
+      DepRef::Css(_) | DepRef::Ignored => Some(call_expr(
+        self.promise_member("resolve"),
+        vec![Expr::Object(ObjectLit {
+          span: DUMMY_SP,
+          props: vec![],
+        })],
+      )),
+      DepRef::External(_) => None,
+    }
+  }
+
+  /// replace the url of the asset or the web worker with the url relative to the chunk
+  fn replace_url(&self, new_expr: &mut NewExpr, is_worker: bool) {
+    let Some(args) = new_expr.args.as_mut() else {
+      return;
+    };
+    let Some(ExprOrSpread {
+      expr: box Expr::Lit(Lit::Str(Str { value, .. })),
+      ..
+    }) = args.first()
+    else {
+      return;
+    };
+
+    let url = match self.linker.dep_ref(self.module_id, value) {
+      Some(DepRef::Module(dep)) if is_worker => {
+        library_chunk_placeholder(&self.linker.resource_pot_of(dep).unwrap().to_string())
+      }
+      Some(DepRef::Ignored) => {
+        let dep = self.module_graph.get_dep_by_source(self.module_id, value);
+        let Some(name) = self.asset_names.get(&dep) else {
+          return;
+        };
+
+        format!("./{}", name)
+      }
+      _ => return,
+    };
+
+    args[0].expr = Box::new(Expr::Lit(Lit::Str(url.as_str().into())));
+  }
+
+  fn promise_member(&self, name: &str) -> Expr {
+    member_expr(
+      Expr::Ident(Ident::new(
+        "Promise".into(),
+        DUMMY_SP.with_ctxt(self.unresolved_ctxt),
+      )),
+      name,
+    )
+  }
+}
+
+/// `Object.freeze({ __proto__: null, ...spread, get a() { return a; } })`
+fn namespace_object(
+  spreads: Vec<Ident>,
+  getters: Vec<(String, Ident)>,
+  unresolved_ctxt: SyntaxContext,
+) -> Expr {
+  let mut props = vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+    key: PropName::Ident(Ident::new("__proto__".into(), DUMMY_SP)),
+    value: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+  })))];
+
+  for spread in spreads {
+    props.push(PropOrSpread::Spread(SpreadElement {
+      dot3_token: DUMMY_SP,
+      expr: Box::new(Expr::Ident(spread)),
+    }));
+  }
+
+  for (name, ident) in getters {
+    props.push(PropOrSpread::Prop(Box::new(Prop::Getter(GetterProp {
+      span: DUMMY_SP,
+      key: prop_name(&name),
+      type_ann: None,
+      body: Some(BlockStmt {
+        span: DUMMY_SP,
+        stmts: vec![Stmt::Return(ReturnStmt {
+          span: DUMMY_SP,
+          arg: Some(Box::new(Expr::Ident(ident))),
+        })],
+      }),
+    }))));
+  }
+
+  let object = Ident::new("Object".into(), DUMMY_SP.with_ctxt(unresolved_ctxt));
+
+  call_expr(
+    member_expr(Expr::Ident(object), "freeze"),
+    vec![Expr::Object(ObjectLit {
+      span: DUMMY_SP,
+      props,
+    })],
+  )
+}
+
+fn var_decl(ident: Ident, init: Box<Expr>) -> ModuleItem {
+  ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+    span: DUMMY_SP,
+    kind: VarDeclKind::Var,
+    declare: false,
+    decls: vec![VarDeclarator {
+      span: DUMMY_SP,
+      name: ident.into(),
+      init: Some(init),
+      definite: false,
+    }],
+  }))))
+}
+
+fn call_expr(callee: Expr, args: Vec<Expr>) -> Expr {
+  Expr::Call(CallExpr {
+    span: DUMMY_SP,
+    callee: Callee::Expr(Box::new(callee)),
+    args: args
+      .into_iter()
+      .map(|arg| ExprOrSpread {
+        spread: None,
+        expr: Box::new(arg),
+      })
+      .collect(),
+    type_args: None,
+  })
+}
+
+/// `promise.then(function (params) { return body; })`
+fn then_expr(promise: Expr, params: Vec<Pat>, body: Expr) -> Expr {
+  call_expr(
+    member_expr(promise, "then"),
+    vec![Expr::Fn(FnExpr {
+      ident: None,
+      function: Box::new(Function {
+        params: params
+          .into_iter()
+          .map(|pat| Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat,
+          })
+          .collect(),
+        decorators: vec![],
+        span: DUMMY_SP,
+        body: Some(BlockStmt {
+          span: DUMMY_SP,
+          stmts: vec![Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(Box::new(body)),
+          })],
+        }),
+        is_generator: false,
+        is_async: false,
+        type_params: None,
+        return_type: None,
+      }),
+    })],
+  )
+}
+
+/// `obj.name` for identifiers and `obj["name"]` for other names
+fn member_expr(obj: Expr, name: &str) -> Expr {
+  Expr::Member(MemberExpr {
+    span: DUMMY_SP,
+    obj: Box::new(obj),
+    prop: if is_valid_ident(name) {
+      MemberProp::Ident(Ident::new(name.into(), DUMMY_SP))
+    } else {
+      MemberProp::Computed(farmfe_core::swc_ecma_ast::ComputedPropName {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Lit(Lit::Str(name.into()))),
+      })
+    },
+  })
+}
+
+fn prop_name(name: &str) -> PropName {
+  if is_valid_ident(name) {
+    PropName::Ident(Ident::new(name.into(), DUMMY_SP))
+  } else {
+    PropName::Str(name.into())
+  }
+}
+
+fn module_export_name(name: &str) -> ModuleExportName {
+  if is_valid_ident(name) {
+    ModuleExportName::Ident(Ident::new(name.into(), DUMMY_SP))
+  } else {
+    ModuleExportName::Str(name.into())
+  }
+}
+
+fn is_valid_ident(name: &str) -> bool {
+  let mut chars = name.chars();
+
+  chars
+    .next()
+    .map_or(false, |c| c.is_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// `react-dom/client` => `react_dom_client`
+pub(crate) fn legal_ident_name(name: &str) -> String {
+  let name = name
+    .chars()
+    .map(|c| {
+      if c.is_alphanumeric() || c == '$' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect::<String>();
+
+  if name.is_empty() || name.starts_with(|c: char| c.is_numeric()) {
+    format!("_{}", name)
+  } else {
+    name
+  }
+}
@@ -0,0 +1,285 @@
+//! Wrap the rendered library chunk as a single script for the `iife` and `umd` formats. All modules are bundled into one
+//! chunk, the external modules are passed to the factory as parameters and read from `output.globals`, e.g.
+//! ```js
+//! var MyLib = (function (react) {
+//!   var React = react && react.__esModule ? react.default : react;
+//!   var a = 1;
+//!   return Object.freeze({ __proto__: null, get a() { return a; } });
+//! })(React);
+//! ```
+
+use std::sync::Arc;
+
+use farmfe_core::{
+  config::ModuleFormat,
+  context::CompilationContext,
+  error::{CompilationError, Result},
+  serde_json,
+  swc_common::{SyntaxContext, DUMMY_SP},
+  swc_ecma_ast::{
+    BinExpr, BinaryOp, BlockStmt, CondExpr, Expr, FnExpr, Function, Ident, ImportSpecifier,
+    ModuleExportName, ModuleItem, Param, ReturnStmt, Stmt,
+  },
+};
+use farmfe_toolkit::{
+  script::parse_module,
+  swc_ecma_parser::{EsConfig, Syntax},
+  swc_ecma_visit::{VisitMut, VisitMutWith},
+};
+
+use super::{
+  super::concatenate_modules::private_ident, is_valid_ident, legal_ident_name, member_expr,
+  namespace_object, var_decl, ImportSource, LibraryChunk,
+};
+
+const FACTORY_PLACEHOLDER: &str = "__FARM_LIBRARY_FACTORY__";
+
+/// Wrap `chunk` in a factory function and call it with the globals of the external modules, see the module docs
+pub(super) fn wrap_single_script(
+  chunk: LibraryChunk,
+  context: &Arc<CompilationContext>,
+  unresolved_ctxt: SyntaxContext,
+) -> Result<Vec<ModuleItem>> {
+  let output = &context.config.output;
+  // (source, param of the factory)
+  let mut externals: Vec<(String, Ident)> = vec![];
+  let param_of = |source: &str, externals: &mut Vec<(String, Ident)>| {
+    if let Some((_, param)) = externals.iter().find(|(s, _)| s == source) {
+      return param.clone();
+    }
+
+    let param = private_ident(&legal_ident_name(source));
+    externals.push((source.to_string(), param.clone()));
+    param
+  };
+  let mut stmts = vec![];
+
+  for (source, specifiers) in chunk.imports {
+    let source = match source {
+      ImportSource::External(source) => source,
+      // a single script can not import css, the css resources should be loaded by the users
+      ImportSource::Css(_) => continue,
+      ImportSource::Chunk(resource_pot) => {
+        return Err(CompilationError::GenericError(format!(
+          "the iife and umd formats only support a single chunk, but resource pot {} is imported",
+          resource_pot.to_string()
+        )))
+      }
+    };
+    let param = param_of(&source, &mut externals);
+
+    for specifier in specifiers {
+      let (local, init) = match specifier {
+        // `p && p.__esModule ? p.default : p`
+        ImportSpecifier::Default(default) => (
+          default.local,
+          Expr::Cond(CondExpr {
+            span: DUMMY_SP,
+            test: Box::new(Expr::Bin(BinExpr {
+              span: DUMMY_SP,
+              op: BinaryOp::LogicalAnd,
+              left: Box::new(Expr::Ident(param.clone())),
+              right: Box::new(member_expr(Expr::Ident(param.clone()), "__esModule")),
+            })),
+            cons: Box::new(member_expr(Expr::Ident(param.clone()), "default")),
+            alt: Box::new(Expr::Ident(param.clone())),
+          }),
+        ),
+        ImportSpecifier::Named(named) => {
+          let imported = match &named.imported {
+            Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+            Some(ModuleExportName::Str(str)) => str.value.to_string(),
+            None => named.local.sym.to_string(),
+          };
+          let init = member_expr(Expr::Ident(param.clone()), &imported);
+          (named.local, init)
+        }
+        ImportSpecifier::Namespace(namespace) => (namespace.local, Expr::Ident(param.clone())),
+      };
+
+      stmts.push(into_stmt(var_decl(local, Box::new(init))));
+    }
+  }
+
+  let export_all = chunk
+    .export_all
+    .iter()
+    .map(|source| param_of(source, &mut externals))
+    .collect::<Vec<_>>();
+
+  stmts.extend(chunk.body.into_iter().map(into_stmt));
+
+  let has_exports = !chunk.exports.is_empty() || !export_all.is_empty();
+
+  if has_exports {
+    let exports = match (&chunk.exports[..], &export_all[..]) {
+      ([(ident, name)], []) if name == "default" => Expr::Ident(ident.clone()),
+      _ => namespace_object(
+        export_all,
+        chunk
+          .exports
+          .into_iter()
+          .map(|(ident, name)| (name, ident))
+          .collect(),
+        unresolved_ctxt,
+      ),
+    };
+
+    stmts.push(Stmt::Return(ReturnStmt {
+      span: DUMMY_SP,
+      arg: Some(Box::new(exports)),
+    }));
+  }
+
+  // the global variable is not assigned if the entry has no exports
+  let name = match &output.name {
+    Some(name) if has_exports => Some(name.as_str()),
+    None if has_exports => {
+      return Err(CompilationError::GenericError(
+        "output.name is required by the iife and umd formats when the entry has exports"
+          .to_string(),
+      ))
+    }
+    _ => None,
+  };
+  let globals = externals
+    .iter()
+    .map(|(source, _)| {
+      output.globals.get(source).cloned().ok_or_else(|| {
+        CompilationError::GenericError(format!(
+          "the global variable of the external module {} is not specified in output.globals",
+          source
+        ))
+      })
+    })
+    .collect::<Result<Vec<_>>>()?;
+  let sources = externals
+    .iter()
+    .map(|(source, _)| serde_json::to_string(source).unwrap())
+    .collect::<Vec<_>>();
+
+  let wrapper = match output.format {
+    ModuleFormat::Iife => {
+      let global_deps = globals
+        .iter()
+        .map(|global| global_access(None, global))
+        .collect::<Vec<_>>();
+      let call = format!(
+        "(function {}() {{}})({})",
+        FACTORY_PLACEHOLDER,
+        global_deps.join(", ")
+      );
+
+      match name {
+        Some(name) if is_valid_ident(name) => format!("var {} = {};", name, call),
+        Some(name) => {
+          format!("this[{}] = {};", serde_json::to_string(name).unwrap(), call)
+        }
+        None => format!("{};", call),
+      }
+    }
+    ModuleFormat::Umd => {
+      let assign_global = match name {
+        Some(name) if is_valid_ident(name) => format!("global.{} = ", name),
+        Some(name) => format!("global[{}] = ", serde_json::to_string(name).unwrap()),
+        None => String::new(),
+      };
+      let requires = sources
+        .iter()
+        .map(|source| format!("require({})", source))
+        .collect::<Vec<_>>();
+      let global_deps = globals
+        .iter()
+        .map(|global| global_access(Some("global"), global))
+        .collect::<Vec<_>>();
+
+      format!(
+        "(function (global, factory) {{
+  typeof exports === 'object' && typeof module !== 'undefined' ? {module_exports}factory({requires}) :
+  typeof define === 'function' && define.amd ? define([{sources}], factory) :
+  (global = typeof globalThis !== 'undefined' ? globalThis : global || self, {assign_global}factory({global_deps}));
+}})(this, function {placeholder}() {{}});",
+        module_exports = if has_exports { "module.exports = " } else { "" },
+        requires = requires.join(", "),
+        sources = sources.join(", "),
+        assign_global = assign_global,
+        global_deps = global_deps.join(", "),
+        placeholder = FACTORY_PLACEHOLDER,
+      )
+    }
+    _ => unreachable!("only the iife and umd formats are single scripts"),
+  };
+
+  let mut wrapper_ast = parse_module(
+    "farm_internal_library_wrapper",
+    &wrapper,
+    Syntax::Es(EsConfig::default()),
+    context.config.script.target,
+    context.meta.script.cm.clone(),
+  )?
+  .ast;
+
+  wrapper_ast.visit_mut_with(&mut FactoryReplacer {
+    factory: Some(Function {
+      params: externals
+        .into_iter()
+        .map(|(_, param)| Param {
+          span: DUMMY_SP,
+          decorators: vec![],
+          pat: param.into(),
+        })
+        .collect(),
+      decorators: vec![],
+      span: DUMMY_SP,
+      body: Some(BlockStmt {
+        span: DUMMY_SP,
+        stmts,
+      }),
+      is_generator: false,
+      is_async: false,
+      type_params: None,
+      return_type: None,
+    }),
+  });
+
+  Ok(wrapper_ast.body)
+}
+
+/// The expression that reads the global variable of an external module from `obj`, e.g. `global.React` or `global.MyLib.dep`.
+/// The names that are not (dotted) identifiers are quoted, e.g. `global["react-dom"]`, and they are read from `this` if `obj` is None.
+fn global_access(obj: Option<&str>, global: &str) -> String {
+  match obj {
+    _ if !global.split('.').all(is_valid_ident) => format!(
+      "{}[{}]",
+      obj.unwrap_or("this"),
+      serde_json::to_string(global).unwrap()
+    ),
+    Some(obj) => format!("{}.{}", obj, global),
+    None => global.to_string(),
+  }
+}
+
+fn into_stmt(item: ModuleItem) -> Stmt {
+  match item {
+    ModuleItem::Stmt(stmt) => stmt,
+    ModuleItem::ModuleDecl(_) => {
+      unreachable!("the module declarations are stripped when rendering the chunk")
+    }
+  }
+}
+
+/// Replace the placeholder function `function __FARM_LIBRARY_FACTORY__() {}` of the wrapper with the factory
+struct FactoryReplacer {
+  factory: Option<Function>,
+}
+
+impl VisitMut for FactoryReplacer {
+  fn visit_mut_fn_expr(&mut self, fn_expr: &mut FnExpr) {
+    if matches!(&fn_expr.ident, Some(ident) if &*ident.sym == FACTORY_PLACEHOLDER) {
+      fn_expr.ident = None;
+      fn_expr.function = Box::new(self.factory.take().unwrap());
+    } else {
+      fn_expr.visit_mut_children_with(self);
+    }
+  }
+}
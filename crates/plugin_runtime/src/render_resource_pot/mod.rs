@@ -4,10 +4,10 @@ use farmfe_core::{
   context::CompilationContext,
   error::{CompilationError, Result},
   hashbrown::HashMap,
-  module::{module_graph::ModuleGraph, ModuleSystem},
+  module::{module_graph::ModuleGraph, CommentsMetaData, ModuleId, ModuleSystem},
   parking_lot::Mutex,
   rayon::prelude::*,
-  resource::resource_pot::ResourcePot,
+  resource::{resource_pot::ResourcePot, ResourceOrigin},
   swc_common::{comments::SingleThreadedComments, Mark, DUMMY_SP},
   swc_ecma_ast::{
     BlockStmt, Expr, FnExpr, Function, Ident, KeyValueProp, Module as SwcModule, ModuleItem,
@@ -30,9 +30,14 @@ use farmfe_toolkit::{
   swc_ecma_visit::VisitMutWith,
 };
 
-use self::source_replacer::{SourceReplacer, DYNAMIC_REQUIRE, FARM_REQUIRE};
+use self::{
+  concatenate_modules::ConcatenatedModules,
+  source_replacer::{SourceReplacer, DYNAMIC_REQUIRE, FARM_REQUIRE},
+};
 
+mod concatenate_modules;
 // mod farm_module_system; // TODO: replace with farm_module_system later, as soon as it's ready
+pub mod library;
 mod source_replacer;
 
 /// Merge all modules' ast in a [ResourcePot] to Farm's runtime [ObjectLit]. The [ObjectLit] looks like:
@@ -55,21 +60,32 @@ mod source_replacer;
 ///    }
 /// }
 /// ```
+/// The comments of all modules are merged and returned together with the [ObjectLit].
+///
+/// When `config.concatenate_modules` is enabled, the es modules that are only imported by one module of the resource pot are
+/// concatenated into their importer and are not rendered as separate functions, see [concatenate_modules].
 pub fn resource_pot_to_runtime_object_lit(
   resource_pot: &mut ResourcePot,
   module_graph: &ModuleGraph,
   context: &Arc<CompilationContext>,
-) -> Result<ObjectLit> {
+) -> Result<(ObjectLit, CommentsMetaData)> {
   let mut rendered_resource_ast = ObjectLit {
     span: DUMMY_SP,
     props: vec![],
   };
 
   let props = Mutex::new(HashMap::new());
+  let merged_comments = Mutex::new(CommentsMetaData::default());
+  // the emitted assets are used to replace `new URL('./a.png', import.meta.url)`, the names are copied to release the lock
+  // of the resources map before rendering the modules in parallel
+  let asset_names = asset_names(context);
+  let concatenated_modules = ConcatenatedModules::new(resource_pot, module_graph, &context.config);
+  let async_module_cycles = module_graph.async_module_cycles();
 
   resource_pot
     .modules()
     .into_par_iter()
+    .filter(|m_id| !concatenated_modules.is_concatenated(m_id))
     .try_for_each(|m_id| {
       let module = module_graph
         .module(m_id)
@@ -79,11 +95,21 @@ pub fn resource_pot_to_runtime_object_lit(
         span: DUMMY_SP,
         body: module.meta.as_script().ast.body.to_vec(),
       };
+      let mut comments: SingleThreadedComments = module.meta.as_script().comments.clone().into();
+      let mut hoisted_sources = HashMap::new();
 
       try_with(
         context.meta.script.cm.clone(),
         &context.meta.script.globals,
         || {
+          if let Some(concatenated) =
+            concatenated_modules.concatenate(m_id, module_graph, &context.config)
+          {
+            cloned_module = concatenated.ast;
+            comments = concatenated.comments.into();
+            hoisted_sources = concatenated.hoisted_sources;
+          }
+
           // transform esm to commonjs
           let unresolved_mark = Mark::from_u32(module.meta.as_script().unresolved_mark);
           let top_level_mark = Mark::from_u32(module.meta.as_script().top_level_mark);
@@ -95,7 +121,7 @@ pub fn resource_pot_to_runtime_object_lit(
           ) {
             cloned_module.visit_mut_with(&mut import_analyzer(ImportInterop::Swc, true));
             cloned_module.visit_mut_with(&mut inject_helpers(unresolved_mark));
-            cloned_module.visit_mut_with(&mut common_js::<SingleThreadedComments>(
+            cloned_module.visit_mut_with(&mut common_js(
               unresolved_mark,
               Config {
                 // TODO process dynamic import by ourselves later
@@ -104,31 +130,38 @@ pub fn resource_pot_to_runtime_object_lit(
                 ..Default::default()
               },
               enable_available_feature_from_es_version(context.config.script.target),
-              None,
+              Some(&comments),
             ));
           }
 
           // replace import source with module id
           let mut source_replacer = SourceReplacer::new(
-            unresolved_mark,
-            top_level_mark,
             module_graph,
             m_id.clone(),
-            module.meta.as_script().module_system.clone(),
-            context.config.mode.clone(),
+            &context.config,
+            &asset_names,
+            &hoisted_sources,
+            &async_module_cycles,
           );
           cloned_module.visit_mut_with(&mut source_replacer);
+
+          for warning in source_replacer.warnings {
+            println!("[warn] {}", warning);
+            context.log_store.write().add_warning(warning);
+          }
+
           cloned_module.visit_mut_with(&mut hygiene_with_config(HygieneConfig {
             top_level_mark,
             ..Default::default()
           }));
-          // TODO support comments
-          cloned_module.visit_mut_with(&mut fixer(None));
+          cloned_module.visit_mut_with(&mut fixer(Some(&comments)));
         },
       )?;
 
+      merged_comments.lock().extend(comments.into());
+
       // wrap module function
-      let wrapped_module = wrap_module_ast(cloned_module);
+      let wrapped_module = wrap_module_ast(cloned_module, module.is_async);
 
       props.lock().insert(
         module.id.id(context.config.mode.clone()),
@@ -154,7 +187,25 @@ pub fn resource_pot_to_runtime_object_lit(
   // insert props to the object lit
   rendered_resource_ast.props = props.into_iter().map(|(_, v)| v).collect();
 
-  Ok(rendered_resource_ast)
+  let mut merged_comments = merged_comments.into_inner();
+  // the modules are rendered in parallel, sort the comments to make sure the order is stable
+  merged_comments.leading.sort_by_key(|item| item.byte_pos);
+  merged_comments.trailing.sort_by_key(|item| item.byte_pos);
+
+  Ok((rendered_resource_ast, merged_comments))
+}
+
+/// The names of the resources emitted by the modules (the assets), module id -> resource name
+pub(crate) fn asset_names(context: &Arc<CompilationContext>) -> HashMap<ModuleId, String> {
+  context
+    .resources_map
+    .lock()
+    .values()
+    .filter_map(|resource| match &resource.origin {
+      ResourceOrigin::Module(module_id) => Some((module_id.clone(), resource.name.clone())),
+      _ => None,
+    })
+    .collect()
 }
 
 /// Wrap the module ast to follow Farm's commonjs-style module system.
@@ -174,7 +225,9 @@ pub fn resource_pot_to_runtime_object_lit(
 ///   exports.b = b;
 /// }
 /// ```
-fn wrap_module_ast(ast: SwcModule) -> Function {
+/// The async modules(modules that use top level await or import async modules) are wrapped by an async function,
+/// and the async dependencies are awaited, see [SourceReplacer].
+fn wrap_module_ast(ast: SwcModule, is_async: bool) -> Function {
   let params = vec!["module", "exports", FARM_REQUIRE, DYNAMIC_REQUIRE]
     .into_iter()
     .map(|ident| Param {
@@ -209,6 +262,7 @@ fn wrap_module_ast(ast: SwcModule) -> Function {
             Arc::new(Default::default()),
             None,
             false,
+            None,
           )
           .unwrap();
 
@@ -228,7 +282,7 @@ fn wrap_module_ast(ast: SwcModule) -> Function {
     span: DUMMY_SP,
     body,
     is_generator: false,
-    is_async: false,
+    is_async,
     type_params: None,
     return_type: None,
   }
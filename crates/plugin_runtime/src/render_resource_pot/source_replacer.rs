@@ -6,15 +6,29 @@
 //! ```js
 //! const { b } = require("xxx"); // xxx is b's id.
 //! ```
+//! `require.resolve('./xxx')` is replaced by the id of xxx, e.g. the `resolve` of the expanded `require.context`.
+//! The requires of async modules in the module scope of an async module are awaited: `await require("xxx", true)`.
+//! and replace the asset references `new URL('./a.png', import.meta.url)` to the final url of the emitted asset.
+//! The url of web workers `new Worker(new URL('./worker.js', import.meta.url))` is replaced by a placeholder, which is
+//! replaced by the url of the worker entry resource after all resources are generated.
+//! The `import.meta` left is replaced for browser as the resources are classic scripts, see [SourceReplacer::replace_import_meta].
 
 use farmfe_core::{
-  config::Mode,
-  module::{module_graph::ModuleGraph, ModuleId, ModuleSystem, ModuleType},
-  swc_common::{Mark, DUMMY_SP},
-  swc_ecma_ast::{CallExpr, Callee, Expr, ExprOrSpread, Ident, Lit, Str},
+  config::{Config, TargetEnv},
+  hashbrown::HashMap,
+  module::{module_graph::ModuleGraph, ModuleId, ModuleType},
+  swc_common::{util::take::Take, Mark, DUMMY_SP},
+  swc_ecma_ast::{
+    ArrowExpr, AwaitExpr, CallExpr, Callee, Expr, ExprOrSpread, Function, Ident, KeyValueProp, Lit,
+    MemberExpr, MemberProp, MetaPropExpr, MetaPropKind, NewExpr, ObjectLit, Prop, PropName,
+    PropOrSpread, Str,
+  },
 };
 use farmfe_toolkit::{
-  script::{is_commonjs_require, is_dynamic_import},
+  script::{
+    is_commonjs_require, is_dynamic_import, is_import_meta_url, is_require_context_resolve,
+    is_worker, worker_url_placeholder,
+  },
   swc_ecma_visit::{VisitMut, VisitMutWith},
 };
 
@@ -32,26 +46,42 @@ pub struct SourceReplacer<'a> {
   top_level_mark: Mark,
   module_graph: &'a ModuleGraph,
   module_id: ModuleId,
-  module_system: ModuleSystem,
-  mode: Mode,
+  config: &'a Config,
+  /// module id -> the name of the asset emitted by the module
+  asset_names: &'a HashMap<ModuleId, String>,
+  /// the sources of the imports hoisted from the modules concatenated into this module, see [super::concatenate_modules]
+  hoisted_sources: &'a HashMap<String, ModuleId>,
+  /// the cycles of the async modules, see [ModuleGraph::async_module_cycles]
+  async_module_cycles: &'a HashMap<ModuleId, usize>,
+  /// the depth of the functions we are in, the requires can only be awaited in the module scope
+  function_depth: usize,
+  /// the warnings found when replacing, they are added to the log store after rendering the module
+  pub warnings: Vec<String>,
 }
 
 impl<'a> SourceReplacer<'a> {
+  /// the marks are read from the script meta of the module
   pub fn new(
-    unresolved_mark: Mark,
-    top_level_mark: Mark,
     module_graph: &'a ModuleGraph,
     module_id: ModuleId,
-    module_system: ModuleSystem,
-    mode: Mode,
+    config: &'a Config,
+    asset_names: &'a HashMap<ModuleId, String>,
+    hoisted_sources: &'a HashMap<String, ModuleId>,
+    async_module_cycles: &'a HashMap<ModuleId, usize>,
   ) -> Self {
+    let meta = module_graph.module(&module_id).unwrap().meta.as_script();
+
     Self {
-      unresolved_mark,
-      top_level_mark,
+      unresolved_mark: Mark::from_u32(meta.unresolved_mark),
+      top_level_mark: Mark::from_u32(meta.top_level_mark),
       module_graph,
       module_id,
-      module_system,
-      mode,
+      config,
+      asset_names,
+      hoisted_sources,
+      async_module_cycles,
+      function_depth: 0,
+      warnings: vec![],
     }
   }
 }
@@ -59,27 +89,76 @@ impl<'a> SourceReplacer<'a> {
 impl<'a> VisitMut for SourceReplacer<'a> {
   fn visit_mut_expr(&mut self, expr: &mut Expr) {
     if let Expr::Call(call_expr) = expr {
-      if let SourceReplaceResult::NotScriptModule = self.replace_source_with_id(call_expr) {
-        *expr = Expr::Lit(Lit::Str(Str {
-          span: DUMMY_SP,
-          value: "".into(),
-          raw: None,
-        }));
+      match self.replace_source_with_id(call_expr) {
+        SourceReplaceResult::NotScriptModule => {
+          *expr = Expr::Lit(Lit::Str(Str {
+            span: DUMMY_SP,
+            value: "".into(),
+            raw: None,
+          }));
+        }
+        SourceReplaceResult::ResolvedId(id) => {
+          *expr = Expr::Lit(Lit::Str(id.as_str().into()));
+        }
+        SourceReplaceResult::ReplacedAsync => {
+          *expr = Expr::Await(AwaitExpr {
+            span: DUMMY_SP,
+            arg: Box::new(expr.take()),
+          });
+        }
+        _ => {}
       }
+    } else if let Expr::New(new_expr) = expr {
+      if self.replace_worker_url(new_expr) {
+        // the url of the worker is already replaced, only visit the options
+        for arg in &mut new_expr.args.as_mut().unwrap()[1..] {
+          arg.visit_mut_with(self);
+        }
+        return;
+      }
+
+      self.replace_import_meta_url(new_expr);
+      new_expr.visit_mut_children_with(self);
+    } else if let Some(replaced) = self.replace_import_meta(expr) {
+      *expr = replaced;
     } else {
       expr.visit_mut_children_with(self);
     }
   }
+
+  fn visit_mut_function(&mut self, function: &mut Function) {
+    self.function_depth += 1;
+    function.visit_mut_children_with(self);
+    self.function_depth -= 1;
+  }
+
+  fn visit_mut_arrow_expr(&mut self, arrow_expr: &mut ArrowExpr) {
+    self.function_depth += 1;
+    arrow_expr.visit_mut_children_with(self);
+    self.function_depth -= 1;
+  }
 }
 
 enum SourceReplaceResult {
   NotReplaced,
   Replaced,
+  /// the source is an async module and should be awaited
+  ReplacedAsync,
   /// the source is not a script module
   NotScriptModule,
+  /// `require.resolve('./xxx')` is replaced by the module id
+  ResolvedId(String),
 }
 
 impl SourceReplacer<'_> {
+  fn get_dep_by_source(&self, source: &str) -> ModuleId {
+    if let Some(id) = self.hoisted_sources.get(source) {
+      return id.clone();
+    }
+
+    self.module_graph.get_dep_by_source(&self.module_id, source)
+  }
+
   fn replace_source_with_id(&mut self, call_expr: &mut CallExpr) -> SourceReplaceResult {
     if call_expr.args.len() != 1 {
       call_expr.visit_mut_children_with(self);
@@ -119,9 +198,7 @@ impl SourceReplacer<'_> {
           return SourceReplaceResult::NotReplaced;
         }
 
-        let id = self
-          .module_graph
-          .get_dep_by_source(&self.module_id, &source);
+        let id = self.get_dep_by_source(&source);
         // only execute script module
         let dep_module = self.module_graph.module(&id).unwrap();
 
@@ -130,30 +207,56 @@ impl SourceReplacer<'_> {
         }
 
         if dep_module.module_type.is_script() || dep_module.module_type == ModuleType::Runtime {
-          *value = id.id(self.mode.clone()).into();
+          *value = id.id(self.config.mode.clone()).into();
+
+          if self.should_await(&id) {
+            // `farmRequire("xxx")` => `farmRequire("xxx", true)`, the async exports of the dep is returned
+            call_expr.args.push(ExprOrSpread {
+              spread: None,
+              expr: Box::new(Expr::Lit(Lit::Bool(true.into()))),
+            });
+            return SourceReplaceResult::ReplacedAsync;
+          }
+
           return SourceReplaceResult::Replaced;
         } else {
           // not script module should not be executed and should be removed
           return SourceReplaceResult::NotScriptModule;
         }
       }
+    } else if is_require_context_resolve(self.unresolved_mark, &*call_expr) {
+      if let Some(ExprOrSpread {
+        spread: None,
+        expr: box Expr::Lit(Lit::Str(Str { value, .. })),
+      }) = call_expr.args.first()
+      {
+        if let Some(id) = self
+          .module_graph
+          .get_dep_by_source_optional(&self.module_id, value)
+        {
+          return SourceReplaceResult::ResolvedId(id.id(self.config.mode.clone()));
+        }
+      }
     } else if is_dynamic_import(&*call_expr) {
       if let ExprOrSpread {
         spread: None,
         expr: box Expr::Lit(Lit::Str(Str { value, .. })),
       } = &mut call_expr.args[0]
       {
+        let source = value.to_string();
+        // the dynamic import is ignored by `webpackIgnore: true`, leave it untouched
+        let Some(id) = self
+          .module_graph
+          .get_dep_by_source_optional(&self.module_id, &source) else {
+          return SourceReplaceResult::NotReplaced;
+        };
+
         call_expr.callee = Callee::Expr(Box::new(Expr::Ident(Ident {
           span: DUMMY_SP,
           sym: DYNAMIC_REQUIRE.into(),
           optional: false,
         })));
-
-        let source = value.to_string();
-        let id = self
-          .module_graph
-          .get_dep_by_source(&self.module_id, &source);
-        *value = id.id(self.mode.clone()).into();
+        *value = id.id(self.config.mode.clone()).into();
         return SourceReplaceResult::Replaced;
       }
     }
@@ -161,4 +264,178 @@ impl SourceReplacer<'_> {
     call_expr.visit_mut_children_with(self);
     SourceReplaceResult::NotReplaced
   }
+
+  /// the require of an async dep should be awaited when it's in the module scope of an async module.
+  /// the deps in the same cycle are not awaited, otherwise the modules in the cycle would wait for each other forever.
+  fn should_await(&self, dep: &ModuleId) -> bool {
+    if self.function_depth > 0 {
+      return false;
+    }
+
+    // only the async modules are in the cycles
+    match (
+      self.async_module_cycles.get(&self.module_id),
+      self.async_module_cycles.get(dep),
+    ) {
+      (Some(cycle), Some(dep_cycle)) => cycle != dep_cycle,
+      _ => false,
+    }
+  }
+
+  /// `new URL('./a.png', import.meta.url)` => `new URL("a-xxx.png", new URL("/public/", location.href).href)` for browser or `new URL("./a-xxx.png", import.meta.url)` for node
+  fn replace_import_meta_url(&mut self, new_expr: &mut NewExpr) {
+    if !is_import_meta_url(self.unresolved_mark, new_expr) {
+      return;
+    }
+
+    let args = new_expr.args.as_mut().unwrap();
+
+    if let box Expr::Lit(Lit::Str(Str { value, .. })) = &args[0].expr {
+      let id = self.module_graph.get_dep_by_source(&self.module_id, value);
+      let Some(name) = self.asset_names.get(&id) else {
+        return;
+      };
+
+      // the url is relative to the base, see [SourceReplacer::url_base]
+      let url = match self.config.output.target_env {
+        TargetEnv::Browser => name.to_string(),
+        TargetEnv::Node => format!("./{}", name),
+      };
+
+      args[0].expr = Box::new(Expr::Lit(Lit::Str(url.as_str().into())));
+      args[1].expr = Box::new(self.url_base());
+    }
+  }
+
+  /// `new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' })` => `new Worker(new URL("__FARM_WORKER_URL__(xxx)", base), { type: "classic" })`.
+  /// The worker entry resource is a classic script, so `type: 'module'` is replaced too and a warning is emitted.
+  /// Return true if the new expr is a web worker.
+  fn replace_worker_url(&mut self, new_expr: &mut NewExpr) -> bool {
+    if !is_worker(self.unresolved_mark, new_expr) {
+      return false;
+    }
+
+    let args = new_expr.args.as_mut().unwrap();
+
+    if let box Expr::New(NewExpr {
+      args: Some(url_args),
+      ..
+    }) = &mut args[0].expr
+    {
+      if let box Expr::Lit(Lit::Str(Str { value, .. })) = &url_args[0].expr {
+        let id = self.module_graph.get_dep_by_source(&self.module_id, value);
+        let placeholder = worker_url_placeholder(&id.id(self.config.mode.clone()));
+
+        url_args[0].expr = Box::new(Expr::Lit(Lit::Str(placeholder.as_str().into())));
+        url_args[1].expr = Box::new(self.url_base());
+      }
+    }
+
+    if let Some(ExprOrSpread {
+      expr: box Expr::Object(options),
+      ..
+    }) = args.get_mut(1)
+    {
+      for prop in &mut options.props {
+        if let PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key, value })) = prop {
+          let is_type = match key {
+            PropName::Ident(ident) => ident.sym == *"type",
+            PropName::Str(str) => str.value == *"type",
+            _ => false,
+          };
+
+          if is_type {
+            if matches!(value, box Expr::Lit(Lit::Str(Str { value, .. })) if value == "module") {
+              self.warnings.push(format!(
+                "The module worker created in {} is bundled as a classic worker, `type: 'module'` is replaced by `type: 'classic'`",
+                self.module_id.relative_path()
+              ));
+            }
+
+            *value = Box::new(Expr::Lit(Lit::Str("classic".into())));
+          }
+        }
+      }
+    }
+
+    true
+  }
+
+  /// The resources are classic scripts in browser, where `import.meta` is a syntax error. The `import.meta` left after
+  /// replacing the asset urls, e.g. `new URL('https://xxx/a.png', import.meta.url)`, is replaced:
+  /// `import.meta.url` => the url base and `import.meta` => `{ url: base }`, see [SourceReplacer::url_base]
+  fn replace_import_meta(&self, expr: &Expr) -> Option<Expr> {
+    if self.config.output.target_env != TargetEnv::Browser {
+      return None;
+    }
+
+    match expr {
+      Expr::Member(MemberExpr {
+        obj:
+          box Expr::MetaProp(MetaPropExpr {
+            kind: MetaPropKind::ImportMeta,
+            ..
+          }),
+        prop: MemberProp::Ident(Ident { sym, .. }),
+        ..
+      }) if sym == "url" => Some(self.url_base()),
+      Expr::MetaProp(MetaPropExpr {
+        kind: MetaPropKind::ImportMeta,
+        ..
+      }) => Some(Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props: vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+          key: PropName::Ident(Ident::new("url".into(), DUMMY_SP)),
+          value: Box::new(self.url_base()),
+        })))],
+      })),
+      _ => None,
+    }
+  }
+
+  /// The base of the replaced urls, `import.meta.url` for node. For browser, it's the public path resolved against the page,
+  /// where the resources are loaded from: `new URL("/public/", location.href).href`. `location.href` itself is the url of the page
+  /// and `document.currentScript` is null when the modules are executed after the script is loaded.
+  fn url_base(&self) -> Expr {
+    match self.config.output.target_env {
+      TargetEnv::Browser => {
+        let location_href = Expr::Member(MemberExpr {
+          span: DUMMY_SP,
+          obj: Box::new(Expr::Ident(Ident::new("location".into(), DUMMY_SP))),
+          prop: MemberProp::Ident(Ident::new("href".into(), DUMMY_SP)),
+        });
+        let public_path = Expr::New(NewExpr {
+          span: DUMMY_SP,
+          callee: Box::new(Expr::Ident(Ident::new("URL".into(), DUMMY_SP))),
+          args: Some(vec![
+            ExprOrSpread {
+              spread: None,
+              expr: Box::new(Expr::Lit(Lit::Str(
+                self.config.output.public_path.as_str().into(),
+              ))),
+            },
+            ExprOrSpread {
+              spread: None,
+              expr: Box::new(location_href),
+            },
+          ]),
+          type_args: None,
+        });
+
+        Expr::Member(MemberExpr {
+          span: DUMMY_SP,
+          obj: Box::new(public_path),
+          prop: MemberProp::Ident(Ident::new("href".into(), DUMMY_SP)),
+        })
+      }
+      TargetEnv::Node => Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::MetaProp(MetaPropExpr {
+          span: DUMMY_SP,
+          kind: MetaPropKind::ImportMeta,
+        })),
+        prop: MemberProp::Ident(Ident::new("url".into(), DUMMY_SP)),
+      }),
+    }
+  }
 }
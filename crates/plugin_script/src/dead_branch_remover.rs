@@ -0,0 +1,116 @@
+use farmfe_core::{
+  swc_common::{util::take::Take, Mark, SyntaxContext, DUMMY_SP},
+  swc_ecma_ast::{BinExpr, BinaryOp, BlockStmt, CondExpr, Decl, Expr, IfStmt, Stmt},
+};
+use farmfe_toolkit::{
+  swc_ecma_transforms::optimization::simplify::expr_simplifier,
+  swc_ecma_utils::{ExprCtx, ExprExt, StmtExt, Value},
+  swc_ecma_visit::{VisitMut, VisitMutWith},
+};
+
+/// Remove the branches whose condition is statically known after defines are replaced, for example:
+/// ```js
+/// if ("production" !== "production") { require('./dev') } // removed
+/// "development" === "production" && require('./prod'); // `require('./prod')` removed
+/// ```
+/// Unlike swc's `dead_branch_remover`, only the branches with known conditions are touched, other statements are kept as is.
+pub struct DeadBranchRemover {
+  unresolved_mark: Mark,
+  expr_ctx: ExprCtx,
+}
+
+impl DeadBranchRemover {
+  pub fn new(unresolved_mark: Mark) -> Self {
+    Self {
+      unresolved_mark,
+      expr_ctx: ExprCtx {
+        unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+        is_unresolved_ref_safe: false,
+      },
+    }
+  }
+
+  /// Return the boolean value of the test if it's side effects free and can be evaluated statically
+  fn eval_test(&self, test: &Expr) -> Option<bool> {
+    // fold constants like `"production" !== "production"` on a copy, the original test is kept if the value is unknown
+    let mut test = test.clone();
+    test.visit_mut_with(&mut expr_simplifier(
+      self.unresolved_mark,
+      Default::default(),
+    ));
+
+    match test.as_pure_bool(&self.expr_ctx) {
+      Value::Known(v) => Some(v),
+      Value::Unknown => None,
+    }
+  }
+}
+
+impl VisitMut for DeadBranchRemover {
+  fn visit_mut_stmt(&mut self, stmt: &mut Stmt) {
+    stmt.visit_mut_children_with(self);
+
+    if let Stmt::If(IfStmt {
+      test, cons, alt, ..
+    }) = stmt
+    {
+      if let Some(v) = self.eval_test(test) {
+        let (kept, removed) = if v {
+          (Some(cons.take()), alt.take())
+        } else {
+          (alt.take(), Some(cons.take()))
+        };
+
+        let mut stmts = vec![];
+        // var declarations are hoisted, they should be preserved even if the branch is removed
+        if let Some(var) = removed.and_then(|s| s.extract_var_ids_as_var()) {
+          stmts.push(Stmt::Decl(Decl::Var(Box::new(var))));
+        }
+
+        if let Some(kept) = kept {
+          stmts.push(*kept);
+        }
+
+        *stmt = match stmts.len() {
+          0 => Stmt::dummy(),
+          1 => stmts.remove(0),
+          _ => Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+          }),
+        };
+      }
+    }
+  }
+
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    expr.visit_mut_children_with(self);
+
+    match expr {
+      Expr::Cond(CondExpr {
+        test, cons, alt, ..
+      }) => {
+        if let Some(v) = self.eval_test(test) {
+          *expr = if v { *cons.take() } else { *alt.take() };
+        }
+      }
+      Expr::Bin(BinExpr {
+        op: op @ (BinaryOp::LogicalAnd | BinaryOp::LogicalOr),
+        left,
+        right,
+        ..
+      }) => {
+        if let Some(v) = self.eval_test(left) {
+          // `truthy && right` and `falsy || right` are evaluated to `right`, otherwise evaluated to `left`
+          let keep_right = (*op == BinaryOp::LogicalAnd) == v;
+          *expr = if keep_right {
+            *right.take()
+          } else {
+            *left.take()
+          };
+        }
+      }
+      _ => {}
+    }
+  }
+}
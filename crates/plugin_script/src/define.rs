@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use farmfe_core::{
+  config::{Config, Mode},
+  context::env_store::EnvStore,
+  swc_common::{Mark, DUMMY_SP},
+  swc_ecma_ast::{
+    AssignExpr, Bool, Expr, Ident, KeyValueProp, Lit, MemberExpr, MemberProp, MetaPropExpr,
+    MetaPropKind, ObjectLit, ParenExpr, Prop, PropName, PropOrSpread, Str, UnaryExpr, UnaryOp,
+    UpdateExpr,
+  },
+};
+use farmfe_toolkit::swc_ecma_visit::{VisitMut, VisitMutWith};
+
+const IMPORT_META_ENV: &str = "import.meta.env";
+
+/// Replace the global expressions defined in `config.define` (e.g. `FARM_VERSION`, `process.env.NODE_ENV`) and `import.meta.env.*`
+/// with their values, so that the branches depending on them can be removed statically.
+/// Only unresolved (global) references are replaced, local bindings with the same name are kept untouched.
+pub struct DefineReplacer<'a> {
+  defines: &'a HashMap<String, Expr>,
+  unresolved_mark: Mark,
+  /// whether `import.meta.env` is used in this module
+  pub import_meta_env_used: bool,
+}
+
+impl<'a> DefineReplacer<'a> {
+  pub fn new(defines: &'a HashMap<String, Expr>, unresolved_mark: Mark) -> Self {
+    Self {
+      defines,
+      unresolved_mark,
+      import_meta_env_used: false,
+    }
+  }
+
+  /// Get the dotted path of expressions like `a.b.c`, return [None] if the root of the path is not a global variable or `import.meta`
+  fn expr_path(&self, expr: &Expr) -> Option<String> {
+    match expr {
+      Expr::Ident(Ident { sym, span, .. }) if span.ctxt.outer() == self.unresolved_mark => {
+        Some(sym.to_string())
+      }
+      Expr::MetaProp(MetaPropExpr {
+        kind: MetaPropKind::ImportMeta,
+        ..
+      }) => Some("import.meta".to_string()),
+      Expr::Member(MemberExpr { obj, prop, .. }) => {
+        let prop = match prop {
+          MemberProp::Ident(ident) => ident.sym.to_string(),
+          MemberProp::Computed(computed) => match &*computed.expr {
+            Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+            _ => return None,
+          },
+          MemberProp::PrivateName(_) => return None,
+        };
+
+        self.expr_path(obj).map(|obj| format!("{}.{}", obj, prop))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl<'a> VisitMut for DefineReplacer<'a> {
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    if let Some(path) = self.expr_path(expr) {
+      if let Some(value) = self.defines.get(&path) {
+        self.import_meta_env_used |= path.starts_with(IMPORT_META_ENV);
+        *expr = value.clone();
+        return;
+      }
+
+      // env variables that are not defined, e.g. `import.meta.env.UNKNOWN`, are replaced by `undefined`
+      if let Some(key) = path.strip_prefix(&format!("{}.", IMPORT_META_ENV)) {
+        if !key.contains('.') {
+          self.import_meta_env_used = true;
+          *expr = Expr::Unary(UnaryExpr {
+            span: DUMMY_SP,
+            op: UnaryOp::Void,
+            arg: Box::new(Expr::Lit(Lit::Num(0.into()))),
+          });
+          return;
+        }
+      }
+    }
+
+    expr.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_assign_expr(&mut self, assign_expr: &mut AssignExpr) {
+    // the assignment target should never be replaced, e.g. `process.env.NODE_ENV = 'test'`
+    assign_expr.right.visit_mut_with(self);
+  }
+
+  fn visit_mut_update_expr(&mut self, _update_expr: &mut UpdateExpr) {
+    // the update target should never be replaced, e.g. `FARM_COUNTER++`
+  }
+}
+
+/// All defines that should be replaced at compile time, including:
+/// * the user defined `config.define` and the `process.env.NODE_ENV` derived from `config.mode`
+/// * `import.meta.env.*` loaded from the env files, and the builtin `MODE`, `DEV`, `PROD` derived from `config.mode`
+pub fn get_defines(config: &Config, env_store: &EnvStore) -> HashMap<String, Expr> {
+  let mode = config.mode.to_string();
+  let mut defines = HashMap::from([("process.env.NODE_ENV".to_string(), str_expr(&mode))]);
+
+  for (key, value) in &config.define {
+    defines.insert(key.clone(), str_expr(value));
+  }
+
+  let is_dev = matches!(config.mode, Mode::Development);
+  let mut env = vec![
+    ("MODE".to_string(), str_expr(&mode)),
+    ("DEV".to_string(), bool_expr(is_dev)),
+    ("PROD".to_string(), bool_expr(!is_dev)),
+  ];
+  let mut vars = env_store.vars().iter().collect::<Vec<_>>();
+  // keep the generated `import.meta.env` object stable
+  vars.sort();
+  env.extend(
+    vars
+      .into_iter()
+      .map(|(key, value)| (key.clone(), str_expr(value))),
+  );
+
+  // `import.meta.env` itself is replaced by an object so dynamic access like `import.meta.env[key]` still works
+  let env_object = Expr::Paren(ParenExpr {
+    span: DUMMY_SP,
+    expr: Box::new(Expr::Object(ObjectLit {
+      span: DUMMY_SP,
+      props: env
+        .iter()
+        .map(|(key, value)| {
+          PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Str(key.as_str().into()),
+            value: Box::new(value.clone()),
+          })))
+        })
+        .collect(),
+    })),
+  });
+
+  for (key, value) in env {
+    defines.insert(format!("{}.{}", IMPORT_META_ENV, key), value);
+  }
+  defines.insert(IMPORT_META_ENV.to_string(), env_object);
+
+  defines
+}
+
+fn str_expr(value: &str) -> Expr {
+  Expr::Lit(Lit::Str(Str {
+    span: DUMMY_SP,
+    value: value.into(),
+    raw: None,
+  }))
+}
+
+fn bool_expr(value: bool) -> Expr {
+  Expr::Lit(Lit::Bool(Bool {
+    span: DUMMY_SP,
+    value,
+  }))
+}
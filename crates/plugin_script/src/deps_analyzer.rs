@@ -1,20 +1,18 @@
 use farmfe_core::{
-  module::ModuleId,
-  plugin::{PluginAnalyzeDepsHookResultEntry, ResolveKind},
+  plugin::{constants::WORKER_RESOLVE_KIND, PluginAnalyzeDepsHookResultEntry, ResolveKind},
   swc_common::Mark,
   swc_ecma_ast::{
-    CallExpr, ExportAll, Expr, Lit, Module, ModuleDecl, ModuleItem, NamedExport,
-    TsExternalModuleRef, TsImportEqualsDecl,
+    CallExpr, ExportAll, Expr, ExprOrSpread, Lit, Module, ModuleDecl, ModuleItem, NamedExport,
+    NewExpr, TsExternalModuleRef, TsImportEqualsDecl, TsModuleRef,
   },
 };
 
 use farmfe_toolkit::{
-  script::{is_commonjs_require, is_dynamic_import},
+  script::{is_commonjs_require, is_dynamic_import, is_import_meta_url, is_worker},
   swc_ecma_visit::{Visit, VisitWith},
 };
 
 pub struct DepsAnalyzer<'a> {
-  module_id: &'a ModuleId,
   ast: &'a Module,
   deps: Option<Vec<PluginAnalyzeDepsHookResultEntry>>,
   unresolved_mark: Mark,
@@ -22,14 +20,8 @@ pub struct DepsAnalyzer<'a> {
 }
 
 impl<'a> DepsAnalyzer<'a> {
-  pub fn new(
-    module_id: &'a ModuleId,
-    ast: &'a Module,
-    unresolved_mark: Mark,
-    top_level_mark: Mark,
-  ) -> Self {
+  pub fn new(ast: &'a Module, unresolved_mark: Mark, top_level_mark: Mark) -> Self {
     Self {
-      module_id,
       ast,
       deps: None,
       unresolved_mark,
@@ -76,17 +68,15 @@ impl<'a> Visit for DepsAnalyzer<'a> {
           }
         }
         ModuleDecl::TsImportEquals(box TsImportEqualsDecl { module_ref, .. }) => match module_ref {
-          farmfe_core::swc_ecma_ast::TsModuleRef::TsEntityName(_) => {
-            panic!("TsEntityName not supported in {:?}", self.module_id)
+          // `import x = Foo.Bar` is an alias of a namespace, it does not create a dependency
+          TsModuleRef::TsEntityName(_) => {}
+          TsModuleRef::TsExternalModuleRef(TsExternalModuleRef { expr, .. }) => {
+            self.insert_dep(PluginAnalyzeDepsHookResultEntry {
+              source: expr.value.to_string(),
+              // treat TsImportEquals as require cause it only works in commonjs
+              kind: ResolveKind::Require,
+            })
           }
-          farmfe_core::swc_ecma_ast::TsModuleRef::TsExternalModuleRef(TsExternalModuleRef {
-            expr,
-            ..
-          }) => self.insert_dep(PluginAnalyzeDepsHookResultEntry {
-            source: expr.value.to_string(),
-            // treat TsImportEquals as require cause it only works in commonjs
-            kind: ResolveKind::Require,
-          }),
         },
         _ => {
           n.visit_children_with(self);
@@ -122,4 +112,51 @@ impl<'a> Visit for DepsAnalyzer<'a> {
 
     call_expr.visit_children_with(self);
   }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr) {
+    let args = new_expr.args.as_deref().unwrap_or_default();
+
+    if is_worker(self.unresolved_mark, new_expr) {
+      if let [ExprOrSpread {
+        expr: box Expr::New(url),
+        ..
+      }, rest @ ..] = args
+      {
+        if let Some(
+          [ExprOrSpread {
+            expr: box Expr::Lit(Lit::Str(str)),
+            ..
+          }, ..],
+        ) = url.args.as_deref()
+        {
+          self.insert_dep(PluginAnalyzeDepsHookResultEntry {
+            source: str.value.to_string(),
+            kind: ResolveKind::Custom(WORKER_RESOLVE_KIND.to_string()),
+          })
+        }
+
+        // the url of the worker is not an asset, skip visiting it
+        for arg in rest {
+          arg.visit_with(self);
+        }
+      }
+
+      return;
+    }
+
+    if is_import_meta_url(self.unresolved_mark, new_expr) {
+      if let [ExprOrSpread {
+        expr: box Expr::Lit(Lit::Str(str)),
+        ..
+      }, ..] = args
+      {
+        self.insert_dep(PluginAnalyzeDepsHookResultEntry {
+          source: str.value.to_string(),
+          kind: ResolveKind::ImportMetaUrl,
+        })
+      }
+    }
+
+    new_expr.visit_children_with(self);
+  }
 }
@@ -0,0 +1,69 @@
+use farmfe_core::{
+  swc_common::{comments::SingleThreadedComments, Mark},
+  swc_ecma_ast::{EsVersion, Module},
+};
+use farmfe_toolkit::{
+  swc_ecma_transforms::{
+    compat::{es2015, es2016, es2017, es2018, es2019, es2020, es2021, es2022, es3},
+    helpers::inject_helpers,
+  },
+  swc_ecma_visit::{FoldWith, VisitMutWith},
+};
+
+/// Transform the syntax newer than `target` down to `target`, e.g. class fields, optional chaining and async generators.
+/// The helpers are imported from `@swc/helpers`, which is aliased to `config.runtime.swc_helpers_path`.
+pub fn downlevel(
+  mut ast: Module,
+  target: EsVersion,
+  unresolved_mark: Mark,
+  comments: &SingleThreadedComments,
+) -> Module {
+  let comments = Some(comments);
+
+  if target < EsVersion::Es2022 {
+    ast = ast.fold_with(&mut es2022::es2022(comments, Default::default()));
+  }
+
+  if target < EsVersion::Es2021 {
+    ast = ast.fold_with(&mut es2021::es2021());
+  }
+
+  if target < EsVersion::Es2020 {
+    ast = ast.fold_with(&mut es2020::es2020(Default::default()));
+  }
+
+  if target < EsVersion::Es2019 {
+    ast = ast.fold_with(&mut es2019::es2019());
+  }
+
+  if target < EsVersion::Es2018 {
+    ast = ast.fold_with(&mut es2018::es2018(Default::default()));
+  }
+
+  if target < EsVersion::Es2017 {
+    ast = ast.fold_with(&mut es2017::es2017(
+      Default::default(),
+      comments,
+      unresolved_mark,
+    ));
+  }
+
+  if target < EsVersion::Es2016 {
+    ast = ast.fold_with(&mut es2016::es2016());
+  }
+
+  if target < EsVersion::Es2015 {
+    ast = ast.fold_with(&mut es2015::es2015(
+      unresolved_mark,
+      comments,
+      Default::default(),
+    ));
+  }
+
+  if target < EsVersion::Es5 {
+    ast = ast.fold_with(&mut es3::es3(true));
+  }
+
+  ast.visit_mut_with(&mut inject_helpers(unresolved_mark));
+  ast
+}
@@ -3,86 +3,257 @@ use std::sync::Arc;
 use farmfe_core::{
   config::{ModuleFormat, TargetEnv, FARM_GLOBAL_THIS, FARM_MODULE_SYSTEM, FARM_NAMESPACE},
   context::CompilationContext,
-  hashbrown::HashMap,
-  module::{module_graph::ModuleGraph, module_group::ModuleGroupGraph, Module, ModuleId},
-  resource::{Resource, ResourceType},
-  swc_ecma_ast::{ModuleDecl, ModuleItem},
+  hashbrown::{HashMap, HashSet},
+  module::{
+    module_graph::ModuleGraph, module_group::ModuleGroupGraph, Module, ModuleId, ModuleMetaData,
+  },
+  resource::{Resource, ResourceOrigin, ResourceType},
+  serde_json,
+  swc_ecma_ast::{
+    Decl, ExportAll, ExportDecl, ExportDefaultSpecifier, ExportNamedSpecifier,
+    ExportNamespaceSpecifier, ExportSpecifier, Ident, ModuleDecl, ModuleExportName, ModuleItem,
+    NamedExport,
+  },
 };
-use farmfe_toolkit::get_dynamic_resources_map::{
-  get_dynamic_resources_code, get_dynamic_resources_map,
+use farmfe_toolkit::{
+  get_dynamic_resources_map::{get_dynamic_resources_code, get_dynamic_resources_map},
+  script::{library_chunk_placeholder, worker_url_placeholder},
+  swc_ecma_utils::find_pat_ids,
 };
 
 use crate::FARM_NODE_MODULE;
 
+/// Get the export names of the entry module, all the esm export syntaxes are supported:
+/// ```js
+/// export const a = 1, { b } = obj;
+/// export function c() {}
+/// export { d, e as f, g as 'h-i' };
+/// export { default, j as k } from './dep';
+/// export * as ns from './dep';
+/// export * from './dep'; // the export names of `./dep` except `default`
+/// export default 1;
+/// ```
+/// The export names of external modules and commonjs modules are unknown, so they are not included.
 pub fn get_export_info_of_entry_module(
   entry_module_id: &ModuleId,
   module_graph: &ModuleGraph,
   _context: &Arc<CompilationContext>,
 ) -> Vec<String> {
-  let entry_module = module_graph
-    .module(entry_module_id)
-    .expect("entry module is not found");
-
-  let ast = &entry_module.meta.as_script().ast;
   let mut export_info = vec![];
+  collect_export_names(
+    entry_module_id,
+    module_graph,
+    true,
+    &mut HashSet::new(),
+    &mut export_info,
+  );
+
+  export_info
+}
+
+fn collect_export_names(
+  module_id: &ModuleId,
+  module_graph: &ModuleGraph,
+  include_default: bool,
+  visited: &mut HashSet<ModuleId>,
+  export_names: &mut Vec<String>,
+) {
+  if !visited.insert(module_id.clone()) {
+    return;
+  }
+
+  let module = module_graph
+    .module(module_id)
+    .expect("module is not found in module graph");
+
+  let ModuleMetaData::Script(meta) = &module.meta else {
+    return;
+  };
+
+  if module.external {
+    return;
+  }
+
+  let mut push = |name: String| {
+    // `export *` does not re-export the default export
+    if (include_default || name != "default") && !export_names.contains(&name) {
+      export_names.push(name);
+    }
+  };
+  let mut export_all_sources = vec![];
 
-  for item in ast.body.iter() {
-    match item {
-      ModuleItem::ModuleDecl(module_decl) => match module_decl {
-        // TODO: support more export syntax
-
-        // ModuleDecl::ExportDecl(export_decl) => {
-        //   if let Decl::Class(class_decl) = &export_decl.decl {
-        //     export_info.push(class_decl.ident.sym.to_string());
-        //   }
-        // }
-        // ModuleDecl::ExportNamed(named_export) => {
-        //   for specifier in named_export.specifiers.iter() {
-        //     match specifier {
-        //       ExportSpecifier::Named(named_specifier) => {
-        //         export_info.push(named_specifier.orig.sym.to_string());
-        //       }
-        //       _ => {}
-        //     }
-        //   }
-        // }
-        ModuleDecl::ExportDefaultDecl(_) | ModuleDecl::ExportDefaultExpr(_) => {
-          export_info.push("default".to_string());
+  for item in &meta.ast.body {
+    let ModuleItem::ModuleDecl(module_decl) = item else {
+      continue;
+    };
+
+    match module_decl {
+      ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => match decl {
+        Decl::Class(class_decl) => push(class_decl.ident.sym.to_string()),
+        Decl::Fn(fn_decl) => push(fn_decl.ident.sym.to_string()),
+        Decl::Var(var_decl) => {
+          for ident in find_pat_ids::<_, Ident>(&var_decl.decls) {
+            push(ident.sym.to_string());
+          }
         }
+        // typescript declarations are stripped already
         _ => {}
       },
+      ModuleDecl::ExportNamed(NamedExport { specifiers, .. }) => {
+        for specifier in specifiers {
+          match specifier {
+            ExportSpecifier::Named(ExportNamedSpecifier { orig, exported, .. }) => {
+              push(module_export_name(exported.as_ref().unwrap_or(orig)))
+            }
+            ExportSpecifier::Namespace(ExportNamespaceSpecifier { name, .. }) => {
+              push(module_export_name(name))
+            }
+            ExportSpecifier::Default(ExportDefaultSpecifier { exported }) => {
+              push(exported.sym.to_string())
+            }
+          }
+        }
+      }
+      ModuleDecl::ExportDefaultDecl(_) | ModuleDecl::ExportDefaultExpr(_) => {
+        push("default".to_string())
+      }
+      ModuleDecl::ExportAll(ExportAll { src, .. }) => {
+        export_all_sources.push(src.value.to_string())
+      }
       _ => {}
     }
   }
 
-  export_info
+  // the explicit exports take precedence over the names of `export *`
+  for source in export_all_sources {
+    if let Some(dep) = module_graph.get_dep_by_source_optional(module_id, &source) {
+      collect_export_names(&dep, module_graph, false, visited, export_names);
+    }
+  }
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
 }
 
+/// `entry.a` for identifiers and `entry["h-i"]` for other names
+fn member_access(obj: &str, name: &str) -> String {
+  if is_valid_ident(name) {
+    format!("{}.{}", obj, name)
+  } else {
+    format!("{}[{}]", obj, string_literal(name))
+  }
+}
+
+/// Quote the name as a js string literal, e.g. `h-i` => `"h-i"`
+fn string_literal(name: &str) -> String {
+  // a json string is a valid js string literal except the line terminators U+2028 and U+2029 before ES2019
+  serde_json::to_string(name)
+    .unwrap()
+    .replace('\u{2028}', "\\u2028")
+    .replace('\u{2029}', "\\u2029")
+}
+
+fn is_valid_ident(name: &str) -> bool {
+  let mut chars = name.chars();
+
+  chars
+    .next()
+    .map_or(false, |c| c.is_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Surface the exports of the entry module as the exports of the entry resource, for example:
+/// ```js
+/// // cjs, a module that only has a default export is exported as `module.exports` directly
+/// module.exports.a = entry.a;module.exports.default = entry.default;
+/// // esm
+/// var __farm_entry_export_0__ = entry.a;export { __farm_entry_export_0__ as a };export default entry.default;
+/// ```
+/// Note that the exports are read once the entry module is executed, they are snapshots rather than live bindings:
+/// reassigning an exported `let` in the entry module later is not visible to the importers of the entry resource.
+///
+/// If the entry is an async module(uses top level await or imports async modules), `entry` is the promise of its exports, it's awaited
+/// by the esm entry resource, and the exports of the cjs entry resource are assigned after it's resolved:
+/// ```js
+/// var entry = farmModuleSystem.require("xxx", true);entry.then(function (entry) {module.exports.a = entry.a;});
+/// ```
 fn get_export_info_code(
   entry_module_id: &ModuleId,
   module_graph: &ModuleGraph,
+  is_async: bool,
   context: &Arc<CompilationContext>,
 ) -> String {
   let export_info = get_export_info_of_entry_module(entry_module_id, module_graph, context);
 
-  if !export_info.is_empty() {
-    export_info
-      .iter()
-      .map(|export| {
-        if export == "default" {
-          match context.config.output.format {
-            ModuleFormat::CommonJs => "module.exports = entry.default;".to_string(),
-            ModuleFormat::EsModule => "export default entry.default;".to_string(),
-          }
+  if export_info.is_empty() {
+    return "".to_string();
+  }
+
+  match context.config.output.format {
+    ModuleFormat::CommonJs => {
+      // `module.exports` can not be replaced after the async entry resource is required
+      if !is_async && export_info.len() == 1 && export_info[0] == "default" {
+        return "module.exports = entry.default;".to_string();
+      }
+
+      let es_module_flag = if export_info.iter().any(|e| e == "default") {
+        r#"Object.defineProperty(module.exports, "__esModule", { value: true });"#
+      } else {
+        ""
+      };
+      let mut code = String::new();
+
+      for export in &export_info {
+        code.push_str(&format!(
+          "{} = {};",
+          member_access("module.exports", export),
+          member_access("entry", export)
+        ));
+      }
+
+      if is_async {
+        format!(
+          "{}entry.then(function (entry) {{{}}});",
+          es_module_flag, code
+        )
+      } else {
+        format!("{}{}", es_module_flag, code)
+      }
+    }
+    ModuleFormat::EsModule => {
+      let mut code = String::new();
+      let mut specifiers = vec![];
+
+      for (i, export) in export_info.iter().filter(|e| *e != "default").enumerate() {
+        let local = format!("__farm_entry_export_{}__", i);
+        code.push_str(&format!(
+          "var {} = {};",
+          local,
+          member_access("entry", export)
+        ));
+
+        if is_valid_ident(export) {
+          specifiers.push(format!("{} as {}", local, export));
         } else {
-          // format!("export {{ {}: entry.{} }};", export, export)
-          panic!("named export is not supported");
+          specifiers.push(format!("{} as {}", local, string_literal(export)));
         }
-      })
-      .collect::<Vec<String>>()
-      .join("")
-  } else {
-    "".to_string()
+      }
+
+      if !specifiers.is_empty() {
+        code.push_str(&format!("export {{ {} }};", specifiers.join(", ")));
+      }
+
+      if export_info.iter().any(|e| e == "default") {
+        code.push_str("export default entry.default;");
+      }
+
+      code
+    }
+    ModuleFormat::Iife | ModuleFormat::Umd => unreachable!("iife and umd are rendered as library"),
   }
 }
 
@@ -151,8 +322,31 @@ pub fn handle_entry_resources(
   resources_map: &mut HashMap<String, Resource>,
   context: &Arc<CompilationContext>,
 ) {
+  // the library chunks import each other natively, there is no runtime to inject
+  if context.config.output.library {
+    let chunk_urls = resources_map
+      .values()
+      .filter_map(
+        |resource| match (&resource.resource_type, &resource.origin) {
+          (ResourceType::Js | ResourceType::Css, ResourceOrigin::ResourcePot(resource_pot_id)) => {
+            Some((
+              library_chunk_placeholder(&resource_pot_id.to_string()),
+              format!("./{}", resource.name),
+            ))
+          }
+          _ => None,
+        },
+      )
+      .collect::<Vec<_>>();
+    replace_placeholders(resources_map, &chunk_urls);
+
+    return;
+  }
+
   let module_graph = context.module_graph.read();
   let module_group_graph = context.module_group_graph.read();
+  // (placeholder, url) of the web workers, see [worker_url_placeholder]
+  let mut worker_urls = vec![];
 
   for (entry, _) in &module_graph.entries {
     let module = module_graph
@@ -169,6 +363,17 @@ pub fn handle_entry_resources(
           resources_map,
           context,
         );
+      let is_worker = module_graph
+        .dependents(entry)
+        .iter()
+        .any(|(_, edge)| edge.is_worker());
+
+      if is_worker {
+        worker_urls.push((
+          worker_url_placeholder(&entry.id(context.config.mode.clone())),
+          worker_url(&entry_js_resource_name, context),
+        ));
+      }
 
       // 1. node specific code.
       // TODO: support async module for node, using dynamic require to load external module instead of createRequire. createRequire does not support load ESM module.
@@ -181,6 +386,7 @@ pub fn handle_entry_resources(
           }
           ModuleFormat::CommonJs => r#"var __farmNodeRequire = require;var __farmNodeBuiltinModules = require('node:module').builtinModules;"#
             .to_string(), // _ => panic!("node only support cjs and esm format"),
+          ModuleFormat::Iife | ModuleFormat::Umd => unreachable!("iife and umd are rendered as library"),
         }
       } else {
         "".to_string()
@@ -207,13 +413,24 @@ pub fn handle_entry_resources(
       )
       .unwrap();
 
-      // 4. __farmNodeRequire(dep) to entry resource if target env is node
+      // 4. __farmNodeRequire(dep) to entry resource if target env is node, or importScripts(dep) if the entry is a web worker
       let dep_resources_require_code = if context.config.output.target_env == TargetEnv::Node {
         dep_resources
           .iter()
           .map(|rn| format!("__farmNodeRequire('./{}');", rn))
           .collect::<Vec<_>>()
           .join("\n")
+      } else if is_worker {
+        dep_resources
+          .iter()
+          .map(|rn| {
+            format!(
+              "importScripts('{}{}');",
+              context.config.output.public_path, rn
+            )
+          })
+          .collect::<Vec<_>>()
+          .join("\n")
       } else {
         "".to_string()
       };
@@ -230,16 +447,37 @@ pub fn handle_entry_resources(
         r#"{FARM_GLOBAL_THIS}.{FARM_MODULE_SYSTEM}.setDynamicModuleResourcesMap({dynamic_resources_code});"#,
       );
 
-      // 6. append call entry
+      // 6. append call entry, the exports of the async entry are resolved after it's initialized, see [get_export_info_code]
+      let is_async = module.is_async && !is_worker;
+      let require_entry = if is_async {
+        format!(
+          r#"farmModuleSystem.require("{}", true)"#,
+          entry.id(context.config.mode.clone())
+        )
+      } else {
+        format!(
+          r#"farmModuleSystem.require("{}")"#,
+          entry.id(context.config.mode.clone())
+        )
+      };
       let call_entry_code = format!(
-        r#"var farmModuleSystem = {}.{};farmModuleSystem.bootstrap();var entry = farmModuleSystem.require("{}");"#,
+        r#"var farmModuleSystem = {}.{};farmModuleSystem.bootstrap();var entry = {}{};"#,
         FARM_GLOBAL_THIS,
         FARM_MODULE_SYSTEM,
-        entry.id(context.config.mode.clone()),
+        if is_async && context.config.output.format == ModuleFormat::EsModule {
+          "await "
+        } else {
+          ""
+        },
+        require_entry,
       );
 
-      // 7. append export code
-      let export_info_code = get_export_info_code(entry, &module_graph, context);
+      // 7. append export code, web workers are classic scripts that have no exports
+      let export_info_code = if is_worker {
+        "".to_string()
+      } else {
+        get_export_info_code(entry, &module_graph, is_async, context)
+      };
 
       let entry_js_resource_code = String::from_utf8(
         resources_map
@@ -281,4 +519,77 @@ pub fn handle_entry_resources(
       .into_bytes();
     }
   }
+
+  // replace the placeholders with the url of the worker entry resources
+  replace_placeholders(resources_map, &worker_urls);
+}
+
+/// The url of the worker entry resource, it's relative to the public path, which is the base of the `new URL()`
+fn worker_url(entry_js_resource_name: &str, context: &Arc<CompilationContext>) -> String {
+  match context.config.output.target_env {
+    TargetEnv::Browser => entry_js_resource_name.to_string(),
+    TargetEnv::Node => format!("./{}", entry_js_resource_name),
+  }
+}
+
+/// Get the (placeholder, url) pairs of all the web workers whose entry resources are generated, see [worker_url_placeholder].
+/// The hmr update resource is not finalized by [handle_entry_resources], so the placeholders in it are replaced by these urls.
+pub fn get_worker_urls(context: &Arc<CompilationContext>) -> Vec<(String, String)> {
+  let module_graph = context.module_graph.read();
+  let module_group_graph = context.module_group_graph.read();
+  let resource_pot_map = context.resource_pot_map.read();
+  let resources_map = context.resources_map.lock();
+  let mut worker_urls = vec![];
+
+  for entry in module_graph.entries.keys() {
+    if !module_graph.is_worker_entry(entry) {
+      continue;
+    }
+
+    let Some(module_group) = module_group_graph.module_group(entry) else {
+      continue;
+    };
+
+    let entry_js_resource_name = module_group
+      .resource_pots()
+      .iter()
+      .filter_map(|resource_pot_id| resource_pot_map.resource_pot(resource_pot_id))
+      .filter(|resource_pot| resource_pot.entry_module.as_ref() == Some(entry))
+      .flat_map(|resource_pot| resource_pot.resources())
+      .filter_map(|resource_id| resources_map.get(resource_id))
+      .find(|resource| matches!(resource.resource_type, ResourceType::Js))
+      .map(|resource| resource.name.clone());
+
+    if let Some(entry_js_resource_name) = entry_js_resource_name {
+      worker_urls.push((
+        worker_url_placeholder(&entry.id(context.config.mode.clone())),
+        worker_url(&entry_js_resource_name, context),
+      ));
+    }
+  }
+
+  worker_urls
+}
+
+/// replace the (placeholder, url) pairs in the js resources
+fn replace_placeholders(resources_map: &mut HashMap<String, Resource>, urls: &[(String, String)]) {
+  if urls.is_empty() {
+    return;
+  }
+
+  for resource in resources_map.values_mut() {
+    if !matches!(resource.resource_type, ResourceType::Js) {
+      continue;
+    }
+
+    let mut code = String::from_utf8(std::mem::take(&mut resource.bytes)).unwrap();
+
+    for (placeholder, url) in urls {
+      if code.contains(placeholder.as_str()) {
+        code = code.replace(placeholder.as_str(), url);
+      }
+    }
+
+    resource.bytes = code.into_bytes();
+  }
 }
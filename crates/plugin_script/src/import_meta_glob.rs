@@ -0,0 +1,608 @@
+use std::path::{Component, Path, PathBuf};
+
+use farmfe_core::{
+  glob::{glob, Pattern},
+  swc_common::{Mark, SyntaxContext, DUMMY_SP},
+  swc_ecma_ast::{
+    ArrayLit, ArrowExpr, BinExpr, BinaryOp, BlockStmtOrExpr, CallExpr, Callee, ComputedPropName,
+    CondExpr, Expr, ExprOrSpread, Ident, Import, ImportDecl, ImportNamedSpecifier, ImportSpecifier,
+    ImportStarAsSpecifier, KeyValueProp, Lit, MemberExpr, MemberProp, MetaPropExpr, MetaPropKind,
+    Module, ModuleDecl, ModuleExportName, ModuleItem, NewExpr, ObjectLit, ParenExpr, Pat, Prop,
+    PropName, PropOrSpread, Str, Tpl,
+  },
+};
+use farmfe_toolkit::{
+  script::is_dynamic_import,
+  swc_ecma_visit::{VisitMut, VisitMutWith},
+};
+
+/// Expand `import.meta.glob(patterns, { eager, import, query })` at build time, for example:
+/// ```js
+/// const pages = import.meta.glob('./pages/*.tsx');
+/// // =>
+/// const pages = { "./pages/a.tsx": () => import("./pages/a.tsx"), "./pages/b.tsx": () => import("./pages/b.tsx") };
+///
+/// const pages = import.meta.glob('./pages/*.tsx', { eager: true, import: 'default' });
+/// // =>
+/// import { default as __farm_glob_0_0 } from "./pages/a.tsx";
+/// const pages = { "./pages/a.tsx": __farm_glob_0_0 };
+/// ```
+/// The generated imports are analyzed by [crate::deps_analyzer::DepsAnalyzer] like the other imports.
+/// Patterns starting with `/` are relative to `config.root`, and patterns starting with `!` exclude the matched files.
+///
+/// Dynamic imports with template literals or string concatenations are expanded the same way, each expression is matched by `*`:
+/// ```js
+/// import(`./locales/${lang}.json`);
+/// // =>
+/// ((m, p) => m[p] ? m[p]() : Promise.reject(new Error("Unknown variable dynamic import: " + p)))({ "./locales/en.json": () => import("./locales/en.json") }, `./locales/${lang}.json`);
+/// ```
+pub struct ImportMetaGlobExpander<'a> {
+  root: &'a str,
+  /// absolute path of the module that contains `import.meta.glob`
+  importer: &'a str,
+  unresolved_mark: Mark,
+  top_level_mark: Mark,
+  glob_index: usize,
+  eager_imports: Vec<ModuleItem>,
+  /// the base directories of the glob patterns, they should be watched so adding or removing a matched file triggers an update
+  pub watch_dirs: Vec<String>,
+  pub errors: Vec<String>,
+}
+
+struct GlobOptions {
+  eager: bool,
+  import: Option<String>,
+  query: Option<String>,
+}
+
+impl<'a> ImportMetaGlobExpander<'a> {
+  pub fn new(
+    root: &'a str,
+    importer: &'a str,
+    unresolved_mark: Mark,
+    top_level_mark: Mark,
+  ) -> Self {
+    Self {
+      root,
+      importer,
+      unresolved_mark,
+      top_level_mark,
+      glob_index: 0,
+      eager_imports: vec![],
+      watch_dirs: vec![],
+      errors: vec![],
+    }
+  }
+
+  fn importer_dir(&self) -> &Path {
+    Path::new(self.importer)
+      .parent()
+      .unwrap_or(Path::new(self.root))
+  }
+
+  fn to_absolute_pattern(&self, pattern: &str) -> String {
+    let path = if let Some(pattern) = pattern.strip_prefix('/') {
+      Path::new(self.root).join(pattern)
+    } else {
+      self.importer_dir().join(pattern)
+    };
+
+    normalize_path(&path).to_string_lossy().to_string()
+  }
+
+  /// Returns the `(key, source)` of the files matching the patterns, `key` is the pattern style path of the file and `source` is the relative path used to import it
+  fn match_files(&mut self, patterns: &[String]) -> Result<Vec<(String, String)>, String> {
+    let mut includes = vec![];
+    let mut excludes = vec![];
+
+    for pattern in patterns {
+      if let Some(pattern) = pattern.strip_prefix('!') {
+        let pattern = self.to_absolute_pattern(pattern);
+        excludes.push(Pattern::new(&pattern).map_err(|e| format!("{:?}", e))?);
+      } else if pattern.starts_with("./") || pattern.starts_with("../") || pattern.starts_with('/')
+      {
+        includes.push((pattern.starts_with('/'), self.to_absolute_pattern(pattern)));
+      } else {
+        return Err(format!(
+          "pattern `{}` must start with `./`, `../`, `/` or `!`",
+          pattern
+        ));
+      }
+    }
+
+    let mut files = vec![];
+
+    for (from_root, pattern) in includes {
+      self.watch_dirs.push(glob_base_dir(&pattern));
+
+      for path in glob(&pattern).map_err(|e| format!("{:?}", e))?.flatten() {
+        let path_str = path.to_string_lossy().to_string();
+
+        if !path.is_file()
+          || path_str == self.importer
+          || excludes.iter().any(|p| p.matches_path(&path))
+        {
+          continue;
+        }
+
+        let source = relative_source(self.importer_dir(), &path);
+        let key = if from_root {
+          format!("/{}", farmfe_utils::relative(self.root, &path_str))
+        } else {
+          source.clone()
+        };
+
+        if !files.iter().any(|(k, _)| k == &key) {
+          files.push((key, source));
+        }
+      }
+    }
+
+    Ok(files)
+  }
+
+  fn expand(&mut self, call_expr: &CallExpr) -> Result<Expr, String> {
+    let patterns = match call_expr.args.get(0).map(|arg| &*arg.expr) {
+      Some(Expr::Lit(Lit::Str(str))) => vec![str.value.to_string()],
+      Some(Expr::Array(ArrayLit { elems, .. })) => elems
+        .iter()
+        .map(|elem| match elem.as_ref().map(|e| &*e.expr) {
+          Some(Expr::Lit(Lit::Str(str))) => Ok(str.value.to_string()),
+          _ => Err("patterns must be string literals".to_string()),
+        })
+        .collect::<Result<Vec<_>, _>>()?,
+      _ => {
+        return Err(
+          "the first argument must be a string literal or an array of string literals".to_string(),
+        )
+      }
+    };
+    let options = parse_options(call_expr.args.get(1).map(|arg| &*arg.expr))?;
+
+    let files = self.match_files(&patterns)?;
+    let glob_index = self.glob_index;
+    self.glob_index += 1;
+
+    let props = files
+      .into_iter()
+      .enumerate()
+      .map(|(i, (key, source))| {
+        let source = match &options.query {
+          Some(query) => format!("{}{}", source, query),
+          None => source,
+        };
+
+        let value = if options.eager {
+          let local = Ident::new(
+            format!("__farm_glob_{}_{}", glob_index, i).into(),
+            DUMMY_SP.with_ctxt(SyntaxContext::empty().apply_mark(self.top_level_mark)),
+          );
+          let specifier = match &options.import {
+            Some(imported) => ImportSpecifier::Named(ImportNamedSpecifier {
+              span: DUMMY_SP,
+              local: local.clone(),
+              imported: Some(ModuleExportName::Ident(Ident::new(
+                imported.as_str().into(),
+                DUMMY_SP,
+              ))),
+              is_type_only: false,
+            }),
+            None => ImportSpecifier::Namespace(ImportStarAsSpecifier {
+              span: DUMMY_SP,
+              local: local.clone(),
+            }),
+          };
+
+          self
+            .eager_imports
+            .push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+              span: DUMMY_SP,
+              specifiers: vec![specifier],
+              src: Box::new(str_lit(&source)),
+              type_only: false,
+              asserts: None,
+            })));
+
+          Expr::Ident(local)
+        } else {
+          lazy_import(&source, options.import.as_deref())
+        };
+
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+          key: PropName::Str(str_lit(&key)),
+          value: Box::new(value),
+        })))
+      })
+      .collect();
+
+    Ok(Expr::Object(ObjectLit {
+      span: call_expr.span,
+      props,
+    }))
+  }
+
+  /// Return [None] if the specifier of the dynamic import is not a template literal or a string concatenation
+  /// that starts with `./` or `../`, these imports are kept as is.
+  fn expand_dynamic_import(&mut self, call_expr: &CallExpr) -> Option<Expr> {
+    let specifier = match call_expr.args.as_slice() {
+      [ExprOrSpread { spread: None, expr }] => expr,
+      _ => return None,
+    };
+    let pattern = dynamic_import_pattern(specifier)?;
+    let file_name = pattern.rsplit('/').next().unwrap_or_default();
+
+    // the file name must be partially static, e.g. `${name}.json`, otherwise all files in the directory are matched
+    if !(pattern.starts_with("./") || pattern.starts_with("../")) || file_name == "*" {
+      return None;
+    }
+
+    let files = match self.match_files(&[pattern]) {
+      Ok(files) => files,
+      Err(e) => {
+        self
+          .errors
+          .push(format!("Invalid variable dynamic import: {}", e));
+        return None;
+      }
+    };
+
+    let map = Expr::Object(ObjectLit {
+      span: DUMMY_SP,
+      props: files
+        .into_iter()
+        .map(|(key, source)| {
+          PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Str(str_lit(&key)),
+            value: Box::new(lazy_import(&source, None)),
+          })))
+        })
+        .collect(),
+    });
+
+    Some(Expr::Call(CallExpr {
+      span: call_expr.span,
+      callee: Callee::Expr(Box::new(Expr::Paren(ParenExpr {
+        span: DUMMY_SP,
+        expr: Box::new(self.dynamic_import_lookup()),
+      }))),
+      args: vec![
+        ExprOrSpread {
+          spread: None,
+          expr: Box::new(map),
+        },
+        ExprOrSpread {
+          spread: None,
+          expr: specifier.clone(),
+        },
+      ],
+      type_args: None,
+    }))
+  }
+
+  /// `(m, p) => m[p] ? m[p]() : Promise.reject(new Error("Unknown variable dynamic import: " + p))`
+  fn dynamic_import_lookup(&self) -> Expr {
+    let m = Ident::new("m".into(), DUMMY_SP);
+    let p = Ident::new("p".into(), DUMMY_SP);
+    let global = |name: &str| {
+      Ident::new(
+        name.into(),
+        DUMMY_SP.with_ctxt(SyntaxContext::empty().apply_mark(self.unresolved_mark)),
+      )
+    };
+    let lookup = Expr::Member(MemberExpr {
+      span: DUMMY_SP,
+      obj: Box::new(Expr::Ident(m.clone())),
+      prop: MemberProp::Computed(ComputedPropName {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Ident(p.clone())),
+      }),
+    });
+    let error = Expr::New(NewExpr {
+      span: DUMMY_SP,
+      callee: Box::new(Expr::Ident(global("Error"))),
+      args: Some(vec![ExprOrSpread {
+        spread: None,
+        expr: Box::new(Expr::Bin(BinExpr {
+          span: DUMMY_SP,
+          op: BinaryOp::Add,
+          left: Box::new(Expr::Lit(Lit::Str(str_lit(
+            "Unknown variable dynamic import: ",
+          )))),
+          right: Box::new(Expr::Ident(p.clone())),
+        })),
+      }]),
+      type_args: None,
+    });
+
+    Expr::Arrow(ArrowExpr {
+      span: DUMMY_SP,
+      params: vec![Pat::Ident(m.into()), Pat::Ident(p.into())],
+      body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Cond(CondExpr {
+        span: DUMMY_SP,
+        test: Box::new(lookup.clone()),
+        cons: Box::new(Expr::Call(CallExpr {
+          span: DUMMY_SP,
+          callee: Callee::Expr(Box::new(lookup)),
+          args: vec![],
+          type_args: None,
+        })),
+        alt: Box::new(Expr::Call(CallExpr {
+          span: DUMMY_SP,
+          callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: Box::new(Expr::Ident(global("Promise"))),
+            prop: MemberProp::Ident(Ident::new("reject".into(), DUMMY_SP)),
+          }))),
+          args: vec![ExprOrSpread {
+            spread: None,
+            expr: Box::new(error),
+          }],
+          type_args: None,
+        })),
+      })))),
+      is_async: false,
+      is_generator: false,
+      type_params: None,
+      return_type: None,
+    })
+  }
+}
+
+impl<'a> VisitMut for ImportMetaGlobExpander<'a> {
+  fn visit_mut_module(&mut self, module: &mut Module) {
+    module.visit_mut_children_with(self);
+
+    // the eager imports are hoisted anyway, put them at the top of the module
+    module.body.splice(0..0, self.eager_imports.drain(..));
+  }
+
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    expr.visit_mut_children_with(self);
+
+    if let Expr::Call(call_expr) = expr {
+      if is_import_meta_glob(call_expr) {
+        match self.expand(call_expr) {
+          Ok(expanded) => *expr = expanded,
+          Err(e) => self
+            .errors
+            .push(format!("Invalid `import.meta.glob` call: {}", e)),
+        }
+      } else if is_dynamic_import(call_expr) {
+        if let Some(expanded) = self.expand_dynamic_import(call_expr) {
+          *expr = expanded;
+        }
+      }
+    }
+  }
+}
+
+fn is_import_meta_glob(call_expr: &CallExpr) -> bool {
+  matches!(
+    &call_expr.callee,
+    Callee::Expr(box Expr::Member(MemberExpr {
+      obj: box Expr::MetaProp(MetaPropExpr {
+        kind: MetaPropKind::ImportMeta,
+        ..
+      }),
+      prop: MemberProp::Ident(Ident { sym, .. }),
+      ..
+    })) if sym == "glob"
+  )
+}
+
+/// The glob pattern of a dynamic import specifier, e.g. `./locales/*.json` for `` `./locales/${lang}.json` `` or `'./locales/' + lang + '.json'`.
+/// Return [None] if the specifier is fully static or contains glob magic characters.
+fn dynamic_import_pattern(specifier: &Expr) -> Option<String> {
+  let mut parts = vec![];
+
+  match specifier {
+    Expr::Tpl(Tpl { exprs, quasis, .. }) => {
+      for (i, quasi) in quasis.iter().enumerate() {
+        parts.push(Some(quasi.cooked.as_ref()?.to_string()));
+
+        if i < exprs.len() {
+          parts.push(None);
+        }
+      }
+    }
+    Expr::Bin(BinExpr {
+      op: BinaryOp::Add, ..
+    }) => concat_parts(specifier, &mut parts),
+    _ => return None,
+  }
+
+  if parts.iter().all(|part| part.is_some()) {
+    return None;
+  }
+
+  let mut pattern = String::new();
+
+  for part in parts {
+    match part {
+      Some(part) if part.contains(['*', '?', '[', ']', '{', '}']) => return None,
+      Some(part) => pattern.push_str(&part),
+      // `${a}${b}` is matched by a single `*`
+      None if pattern.ends_with('*') => {}
+      None => pattern.push('*'),
+    }
+  }
+
+  Some(pattern)
+}
+
+/// flatten `'./a/' + b + '.js'` to `[Some("./a/"), None, Some(".js")]`, [None] for the non string literal expressions
+fn concat_parts(expr: &Expr, parts: &mut Vec<Option<String>>) {
+  match expr {
+    Expr::Bin(BinExpr {
+      op: BinaryOp::Add,
+      left,
+      right,
+      ..
+    }) => {
+      concat_parts(left, parts);
+      concat_parts(right, parts);
+    }
+    Expr::Lit(Lit::Str(str)) => parts.push(Some(str.value.to_string())),
+    _ => parts.push(None),
+  }
+}
+
+fn parse_options(options: Option<&Expr>) -> Result<GlobOptions, String> {
+  let mut result = GlobOptions {
+    eager: false,
+    import: None,
+    query: None,
+  };
+
+  let Some(options) = options else {
+    return Ok(result);
+  };
+
+  let Expr::Object(ObjectLit { props, .. }) = options else {
+    return Err("the options must be an object literal".to_string());
+  };
+
+  for prop in props {
+    let PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key, value })) = prop else {
+      return Err("the options must only contain key-value properties".to_string());
+    };
+
+    let key = match key {
+      PropName::Ident(ident) => ident.sym.to_string(),
+      PropName::Str(str) => str.value.to_string(),
+      _ => return Err("the option names must be identifiers or strings".to_string()),
+    };
+
+    match (key.as_str(), &**value) {
+      ("eager", Expr::Lit(Lit::Bool(b))) => result.eager = b.value,
+      ("import", Expr::Lit(Lit::Str(str))) => result.import = Some(str.value.to_string()),
+      ("query", Expr::Lit(Lit::Str(str))) => {
+        let query = str.value.to_string();
+        result.query = Some(if query.starts_with('?') {
+          query
+        } else {
+          format!("?{}", query)
+        });
+      }
+      ("eager" | "import" | "query", _) => {
+        return Err(format!("the value of option `{}` must be a literal", key))
+      }
+      _ => return Err(format!("unknown option `{}`", key)),
+    }
+  }
+
+  Ok(result)
+}
+
+/// `() => import(source)`, or `() => import(source).then((m) => m[name])` if a named export is imported
+fn lazy_import(source: &str, import: Option<&str>) -> Expr {
+  let mut body = Expr::Call(CallExpr {
+    span: DUMMY_SP,
+    callee: Callee::Import(Import { span: DUMMY_SP }),
+    args: vec![ExprOrSpread {
+      spread: None,
+      expr: Box::new(Expr::Lit(Lit::Str(str_lit(source)))),
+    }],
+    type_args: None,
+  });
+
+  if let Some(import) = import {
+    let m = Ident::new("m".into(), DUMMY_SP);
+    let get_export = Expr::Arrow(ArrowExpr {
+      span: DUMMY_SP,
+      params: vec![Pat::Ident(m.clone().into())],
+      body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::Ident(m)),
+        prop: MemberProp::Ident(Ident::new(import.into(), DUMMY_SP)),
+      })))),
+      is_async: false,
+      is_generator: false,
+      type_params: None,
+      return_type: None,
+    });
+
+    body = Expr::Call(CallExpr {
+      span: DUMMY_SP,
+      callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(body),
+        prop: MemberProp::Ident(Ident::new("then".into(), DUMMY_SP)),
+      }))),
+      args: vec![ExprOrSpread {
+        spread: None,
+        expr: Box::new(get_export),
+      }],
+      type_args: None,
+    });
+  }
+
+  Expr::Arrow(ArrowExpr {
+    span: DUMMY_SP,
+    params: vec![],
+    body: Box::new(BlockStmtOrExpr::Expr(Box::new(body))),
+    is_async: false,
+    is_generator: false,
+    type_params: None,
+    return_type: None,
+  })
+}
+
+pub fn str_lit(value: &str) -> Str {
+  Str {
+    span: DUMMY_SP,
+    value: value.into(),
+    raw: None,
+  }
+}
+
+/// Resolve `.` and `..` in the path without touching the file system
+pub fn normalize_path(path: &Path) -> PathBuf {
+  let mut result = PathBuf::new();
+
+  for comp in path.components() {
+    match comp {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        result.pop();
+      }
+      _ => result.push(comp),
+    }
+  }
+
+  result
+}
+
+/// The longest leading directory of the pattern that contains no glob magic characters, e.g. `/root/src/pages` for `/root/src/pages/**/*.tsx`
+fn glob_base_dir(pattern: &str) -> String {
+  let mut base = PathBuf::new();
+
+  for comp in Path::new(pattern).components() {
+    if comp
+      .as_os_str()
+      .to_string_lossy()
+      .contains(['*', '?', '[', '{'])
+    {
+      break;
+    }
+
+    base.push(comp);
+  }
+
+  // the pattern matches a single file without magic characters
+  if base.to_string_lossy() == pattern {
+    base.pop();
+  }
+
+  base.to_string_lossy().to_string()
+}
+
+pub fn relative_source(importer_dir: &Path, path: &Path) -> String {
+  let source = farmfe_utils::relative(&importer_dir.to_string_lossy(), &path.to_string_lossy());
+
+  if source.starts_with("../") {
+    source
+  } else {
+    format!("./{}", source)
+  }
+}
@@ -1,23 +1,27 @@
 #![feature(box_patterns)]
 #![feature(path_file_prefix)]
 
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
+use dead_branch_remover::DeadBranchRemover;
+use define::{get_defines, DefineReplacer};
 use deps_analyzer::DepsAnalyzer;
+use downlevel::downlevel;
 use farmfe_core::{
-  config::Config,
+  config::{config_regex::ConfigRegex, Config},
   context::CompilationContext,
   error::{CompilationError, Result},
-  module::{ModuleMetaData, ModuleSystem, ModuleType, ScriptModuleMetaData},
+  module::{CommentsMetaData, ModuleMetaData, ModuleSystem, ModuleType, ScriptModuleMetaData},
   plugin::{
     Plugin, PluginAnalyzeDepsHookParam, PluginFinalizeModuleHookParam, PluginHookContext,
     PluginLoadHookParam, PluginLoadHookResult, PluginParseHookParam, PluginProcessModuleHookParam,
+    ResolveKind,
   },
   resource::{
     resource_pot::{ResourcePot, ResourcePotType},
     Resource, ResourceOrigin, ResourceType,
   },
-  swc_common::{comments::NoopComments, Mark, GLOBALS},
+  swc_common::{comments::SingleThreadedComments, Mark, GLOBALS},
   swc_ecma_ast::{
     CallExpr, Callee, Expr, ExprStmt, Ident, MemberExpr, MemberProp, ModuleItem, Stmt,
   },
@@ -26,27 +30,52 @@ use farmfe_toolkit::{
   fs::read_file_utf8,
   script::{
     codegen_module, module_system_from_deps, module_type_from_id, parse_module,
-    swc_try_with::try_with, syntax_from_module_type,
+    swc_try_with::try_with, syntax_from_module_type, ParseScriptModuleResult,
   },
   sourcemap::swc_gen::{build_source_map, AstModule},
   swc_ecma_transforms::{
+    compat::class_fields_use_set::class_fields_use_set,
+    helpers::inject_helpers,
+    proposals::decorators::{decorators, Config as DecoratorsConfig},
     resolver,
-    typescript::{strip, strip_with_jsx},
+    typescript::{strip_with_config, strip_with_jsx, Config as TsConfig, ImportsNotUsedAsValues},
   },
-  swc_ecma_visit::VisitMutWith,
+  swc_ecma_visit::{FoldWith, VisitMutWith},
 };
+use import_meta_glob::ImportMetaGlobExpander;
+use magic_comments::collect_dynamic_import_magic_comments;
+use require_context::RequireContextExpander;
+use top_level_await::has_top_level_await;
+use tsconfig::TypescriptConfigResolver;
 
 use swc_plugins::{init_plugin_module_cache_once, transform_by_swc_plugins};
 
+mod dead_branch_remover;
+mod define;
 mod deps_analyzer;
+mod downlevel;
 mod handle_entry_resources;
+mod import_meta_glob;
+mod magic_comments;
+mod require_context;
 mod swc_plugins;
+mod top_level_await;
+mod tsconfig;
+
+pub use handle_entry_resources::get_worker_urls;
 
 const FARM_NODE_MODULE: &str = "__farmNodeModule";
 
 /// ScriptPlugin is used to support compiling js/ts/jsx/tsx/... files, support loading, parse, analyze dependencies and code generation.
-/// Note that we do not do transforms here, the transforms (e.g. strip types, jsx...) are handled in a separate plugin (farmfe_plugin_swc_transforms).
-pub struct FarmPluginScript {}
+/// The module level transforms are done in `process_module`: legacy decorators and typescript stripping, the swc plugins, define replacement
+/// and dead branch removal, the expansion of `import.meta.glob` and `require.context`, and the syntax downleveling to `script.target` at last.
+pub struct FarmPluginScript {
+  /// typescript options merged with the tsconfig.json closest to each module
+  typescript: TypescriptConfigResolver,
+  /// the modules matched by `exclude` and not matched by `include` are not downleveled, same as `presetEnv`
+  downlevel_include: Vec<ConfigRegex>,
+  downlevel_exclude: Vec<ConfigRegex>,
+}
 
 impl Plugin for FarmPluginScript {
   fn name(&self) -> &str {
@@ -87,10 +116,20 @@ impl Plugin for FarmPluginScript {
     context: &Arc<CompilationContext>,
     _hook_context: &PluginHookContext,
   ) -> Result<Option<ModuleMetaData>> {
-    if let Some(syntax) =
-      syntax_from_module_type(&param.module_type, context.config.script.parser.clone())
-    {
-      let mut swc_module = parse_module(
+    let mut parser_config = context.config.script.parser.clone();
+    let typescript = self
+      .typescript
+      .resolve(&param.resolved_path, &context.config);
+
+    if typescript.experimental_decorators == Some(true) {
+      parser_config.ts_config.decorators = true;
+    }
+
+    if let Some(syntax) = syntax_from_module_type(&param.module_type, parser_config) {
+      let ParseScriptModuleResult {
+        ast: mut swc_module,
+        comments,
+      } = parse_module(
         &param.module_id.to_string(),
         &param.content,
         syntax,
@@ -108,6 +147,11 @@ impl Plugin for FarmPluginScript {
           param.module_type.is_typescript(),
         ));
 
+        let dynamic_import_magic_comments =
+          collect_dynamic_import_magic_comments(&swc_module, &comments);
+        // detect before the module is transformed down to the target, which may rewrite the top level `for await`
+        let top_level_await = has_top_level_await(&swc_module);
+
         let meta = ScriptModuleMetaData {
           ast: swc_module,
           top_level_mark: top_level_mark.as_u32(),
@@ -116,6 +160,9 @@ impl Plugin for FarmPluginScript {
           module_system: ModuleSystem::Custom(String::from("unknown")),
           // set module_type to unknown, it will be detected in `finalize_module`
           hmr_accepted: false,
+          comments: CommentsMetaData::from(comments),
+          dynamic_import_magic_comments,
+          top_level_await,
         };
 
         Ok(Some(ModuleMetaData::Script(meta)))
@@ -131,45 +178,211 @@ impl Plugin for FarmPluginScript {
     context: &Arc<CompilationContext>,
   ) -> Result<Option<()>> {
     if param.module_type.is_typescript() {
+      let ts = self.typescript.resolve(
+        &param.module_id.resolved_path(&context.config.root),
+        &context.config,
+      );
+
       try_with(
         context.meta.script.cm.clone(),
         &context.meta.script.globals,
         || {
+          let unresolved_mark = Mark::from_u32(param.meta.as_script().unresolved_mark);
           let top_level_mark = Mark::from_u32(param.meta.as_script().top_level_mark);
-          let ast = &mut param.meta.as_script_mut().ast;
+          let use_define_for_class_fields = ts.use_define_for_class_fields.unwrap_or(true);
+          let mut ast = param.meta.as_script_mut().take_ast();
+          let comments: SingleThreadedComments = param.meta.as_script_mut().take_comments().into();
+
+          // legacy decorators must be transformed before the types are stripped, as the metadata is emitted from the type annotations
+          if ts.experimental_decorators == Some(true) {
+            ast = ast.fold_with(&mut decorators(DecoratorsConfig {
+              legacy: true,
+              emit_metadata: ts.emit_decorator_metadata == Some(true),
+              use_define_for_class_fields,
+            }));
+            ast.visit_mut_with(&mut inject_helpers(unresolved_mark));
+          }
+
+          let strip_config = TsConfig {
+            import_not_used_as_values: if ts.verbatim_module_syntax == Some(true)
+              || matches!(
+                ts.imports_not_used_as_values.as_deref(),
+                Some("preserve") | Some("error")
+              ) {
+              ImportsNotUsedAsValues::Preserve
+            } else {
+              ImportsNotUsedAsValues::Remove
+            },
+            pragma: ts.jsx_factory.clone(),
+            pragma_frag: ts.jsx_fragment_factory.clone(),
+            ..Default::default()
+          };
 
           match param.module_type {
-            farmfe_core::module::ModuleType::Js => {
-              // TODO downgrade syntax
-            }
-            farmfe_core::module::ModuleType::Jsx => {
-              // Do nothing, jsx should be handled by other plugins
+            ModuleType::Ts => {
+              ast.visit_mut_with(&mut strip_with_config(strip_config, top_level_mark));
             }
-            farmfe_core::module::ModuleType::Ts => {
-              ast.visit_mut_with(&mut strip(top_level_mark));
-            }
-            farmfe_core::module::ModuleType::Tsx => {
+            ModuleType::Tsx => {
               ast.visit_mut_with(&mut strip_with_jsx(
                 context.meta.script.cm.clone(),
-                // TODO make it configurable
-                Default::default(),
-                NoopComments, // TODO parse comments
+                strip_config,
+                &comments,
                 top_level_mark,
               ));
             }
             _ => {}
           }
+
+          if !use_define_for_class_fields {
+            ast.visit_mut_with(&mut class_fields_use_set(false));
+          }
+
+          param.meta.as_script_mut().set_ast(ast);
+          param.meta.as_script_mut().set_comments(comments.into());
         },
       )?;
     }
 
     // execute swc plugins
     if param.module_type.is_script() && !context.config.script.plugins.is_empty() {
+      let mut result = Ok(());
+
+      try_with(
+        context.meta.script.cm.clone(),
+        &context.meta.script.globals,
+        || result = transform_by_swc_plugins(param, context),
+      )?;
+
+      result?;
+    }
+
+    // replace defines and remove the statically false branches before analyzing deps,
+    // so the deps inside dead branches(e.g. `if (process.env.NODE_ENV !== 'production') { require('./dev') }`) won't be resolved and bundled
+    if param.module_type.is_script() {
+      let env_store = context.env_store.read();
+      let defines = get_defines(&context.config, &env_store);
+      let mut import_meta_env_used = false;
+
+      try_with(
+        context.meta.script.cm.clone(),
+        &context.meta.script.globals,
+        || {
+          let unresolved_mark = Mark::from_u32(param.meta.as_script().unresolved_mark);
+          let ast = &mut param.meta.as_script_mut().ast;
+
+          let mut define_replacer = DefineReplacer::new(&defines, unresolved_mark);
+          ast.visit_mut_with(&mut define_replacer);
+          ast.visit_mut_with(&mut DeadBranchRemover::new(unresolved_mark));
+          import_meta_env_used = define_replacer.import_meta_env_used;
+        },
+      )?;
+
+      // expand `import.meta.glob` and `` import(`./locales/${lang}.json`) `` to imports of the matched files, so they are analyzed as normal deps
+      let resolved_path = param.module_id.resolved_path(&context.config.root);
+      let mut glob_expander = ImportMetaGlobExpander::new(
+        &context.config.root,
+        &resolved_path,
+        Mark::from_u32(param.meta.as_script().unresolved_mark),
+        Mark::from_u32(param.meta.as_script().top_level_mark),
+      );
+
+      try_with(
+        context.meta.script.cm.clone(),
+        &context.meta.script.globals,
+        || {
+          param
+            .meta
+            .as_script_mut()
+            .ast
+            .visit_mut_with(&mut glob_expander);
+        },
+      )?;
+
+      if !glob_expander.errors.is_empty() {
+        return Err(CompilationError::TransformError {
+          resolved_path: resolved_path.clone(),
+          msg: glob_expander.errors.join("\n"),
+        });
+      }
+
+      // expand webpack's `require.context('./icons', true, /\.svg$/)` to a context module that requires the matched files
+      let mut require_context_expander = RequireContextExpander::new(
+        &resolved_path,
+        Mark::from_u32(param.meta.as_script().unresolved_mark),
+      );
+
       try_with(
         context.meta.script.cm.clone(),
         &context.meta.script.globals,
-        || transform_by_swc_plugins(param, context).unwrap(),
+        || {
+          param
+            .meta
+            .as_script_mut()
+            .ast
+            .visit_mut_with(&mut require_context_expander);
+        },
       )?;
+
+      if !require_context_expander.errors.is_empty() {
+        return Err(CompilationError::TransformError {
+          resolved_path: resolved_path.clone(),
+          msg: require_context_expander.errors.join("\n"),
+        });
+      }
+
+      // watch the directories of the glob patterns and `require.context`, so the module is rebuilt when a matched file is added or removed
+      let watch_dirs = glob_expander
+        .watch_dirs
+        .iter()
+        .chain(require_context_expander.watch_dirs.iter())
+        .collect::<Vec<_>>();
+
+      if !watch_dirs.is_empty() {
+        context.add_watch_files(resolved_path.clone(), watch_dirs)?;
+      }
+
+      // watch the env files, so the module is rebuilt when the env variables change
+      if import_meta_env_used {
+        context.add_watch_files(
+          param.module_id.resolved_path(&context.config.root),
+          env_store.files().iter().collect(),
+        )?;
+      }
+
+      // transform the syntax down to `script.target` at last if `script.downlevel` is enabled, so the syntax that the transforms above rely on are not changed,
+      // e.g. the template literals of dynamic imports. The swc helpers themselves are never transformed.
+      let relative_path = param.module_id.relative_path();
+      let excluded = !self
+        .downlevel_include
+        .iter()
+        .any(|r| r.is_match(relative_path))
+        && self
+          .downlevel_exclude
+          .iter()
+          .any(|r| r.is_match(relative_path));
+      let is_swc_helper = !context.config.runtime.swc_helpers_path.is_empty()
+        && Path::new(&resolved_path).starts_with(&context.config.runtime.swc_helpers_path);
+
+      if context.config.script.downlevel && !excluded && !is_swc_helper {
+        try_with(
+          context.meta.script.cm.clone(),
+          &context.meta.script.globals,
+          || {
+            let unresolved_mark = Mark::from_u32(param.meta.as_script().unresolved_mark);
+            let ast = param.meta.as_script_mut().take_ast();
+            let comments: SingleThreadedComments =
+              param.meta.as_script_mut().take_comments().into();
+            let ast = downlevel(
+              ast,
+              context.config.script.target,
+              unresolved_mark,
+              &comments,
+            );
+            param.meta.as_script_mut().set_ast(ast);
+            param.meta.as_script_mut().set_comments(comments.into());
+          },
+        )?;
+      }
     }
 
     Ok(Some(()))
@@ -183,18 +396,34 @@ impl Plugin for FarmPluginScript {
     let module = param.module;
 
     if module.module_type.is_script() {
-      let module_ast = &module.meta.as_script().ast;
-      // TODO deal with dynamic import, when dynamic import and static import are mixed, using static import
+      let ModuleMetaData::Script(meta) = &module.meta else {
+        return Err(CompilationError::AnalyzeDepsError {
+          resolved_path: module.id.resolved_path(&context.config.root),
+          source: Some(
+            format!(
+              "the meta of script module {:?} is not script meta",
+              module.id
+            )
+            .into(),
+          ),
+        });
+      };
+      // when a module is both statically and dynamically imported, the dynamic import is resolved from the statically loaded module, see partial bundling's module group analysis
       let mut analyzer = DepsAnalyzer::new(
-        &module.id,
-        module_ast,
-        Mark::from_u32(module.meta.as_script().unresolved_mark),
-        Mark::from_u32(module.meta.as_script().top_level_mark),
+        &meta.ast,
+        Mark::from_u32(meta.unresolved_mark),
+        Mark::from_u32(meta.top_level_mark),
       );
 
       GLOBALS.set(&context.meta.script.globals, || {
         let deps = analyzer.analyze_deps();
-        param.deps.extend(deps);
+        param.deps.extend(deps.into_iter().filter(|dep| {
+          // dynamic imports with `webpackIgnore: true` are left untouched and resolved at runtime
+          !matches!(dep.kind, ResolveKind::DynamicImport)
+            || !meta
+              .dynamic_import_magic_comments(&dep.source)
+              .map_or(false, |c| c.ignore)
+        }));
       });
 
       Ok(Some(()))
@@ -291,6 +520,7 @@ impl Plugin for FarmPluginScript {
       // handle_entry_resource_pot::handle_entry_resource_pot(resource_pot, context)?;
 
       let ast = &resource_pot.meta.as_js().ast;
+      let comments: SingleThreadedComments = resource_pot.meta.as_js().comments.clone().into();
       let mut src_map_buf = vec![];
 
       let buf = codegen_module(
@@ -299,6 +529,7 @@ impl Plugin for FarmPluginScript {
         context.meta.script.cm.clone(),
         Some(&mut src_map_buf),
         context.config.minify,
+        Some(&comments),
       )
       .map_err(|e| CompilationError::GenerateResourcesError {
         name: resource_pot.id.to_string(),
@@ -353,6 +584,12 @@ impl Plugin for FarmPluginScript {
 impl FarmPluginScript {
   pub fn new(config: &Config) -> Self {
     init_plugin_module_cache_once(config);
-    Self {}
+    let (downlevel_include, downlevel_exclude) = config.preset_env.include_exclude();
+
+    Self {
+      typescript: TypescriptConfigResolver::default(),
+      downlevel_include,
+      downlevel_exclude,
+    }
   }
 }
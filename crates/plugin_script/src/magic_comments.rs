@@ -0,0 +1,78 @@
+use farmfe_core::{
+  module::DynamicImportMagicComments,
+  swc_common::comments::{Comments, SingleThreadedComments},
+  swc_ecma_ast::{CallExpr, Expr, Lit, Module},
+};
+use farmfe_toolkit::{
+  script::is_dynamic_import,
+  swc_ecma_visit::{Visit, VisitWith},
+};
+
+/// Collect the magic comments of the dynamic imports, both the webpack style and the farm style are supported:
+/// ```js
+/// import(/* webpackChunkName: "settings" */ './settings');
+/// import(/* farmChunkName: "settings" */ './settings');
+/// import(/* webpackIgnore: true */ 'https://cdn.example.com/lib.js');
+/// import(/* farmIgnore: true */ 'https://cdn.example.com/lib.js');
+/// ```
+/// Only the comments in front of the string literal source are recognized.
+pub fn collect_dynamic_import_magic_comments(
+  ast: &Module,
+  comments: &SingleThreadedComments,
+) -> Vec<DynamicImportMagicComments> {
+  let mut collector = MagicCommentsCollector {
+    comments,
+    result: vec![],
+  };
+  ast.visit_with(&mut collector);
+
+  collector.result
+}
+
+struct MagicCommentsCollector<'a> {
+  comments: &'a SingleThreadedComments,
+  result: Vec<DynamicImportMagicComments>,
+}
+
+impl<'a> Visit for MagicCommentsCollector<'a> {
+  fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+    if is_dynamic_import(call_expr) && !call_expr.args.is_empty() {
+      if let box Expr::Lit(Lit::Str(str)) = &call_expr.args[0].expr {
+        if let Some(leading) = self.comments.get_leading(str.span.lo) {
+          let mut magic_comments = DynamicImportMagicComments {
+            source: str.value.to_string(),
+            ..Default::default()
+          };
+
+          for (key, value) in leading.iter().flat_map(|c| parse_magic_comment(&c.text)) {
+            match key.as_str() {
+              "webpackChunkName" | "farmChunkName" => magic_comments.chunk_name = Some(value),
+              "webpackIgnore" | "farmIgnore" => magic_comments.ignore = value == "true",
+              _ => {}
+            }
+          }
+
+          if magic_comments.chunk_name.is_some() || magic_comments.ignore {
+            self.result.push(magic_comments);
+          }
+        }
+      }
+    }
+
+    call_expr.visit_children_with(self);
+  }
+}
+
+/// parse `webpackChunkName: "settings", webpackIgnore: true` to `[("webpackChunkName", "settings"), ("webpackIgnore", "true")]`
+fn parse_magic_comment(text: &str) -> Vec<(String, String)> {
+  text
+    .split(',')
+    .filter_map(|pair| pair.split_once(':'))
+    .map(|(key, value)| {
+      let value = value
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'' || c == '`');
+      (key.trim().to_string(), value.to_string())
+    })
+    .collect()
+}
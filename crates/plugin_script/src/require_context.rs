@@ -0,0 +1,467 @@
+use std::path::Path;
+
+use farmfe_core::{
+  glob::{glob, Pattern},
+  regex::Regex,
+  swc_common::{Mark, SyntaxContext, DUMMY_SP},
+  swc_ecma_ast::{
+    ArrayLit, ArrowExpr, AssignExpr, AssignOp, BinExpr, BinaryOp, BlockStmt, BlockStmtOrExpr,
+    CallExpr, Callee, ComputedPropName, Decl, Expr, ExprOrSpread, ExprStmt, Ident, IfStmt, Import,
+    KeyValueProp, Lit, MemberExpr, MemberProp, NewExpr, ObjectLit, ParenExpr, Pat, PatOrExpr, Prop,
+    PropName, PropOrSpread, ReturnStmt, Stmt, ThrowStmt, UnaryExpr, UnaryOp, VarDecl, VarDeclKind,
+    VarDeclarator,
+  },
+};
+use farmfe_toolkit::swc_ecma_visit::{VisitMut, VisitMutWith};
+
+use crate::import_meta_glob::{normalize_path, relative_source, str_lit};
+
+/// Expand webpack's `require.context(directory, useSubdirectories = true, regExp = /^\.\/.*$/, mode = 'sync')` at build time, for example:
+/// ```js
+/// const icons = require.context('./icons', true, /\.svg$/);
+/// // =>
+/// const icons = ((m, d) => {
+///   var r = (k) => {
+///     if (!(k in m)) throw new Error("Cannot find module '" + k + "'");
+///     return m[k];
+///   };
+///   var c = (k) => r(k)[1]();
+///   c.keys = () => Object.keys(m);
+///   c.resolve = (k) => r(k)[0];
+///   c.id = d;
+///   return c;
+/// })({
+///   "./a.svg": [require.resolve("./icons/a.svg"), () => require("./icons/a.svg")],
+///   "./sub/b.svg": [require.resolve("./icons/sub/b.svg"), () => require("./icons/sub/b.svg")]
+/// }, "./icons");
+/// ```
+/// The keys are the paths relative to the directory, and they are tested by `regExp`. The `lazy` mode loads the matched files by `import()` instead of `require()`.
+/// The generated `require.resolve(source)` is replaced by the module id of the source when rendering, so `resolve` returns the module id like webpack.
+/// The generated requires and imports are analyzed by [crate::deps_analyzer::DepsAnalyzer] like the other dependencies.
+pub struct RequireContextExpander<'a> {
+  /// absolute path of the module that contains `require.context`
+  importer: &'a str,
+  unresolved_mark: Mark,
+  /// the directories of `require.context`, they should be watched so adding or removing a matched file triggers an update
+  pub watch_dirs: Vec<String>,
+  pub errors: Vec<String>,
+}
+
+struct RequireContextOptions {
+  directory: String,
+  use_subdirectories: bool,
+  reg_exp: Regex,
+  lazy: bool,
+}
+
+impl<'a> RequireContextExpander<'a> {
+  pub fn new(importer: &'a str, unresolved_mark: Mark) -> Self {
+    Self {
+      importer,
+      unresolved_mark,
+      watch_dirs: vec![],
+      errors: vec![],
+    }
+  }
+
+  fn importer_dir(&self) -> &Path {
+    Path::new(self.importer).parent().unwrap_or(Path::new("/"))
+  }
+
+  /// Returns the `(key, source)` of the matched files, `key` is the path relative to the directory, e.g. `./sub/b.svg`, and `source` is the relative path used to require it
+  fn match_files(
+    &mut self,
+    options: &RequireContextOptions,
+  ) -> Result<Vec<(String, String)>, String> {
+    let dir = normalize_path(&self.importer_dir().join(&options.directory));
+    let dir_str = dir.to_string_lossy().to_string();
+    let pattern = format!(
+      "{}/{}",
+      Pattern::escape(&dir_str),
+      if options.use_subdirectories {
+        "**/*"
+      } else {
+        "*"
+      }
+    );
+    self.watch_dirs.push(dir_str.clone());
+
+    let mut files = vec![];
+
+    for path in glob(&pattern).map_err(|e| format!("{:?}", e))?.flatten() {
+      if !path.is_file() || path.to_string_lossy() == self.importer {
+        continue;
+      }
+
+      let key = format!(
+        "./{}",
+        farmfe_utils::relative(&dir_str, &path.to_string_lossy())
+      );
+
+      if options.reg_exp.is_match(&key) {
+        files.push((key, relative_source(self.importer_dir(), &path)));
+      }
+    }
+
+    Ok(files)
+  }
+
+  fn expand(&mut self, call_expr: &CallExpr) -> Result<Expr, String> {
+    let options = parse_options(&call_expr.args)?;
+    let files = self.match_files(&options)?;
+    let context_dir = relative_source(
+      self.importer_dir(),
+      &normalize_path(&self.importer_dir().join(&options.directory)),
+    );
+
+    let map = Expr::Object(ObjectLit {
+      span: DUMMY_SP,
+      props: files
+        .into_iter()
+        .map(|(key, source)| {
+          PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Str(str_lit(&key)),
+            value: Box::new(Expr::Array(ArrayLit {
+              span: DUMMY_SP,
+              elems: vec![
+                Some(ExprOrSpread {
+                  spread: None,
+                  expr: Box::new(self.resolve(&source)),
+                }),
+                Some(ExprOrSpread {
+                  spread: None,
+                  expr: Box::new(arrow(vec![], self.load(&source, options.lazy))),
+                }),
+              ],
+            })),
+          })))
+        })
+        .collect(),
+    });
+
+    Ok(Expr::Call(CallExpr {
+      span: call_expr.span,
+      callee: Callee::Expr(Box::new(Expr::Paren(ParenExpr {
+        span: DUMMY_SP,
+        expr: Box::new(self.context_factory()),
+      }))),
+      args: vec![
+        ExprOrSpread {
+          spread: None,
+          expr: Box::new(map),
+        },
+        ExprOrSpread {
+          spread: None,
+          expr: Box::new(Expr::Lit(Lit::Str(str_lit(&context_dir)))),
+        },
+      ],
+      type_args: None,
+    }))
+  }
+
+  fn global(&self, name: &str) -> Ident {
+    Ident::new(
+      name.into(),
+      DUMMY_SP.with_ctxt(SyntaxContext::empty().apply_mark(self.unresolved_mark)),
+    )
+  }
+
+  /// `require(source)`, or `import(source)` for the lazy mode
+  fn load(&self, source: &str, lazy: bool) -> Expr {
+    Expr::Call(CallExpr {
+      span: DUMMY_SP,
+      callee: if lazy {
+        Callee::Import(Import { span: DUMMY_SP })
+      } else {
+        Callee::Expr(Box::new(Expr::Ident(self.global("require"))))
+      },
+      args: vec![ExprOrSpread {
+        spread: None,
+        expr: Box::new(Expr::Lit(Lit::Str(str_lit(source)))),
+      }],
+      type_args: None,
+    })
+  }
+
+  /// `require.resolve(source)`, the `resolve` property is marked by the unresolved mark so it's not confused with the `require.resolve`
+  /// written by users, see [farmfe_toolkit::script::is_require_context_resolve]
+  fn resolve(&self, source: &str) -> Expr {
+    call(
+      Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::Ident(self.global("require"))),
+        prop: MemberProp::Ident(self.global("resolve")),
+      }),
+      vec![str_expr(source)],
+    )
+  }
+
+  /// `(m, d) => { var r = (k) => { ... }; var c = (k) => r(k)[1](); c.keys = ...; c.resolve = ...; c.id = d; return c; }`, see [RequireContextExpander]
+  fn context_factory(&self) -> Expr {
+    let m = || Expr::Ident(ident("m"));
+    let d = || Expr::Ident(ident("d"));
+    let k = || Expr::Ident(ident("k"));
+    let c = || Expr::Ident(ident("c"));
+    let r = || Expr::Ident(ident("r"));
+    let lookup = Expr::Member(MemberExpr {
+      span: DUMMY_SP,
+      obj: Box::new(m()),
+      prop: MemberProp::Computed(ComputedPropName {
+        span: DUMMY_SP,
+        expr: Box::new(k()),
+      }),
+    });
+    let not_found = Expr::New(NewExpr {
+      span: DUMMY_SP,
+      callee: Box::new(Expr::Ident(self.global("Error"))),
+      args: Some(vec![ExprOrSpread {
+        spread: None,
+        expr: Box::new(add(
+          add(str_expr("Cannot find module '"), k()),
+          str_expr("'"),
+        )),
+      }]),
+      type_args: None,
+    });
+    let lookup_fn = arrow_block(
+      vec!["k"],
+      vec![
+        Stmt::If(IfStmt {
+          span: DUMMY_SP,
+          test: Box::new(Expr::Unary(UnaryExpr {
+            span: DUMMY_SP,
+            op: UnaryOp::Bang,
+            arg: Box::new(Expr::Paren(ParenExpr {
+              span: DUMMY_SP,
+              expr: Box::new(Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: BinaryOp::In,
+                left: Box::new(k()),
+                right: Box::new(m()),
+              })),
+            })),
+          })),
+          cons: Box::new(Stmt::Throw(ThrowStmt {
+            span: DUMMY_SP,
+            arg: Box::new(not_found),
+          })),
+          alt: None,
+        }),
+        Stmt::Return(ReturnStmt {
+          span: DUMMY_SP,
+          arg: Some(Box::new(lookup)),
+        }),
+      ],
+    );
+    // `r(k)[i]`
+    let entry = |i: usize| {
+      Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(call(r(), vec![k()])),
+        prop: MemberProp::Computed(ComputedPropName {
+          span: DUMMY_SP,
+          expr: Box::new(Expr::Lit(Lit::Num((i as f64).into()))),
+        }),
+      })
+    };
+    let context_fn = arrow(vec!["k"], call(entry(1), vec![]));
+    let keys = arrow(
+      vec![],
+      call(
+        member(Expr::Ident(self.global("Object")), "keys"),
+        vec![m()],
+      ),
+    );
+    let resolve = arrow(vec!["k"], entry(0));
+
+    arrow_block(
+      vec!["m", "d"],
+      vec![
+        var_decl("r", lookup_fn),
+        var_decl("c", context_fn),
+        assign(member(c(), "keys"), keys),
+        assign(member(c(), "resolve"), resolve),
+        assign(member(c(), "id"), d()),
+        Stmt::Return(ReturnStmt {
+          span: DUMMY_SP,
+          arg: Some(Box::new(c())),
+        }),
+      ],
+    )
+  }
+}
+
+impl<'a> VisitMut for RequireContextExpander<'a> {
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    expr.visit_mut_children_with(self);
+
+    if let Expr::Call(call_expr) = expr {
+      if is_require_context(self.unresolved_mark, call_expr) {
+        match self.expand(call_expr) {
+          Ok(expanded) => *expr = expanded,
+          Err(e) => self
+            .errors
+            .push(format!("Invalid `require.context` call: {}", e)),
+        }
+      }
+    }
+  }
+}
+
+/// `require.context(...)` where `require` is not declared in the module
+fn is_require_context(unresolved_mark: Mark, call_expr: &CallExpr) -> bool {
+  matches!(
+    &call_expr.callee,
+    Callee::Expr(box Expr::Member(MemberExpr {
+      obj: box Expr::Ident(Ident { span, sym: obj, .. }),
+      prop: MemberProp::Ident(Ident { sym: prop, .. }),
+      ..
+    })) if obj == "require" && prop == "context" && span.ctxt.outer() == unresolved_mark
+  )
+}
+
+fn parse_options(args: &[ExprOrSpread]) -> Result<RequireContextOptions, String> {
+  let directory = match args.get(0).map(|arg| &*arg.expr) {
+    Some(Expr::Lit(Lit::Str(str)))
+      if str.value.starts_with("./") || str.value.starts_with("../") || &*str.value == "." =>
+    {
+      str.value.to_string()
+    }
+    _ => {
+      return Err("the directory must be a string literal starting with `./` or `../`".to_string())
+    }
+  };
+  let use_subdirectories = match args.get(1).map(|arg| &*arg.expr) {
+    None => true,
+    Some(Expr::Lit(Lit::Bool(b))) => b.value,
+    Some(_) => return Err("useSubdirectories must be a boolean literal".to_string()),
+  };
+  let reg_exp = match args.get(2).map(|arg| &*arg.expr) {
+    None => Regex::new(r"^\./.*$").unwrap(),
+    Some(Expr::Lit(Lit::Regex(regex))) => {
+      // the flags that affect matching are converted to the inline flags, e.g. `/\.svg$/i` => `(?i)\.svg$`
+      let flags = regex
+        .flags
+        .chars()
+        .filter(|f| matches!(f, 'i' | 'm' | 's'))
+        .collect::<String>();
+      let exp = regex.exp.replace("\\/", "/");
+      let exp = if flags.is_empty() {
+        exp
+      } else {
+        format!("(?{}){}", flags, exp)
+      };
+
+      Regex::new(&exp).map_err(|e| format!("unsupported regExp `{}`: {}", regex.exp, e))?
+    }
+    Some(_) => return Err("regExp must be a regular expression literal".to_string()),
+  };
+  let lazy = match args.get(3).map(|arg| &*arg.expr) {
+    None => false,
+    Some(Expr::Lit(Lit::Str(str))) if &*str.value == "sync" => false,
+    Some(Expr::Lit(Lit::Str(str))) if &*str.value == "lazy" => true,
+    Some(_) => return Err("mode must be `sync` or `lazy`".to_string()),
+  };
+
+  Ok(RequireContextOptions {
+    directory,
+    use_subdirectories,
+    reg_exp,
+    lazy,
+  })
+}
+
+fn ident(name: &str) -> Ident {
+  Ident::new(name.into(), DUMMY_SP)
+}
+
+fn str_expr(value: &str) -> Expr {
+  Expr::Lit(Lit::Str(str_lit(value)))
+}
+
+fn member(obj: Expr, prop: &str) -> Expr {
+  Expr::Member(MemberExpr {
+    span: DUMMY_SP,
+    obj: Box::new(obj),
+    prop: MemberProp::Ident(ident(prop)),
+  })
+}
+
+fn call(callee: Expr, args: Vec<Expr>) -> Expr {
+  Expr::Call(CallExpr {
+    span: DUMMY_SP,
+    callee: Callee::Expr(Box::new(callee)),
+    args: args
+      .into_iter()
+      .map(|expr| ExprOrSpread {
+        spread: None,
+        expr: Box::new(expr),
+      })
+      .collect(),
+    type_args: None,
+  })
+}
+
+fn var_decl(name: &str, init: Expr) -> Stmt {
+  Stmt::Decl(Decl::Var(Box::new(VarDecl {
+    span: DUMMY_SP,
+    kind: VarDeclKind::Var,
+    declare: false,
+    decls: vec![VarDeclarator {
+      span: DUMMY_SP,
+      name: Pat::Ident(ident(name).into()),
+      init: Some(Box::new(init)),
+      definite: false,
+    }],
+  })))
+}
+
+fn add(left: Expr, right: Expr) -> Expr {
+  Expr::Bin(BinExpr {
+    span: DUMMY_SP,
+    op: BinaryOp::Add,
+    left: Box::new(left),
+    right: Box::new(right),
+  })
+}
+
+fn assign(left: Expr, right: Expr) -> Stmt {
+  Stmt::Expr(ExprStmt {
+    span: DUMMY_SP,
+    expr: Box::new(Expr::Assign(AssignExpr {
+      span: DUMMY_SP,
+      op: AssignOp::Assign,
+      left: PatOrExpr::Expr(Box::new(left)),
+      right: Box::new(right),
+    })),
+  })
+}
+
+fn arrow(params: Vec<&str>, body: Expr) -> Expr {
+  arrow_with_body(params, BlockStmtOrExpr::Expr(Box::new(body)))
+}
+
+fn arrow_block(params: Vec<&str>, stmts: Vec<Stmt>) -> Expr {
+  arrow_with_body(
+    params,
+    BlockStmtOrExpr::BlockStmt(BlockStmt {
+      span: DUMMY_SP,
+      stmts,
+    }),
+  )
+}
+
+fn arrow_with_body(params: Vec<&str>, body: BlockStmtOrExpr) -> Expr {
+  Expr::Arrow(ArrowExpr {
+    span: DUMMY_SP,
+    params: params
+      .into_iter()
+      .map(|p| Pat::Ident(ident(p).into()))
+      .collect(),
+    body: Box::new(body),
+    is_async: false,
+    is_generator: false,
+    type_params: None,
+    return_type: None,
+  })
+}
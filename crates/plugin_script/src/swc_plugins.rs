@@ -3,16 +3,15 @@ use std::{path::PathBuf, sync::Arc};
 use farmfe_core::{
   config::{ScriptConfigPlugin, ScriptConfigPluginFilters},
   context::CompilationContext,
-  error::Result,
+  error::{CompilationError, Result},
   hashbrown::HashMap,
   module::ModuleType,
   parking_lot::Mutex,
   plugin::PluginProcessModuleHookParam,
   swc_common::{self, plugin::metadata::TransformPluginMetadataContext, FileName, Mark},
-  swc_ecma_ast::{Module as SwcModule, Program, Script},
+  swc_ecma_ast::Program,
 };
 use farmfe_toolkit::anyhow::{self, Context};
-use farmfe_toolkit::swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 use once_cell::sync::Lazy;
 use swc_ecma_loader::{
   resolve::Resolve,
@@ -91,38 +90,30 @@ pub fn transform_by_swc_plugins(
     None,
   ));
   let unresolved_mark = Mark::from_u32(param.meta.as_script().unresolved_mark);
-  let mut plugin_transforms = swc_plugins(
-    Some(plugins_should_execute),
-    transform_metadata_context,
-    None,
-    context.meta.script.cm.clone(),
+  let comments: swc_common::comments::SingleThreadedComments =
+    param.meta.as_script_mut().take_comments().into();
+  let mut plugin_transforms = RustPlugins {
+    plugins: Some(plugins_should_execute),
+    metadata_context: transform_metadata_context,
+    comments: Some(comments.clone()),
+    source_map: context.meta.script.cm.clone(),
     unresolved_mark,
-  );
+  };
 
-  let mut program = Program::Module(param.meta.as_script_mut().take_ast());
-  // Fold module
-  program = program.fold_with(&mut plugin_transforms);
+  let program = Program::Module(param.meta.as_script_mut().take_ast());
+  // the errors of the plugins are returned instead of panicking, as a panic inside the rayon thread aborts the whole compilation
+  let program = plugin_transforms
+    .apply(program)
+    .map_err(|e| CompilationError::TransformError {
+      resolved_path: param.module_id.resolved_path(&context.config.root),
+      msg: format!("{:?}", e),
+    })?;
 
   param.meta.as_script_mut().set_ast(program.expect_module());
+  param.meta.as_script_mut().set_comments(comments.into());
   Ok(())
 }
 
-pub fn swc_plugins(
-  configured_plugins: Option<Vec<ScriptConfigPlugin>>,
-  metadata_context: std::sync::Arc<swc_common::plugin::metadata::TransformPluginMetadataContext>,
-  comments: Option<swc_common::comments::SingleThreadedComments>,
-  source_map: std::sync::Arc<swc_common::SourceMap>,
-  unresolved_mark: swc_common::Mark,
-) -> impl Fold {
-  RustPlugins {
-    plugins: configured_plugins,
-    metadata_context,
-    comments,
-    source_map,
-    unresolved_mark,
-  }
-}
-
 struct RustPlugins {
   plugins: Option<Vec<ScriptConfigPlugin>>,
   metadata_context: std::sync::Arc<swc_common::plugin::metadata::TransformPluginMetadataContext>,
@@ -133,7 +124,11 @@ struct RustPlugins {
 
 impl RustPlugins {
   fn apply(&mut self, n: Program) -> std::result::Result<Program, anyhow::Error> {
-    if self.plugins.is_none() || self.plugins.as_ref().unwrap().is_empty() {
+    if self
+      .plugins
+      .as_ref()
+      .map_or(true, |plugins| plugins.is_empty())
+    {
       return Ok(n);
     }
 
@@ -151,76 +146,57 @@ impl RustPlugins {
     // swc_plugin_macro will not inject proxy to the comments if comments is empty
     let should_enable_comments_proxy = self.comments.is_some();
 
-    // TODO support comments
     // Set comments once per whole plugin transform execution.
-    // swc_plugin_proxy::COMMENTS.set(
-    //   &swc_plugin_proxy::HostCommentsStorage {
-    //     inner: self.comments.clone(),
-    //   },
-    //   || {
-    let mut serialized = PluginSerializedBytes::try_serialize(
-      &swc_common::plugin::serialized::VersionedSerializable::new(n),
-    )?;
-
-    // Run plugin transformation against current program.
-    // We do not serialize / deserialize between each plugin execution but
-    // copies raw transformed bytes directly into plugin's memory space.
-    // Note: This doesn't mean plugin won't perform any se/deserialization: it
-    // still have to construct from raw bytes internally to perform actual
-    // transform.
-    if let Some(plugins) = &mut self.plugins {
-      for p in plugins.drain(..) {
-        let plugin_module_bytes = PLUGIN_MODULE_CACHE
-          .lock()
-          .get(&p.name)
-          .expect("plugin module should be cached")
-          .clone();
-        let plugin_name = plugin_module_bytes.get_module_name().to_string();
-        let runtime = swc_plugin_runner::wasix_runtime::build_wasi_runtime(None);
-
-        let mut plugin_transform_executor = swc_plugin_runner::create_plugin_transform_executor(
-          &self.source_map,
-          &self.unresolved_mark,
-          &self.metadata_context,
-          plugin_module_bytes,
-          Some(p.options.clone()),
-          runtime,
-        );
-
-        serialized = plugin_transform_executor
-          .transform(&serialized, Some(should_enable_comments_proxy))
-          .with_context(|| {
-            format!(
-              "failed to invoke `{}` as js transform plugin at {}",
-              &p.name, plugin_name
-            )
-          })?;
-      }
-    }
-
-    // Plugin transformation is done. Deserialize transformed bytes back
-    // into Program
-    serialized.deserialize().map(|v| v.into_inner())
-    //   },
-    // )
-  }
-}
-
-impl Fold for RustPlugins {
-  noop_fold_type!();
-
-  fn fold_module(&mut self, n: SwcModule) -> SwcModule {
-    self
-      .apply(Program::Module(n))
-      .expect("failed to invoke plugin")
-      .expect_module()
-  }
-
-  fn fold_script(&mut self, n: Script) -> Script {
-    self
-      .apply(Program::Script(n))
-      .expect("failed to invoke plugin")
-      .expect_script()
+    swc_plugin_proxy::COMMENTS.set(
+      &swc_plugin_proxy::HostCommentsStorage {
+        inner: self.comments.clone(),
+      },
+      || {
+        let mut serialized = PluginSerializedBytes::try_serialize(
+          &swc_common::plugin::serialized::VersionedSerializable::new(n),
+        )?;
+
+        // Run plugin transformation against current program.
+        // We do not serialize / deserialize between each plugin execution but
+        // copies raw transformed bytes directly into plugin's memory space.
+        // Note: This doesn't mean plugin won't perform any se/deserialization: it
+        // still have to construct from raw bytes internally to perform actual
+        // transform.
+        if let Some(plugins) = &mut self.plugins {
+          for p in plugins.drain(..) {
+            let plugin_module_bytes = PLUGIN_MODULE_CACHE
+              .lock()
+              .get(&p.name)
+              .with_context(|| format!("plugin module `{}` is not cached", &p.name))?
+              .clone();
+            let plugin_name = plugin_module_bytes.get_module_name().to_string();
+            let runtime = swc_plugin_runner::wasix_runtime::build_wasi_runtime(None);
+
+            let mut plugin_transform_executor = swc_plugin_runner::create_plugin_transform_executor(
+              &self.source_map,
+              &self.unresolved_mark,
+              &self.metadata_context,
+              plugin_module_bytes,
+              Some(p.options.clone()),
+              runtime,
+            );
+
+            serialized = plugin_transform_executor
+              .transform(&serialized, Some(should_enable_comments_proxy))
+              .with_context(|| {
+                format!(
+                  "failed to invoke `{}` as js transform plugin at {}",
+                  &p.name, plugin_name
+                )
+              })?;
+          }
+        }
+
+        // Plugin transformation is done. Deserialize transformed bytes back
+        // into Program
+        serialized.deserialize().map(|v| v.into_inner())
+      },
+    )
   }
 }
 
@@ -0,0 +1,38 @@
+use farmfe_core::swc_ecma_ast::{ArrowExpr, AwaitExpr, ForOfStmt, Function, Module};
+use farmfe_toolkit::swc_ecma_visit::{Visit, VisitWith};
+
+/// Return true if the module uses `await` or `for await` in the module scope, for example:
+/// ```js
+/// const wasm = await init();
+/// for await (const chunk of stream) {}
+/// ```
+/// The awaits inside functions are not top level awaits and are ignored.
+pub fn has_top_level_await(ast: &Module) -> bool {
+  let mut finder = TopLevelAwaitFinder { found: false };
+  ast.visit_with(&mut finder);
+
+  finder.found
+}
+
+struct TopLevelAwaitFinder {
+  found: bool,
+}
+
+impl Visit for TopLevelAwaitFinder {
+  fn visit_await_expr(&mut self, _: &AwaitExpr) {
+    self.found = true;
+  }
+
+  fn visit_for_of_stmt(&mut self, stmt: &ForOfStmt) {
+    if stmt.is_await {
+      self.found = true;
+      return;
+    }
+
+    stmt.visit_children_with(self);
+  }
+
+  fn visit_function(&mut self, _: &Function) {}
+
+  fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+}
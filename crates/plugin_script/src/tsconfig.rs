@@ -0,0 +1,218 @@
+use std::{
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use farmfe_core::{
+  config::{Config, ScriptTypescriptConfig},
+  hashbrown::HashMap,
+  parking_lot::Mutex,
+  serde_json::{self, Map, Value},
+};
+
+/// `extends` chains longer than this are treated as cyclic and ignored
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// Resolves the typescript options of the modules from the `tsconfig.json` closest to each module,
+/// the resolved options are cached by the directory of the module.
+#[derive(Default)]
+pub struct TypescriptConfigResolver {
+  cache: Mutex<HashMap<PathBuf, Arc<ScriptTypescriptConfig>>>,
+}
+
+impl TypescriptConfigResolver {
+  pub fn resolve(&self, resolved_path: &str, config: &Config) -> Arc<ScriptTypescriptConfig> {
+    // virtual modules do not have a directory, use the tsconfig of the root instead
+    let dir = Path::new(resolved_path)
+      .parent()
+      .filter(|dir| dir.is_absolute())
+      .unwrap_or(Path::new(&config.root))
+      .to_path_buf();
+
+    if let Some(typescript) = self.cache.lock().get(&dir) {
+      return typescript.clone();
+    }
+
+    let typescript = Arc::new(resolve_typescript_config(&dir, config));
+    self.cache.lock().insert(dir, typescript.clone());
+    typescript
+  }
+}
+
+/// Fill the options that are not specified in `config.script.typescript` with the `compilerOptions` of the nearest `tsconfig.json` of `dir`.
+/// The tsconfig that the nearest `tsconfig.json` extends is respected too.
+fn resolve_typescript_config(dir: &Path, config: &Config) -> ScriptTypescriptConfig {
+  let options = find_tsconfig(dir)
+    .map(|path| load_compiler_options(&path, 0))
+    .unwrap_or_default();
+  let get_bool = |key: &str| options.get(key).and_then(Value::as_bool);
+  let get_str = |key: &str| options.get(key).and_then(Value::as_str).map(String::from);
+  let ts = &config.script.typescript;
+
+  ScriptTypescriptConfig {
+    experimental_decorators: ts
+      .experimental_decorators
+      .or_else(|| get_bool("experimentalDecorators")),
+    emit_decorator_metadata: ts
+      .emit_decorator_metadata
+      .or_else(|| get_bool("emitDecoratorMetadata")),
+    use_define_for_class_fields: ts
+      .use_define_for_class_fields
+      .or_else(|| get_bool("useDefineForClassFields")),
+    jsx_factory: ts.jsx_factory.clone().or_else(|| get_str("jsxFactory")),
+    jsx_fragment_factory: ts
+      .jsx_fragment_factory
+      .clone()
+      .or_else(|| get_str("jsxFragmentFactory")),
+    imports_not_used_as_values: ts
+      .imports_not_used_as_values
+      .clone()
+      .or_else(|| get_str("importsNotUsedAsValues")),
+    verbatim_module_syntax: ts
+      .verbatim_module_syntax
+      .or_else(|| get_bool("verbatimModuleSyntax")),
+  }
+}
+
+fn find_tsconfig(dir: &Path) -> Option<PathBuf> {
+  dir
+    .ancestors()
+    .map(|dir| dir.join("tsconfig.json"))
+    .find(|path| path.is_file())
+}
+
+/// `compilerOptions` of the tsconfig, the options of the extended tsconfig are overridden
+fn load_compiler_options(path: &Path, depth: usize) -> Map<String, Value> {
+  if depth > MAX_EXTENDS_DEPTH {
+    return Map::new();
+  }
+
+  let Some(tsconfig) = std::fs::read_to_string(path)
+    .ok()
+    .and_then(|content| serde_json::from_str::<Value>(&strip_json_comments(&content)).ok()) else {
+    return Map::new();
+  };
+  let dir = path.parent().unwrap_or(Path::new("/"));
+
+  let extends = match tsconfig.get("extends") {
+    Some(Value::String(extends)) => vec![extends.as_str()],
+    Some(Value::Array(extends)) => extends.iter().filter_map(Value::as_str).collect(),
+    _ => vec![],
+  };
+  let mut options = Map::new();
+
+  for extends in extends {
+    if let Some(path) = resolve_extends(dir, extends) {
+      options.extend(load_compiler_options(&path, depth + 1));
+    }
+  }
+
+  if let Some(Value::Object(compiler_options)) = tsconfig.get("compilerOptions") {
+    options.extend(compiler_options.clone());
+  }
+
+  options
+}
+
+/// `./tsconfig.base.json` is relative to the tsconfig, `@tsconfig/node18/tsconfig.json` or `@tsconfig/node18` is resolved from `node_modules`
+fn resolve_extends(dir: &Path, extends: &str) -> Option<PathBuf> {
+  let candidates = |base: PathBuf| {
+    [
+      base.clone(),
+      base.with_extension("json"),
+      base.join("tsconfig.json"),
+    ]
+  };
+
+  if extends.starts_with("./") || extends.starts_with("../") || Path::new(extends).is_absolute() {
+    candidates(dir.join(extends))
+      .into_iter()
+      .find(|p| p.is_file())
+  } else {
+    dir.ancestors().find_map(|dir| {
+      candidates(dir.join("node_modules").join(extends))
+        .into_iter()
+        .find(|p| p.is_file())
+    })
+  }
+}
+
+/// tsconfig.json allows comments and trailing commas, remove them so it can be parsed as json
+fn strip_json_comments(content: &str) -> String {
+  let chars = content.chars().collect::<Vec<_>>();
+  let mut result = String::with_capacity(content.len());
+  let mut i = 0;
+
+  while i < chars.len() {
+    match chars[i] {
+      '"' => {
+        let start = i;
+        i += 1;
+
+        while i < chars.len() && chars[i] != '"' {
+          if chars[i] == '\\' {
+            i += 1;
+          }
+          i += 1;
+        }
+
+        result.extend(&chars[start..(i + 1).min(chars.len())]);
+      }
+      '/' if chars.get(i + 1) == Some(&'/') => {
+        while i < chars.len() && chars[i] != '\n' {
+          i += 1;
+        }
+        continue;
+      }
+      '/' if chars.get(i + 1) == Some(&'*') => {
+        i += 2;
+
+        while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+          i += 1;
+        }
+        i += 1;
+      }
+      ',' => {
+        let next = chars[i + 1..]
+          .iter()
+          .position(|c| !c.is_whitespace())
+          .map(|p| chars[i + 1 + p]);
+
+        if !matches!(next, Some('}') | Some(']')) {
+          result.push(',');
+        }
+      }
+      c => result.push(c),
+    }
+
+    i += 1;
+  }
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::strip_json_comments;
+
+  #[test]
+  fn strip_comments() {
+    let content = r#"{
+  // comment
+  "compilerOptions": {
+    /* block
+       comment */
+    "paths": { "@/*": ["./src/*"] },
+    "experimentalDecorators": true,
+  },
+  "include": ["src", "a,//b"],
+}"#;
+
+    let value: farmfe_core::serde_json::Value =
+      farmfe_core::serde_json::from_str(&strip_json_comments(content)).unwrap();
+
+    assert_eq!(value["compilerOptions"]["experimentalDecorators"], true);
+    assert_eq!(value["compilerOptions"]["paths"]["@/*"][0], "./src/*");
+    assert_eq!(value["include"][1], "a,//b");
+  }
+}
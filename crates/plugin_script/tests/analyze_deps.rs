@@ -1,3 +1,4 @@
+use farmfe_core::plugin::ResolveKind;
 use farmfe_testing_helpers::fixture;
 
 use crate::common::build_module_deps;
@@ -16,3 +17,93 @@ pub fn import_equal() {
     }
   );
 }
+
+#[test]
+pub fn dead_branch() {
+  fixture!(
+    "tests/fixtures/analyze_deps/dead_branch.js",
+    |path, base| {
+      let (_, deps) = build_module_deps(path, base);
+
+      assert_eq!(deps.len(), 2);
+      assert_eq!(deps[0].source, "./dev".to_string());
+      assert_eq!(deps[1].source, "./local".to_string());
+    }
+  );
+}
+
+#[test]
+pub fn import_meta_url() {
+  fixture!(
+    "tests/fixtures/analyze_deps/import_meta_url.js",
+    |path, base| {
+      let (_, deps) = build_module_deps(path, base);
+
+      assert_eq!(deps.len(), 1);
+      assert_eq!(deps[0].source, "./logo.png".to_string());
+      assert_eq!(deps[0].kind, ResolveKind::ImportMetaUrl);
+    }
+  );
+}
+
+#[test]
+pub fn worker() {
+  fixture!("tests/fixtures/analyze_deps/worker.js", |path, base| {
+    let (_, deps) = build_module_deps(path, base);
+
+    assert_eq!(deps.len(), 2);
+    assert_eq!(deps[0].source, "./worker.js".to_string());
+    assert!(deps[0].kind.is_worker());
+    assert_eq!(deps[1].source, "./shared.js".to_string());
+    assert!(deps[1].kind.is_worker());
+  });
+}
+
+#[test]
+pub fn magic_comments() {
+  fixture!(
+    "tests/fixtures/analyze_deps/magic_comments.js",
+    |path, base| {
+      let (module, deps) = build_module_deps(path, base);
+
+      assert_eq!(deps.len(), 2);
+      assert_eq!(deps[0].source, "./settings".to_string());
+      assert_eq!(deps[0].kind, ResolveKind::DynamicImport);
+      assert_eq!(deps[1].source, "./profile".to_string());
+
+      let meta = module.meta.as_script();
+      assert_eq!(
+        meta
+          .dynamic_import_magic_comments("./settings")
+          .unwrap()
+          .chunk_name,
+        Some("settings".to_string())
+      );
+      assert_eq!(
+        meta
+          .dynamic_import_magic_comments("./profile")
+          .unwrap()
+          .chunk_name,
+        Some("profile".to_string())
+      );
+      assert!(
+        meta
+          .dynamic_import_magic_comments("https://cdn.example.com/lib.js")
+          .unwrap()
+          .ignore
+      );
+    }
+  );
+}
+
+#[test]
+pub fn import_equal_entity_name() {
+  fixture!(
+    "tests/fixtures/analyze_deps/import_equal_entity_name.ts",
+    |path, base| {
+      let (_, deps) = build_module_deps(path, base);
+
+      assert!(deps.is_empty());
+    }
+  );
+}
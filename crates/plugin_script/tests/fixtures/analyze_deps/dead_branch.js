@@ -0,0 +1,17 @@
+if (process.env.NODE_ENV === 'production') {
+  require('./prod');
+} else {
+  require('./dev');
+}
+
+if ('production' !== 'production') {
+  import('./dynamic');
+}
+
+process.env.NODE_ENV === 'production' && require('./prod-only');
+const mod = process.env.NODE_ENV !== 'production' ? null : require('./prod-ternary');
+
+function local() {
+  const process = { env: { NODE_ENV: 'production' } };
+  process.env.NODE_ENV === 'production' && require('./local');
+}
@@ -0,0 +1,10 @@
+namespace Shapes {
+  export namespace Polygons {
+    export const square = 'square';
+  }
+}
+
+import Polygons = Shapes.Polygons;
+export import Square = Polygons.square;
+
+console.log(Polygons.square, Square);
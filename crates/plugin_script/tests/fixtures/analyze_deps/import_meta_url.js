@@ -0,0 +1,6 @@
+const logo = new URL('./logo.png', import.meta.url);
+const remote = new URL('https://farmfe.org/logo.png', import.meta.url);
+
+function local(URL) {
+  return new URL('./local.png', import.meta.url);
+}
@@ -0,0 +1,4 @@
+import(/* webpackChunkName: "settings" */ './settings');
+import(/* farmChunkName: 'profile', farmIgnore: false */ './profile');
+import(/* webpackIgnore: true */ 'https://cdn.example.com/lib.js');
+import(/* farmIgnore: true */ './ignored');
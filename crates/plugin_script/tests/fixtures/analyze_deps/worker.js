@@ -0,0 +1,3 @@
+const worker = new Worker(new URL('./worker.js', import.meta.url), { type: 'module' });
+const shared = new SharedWorker(new URL('./shared.js', import.meta.url));
+const url = new Worker('./classic.js');
@@ -0,0 +1,11 @@
+export function loadLocale(lang: string) {
+  return import(`./locales/${lang}.json`);
+}
+
+export function loadPage(name: string) {
+  return import('./pages/' + name + '.ts');
+}
+
+export function loadAny(name: string) {
+  return import(name);
+}
@@ -0,0 +1,7 @@
+const pages = import.meta.glob('./pages/*.ts');
+const titles = import.meta.glob(['./pages/*.ts', '!./pages/b.ts'], {
+  eager: true,
+  import: 'title',
+});
+
+console.log(pages, titles);
@@ -0,0 +1,5 @@
+const icons = require.context('./icons', true, /\.svg$/);
+const flatIcons = require.context('./icons', false, /\.SVG$/i);
+const lazyIcons = require.context('./icons/sub', false, /\.svg$/, 'lazy');
+
+console.log(icons.keys(), flatIcons, lazyIcons);
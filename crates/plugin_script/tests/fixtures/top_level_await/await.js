@@ -0,0 +1,5 @@
+import init from './wasm';
+
+const wasm = await init();
+
+export default wasm;
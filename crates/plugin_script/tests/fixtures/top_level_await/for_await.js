@@ -0,0 +1,11 @@
+const chunks = [];
+
+for await (const chunk of stream()) {
+  chunks.push(chunk);
+}
+
+async function* stream() {
+  yield 'chunk';
+}
+
+export { chunks };
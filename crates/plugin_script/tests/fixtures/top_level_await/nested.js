@@ -0,0 +1,18 @@
+export async function load() {
+  const wasm = await import('./wasm');
+  return wasm;
+}
+
+export const loadArrow = async () => {
+  for await (const chunk of []) {
+    console.log(chunk);
+  }
+};
+
+class Loader {
+  async load() {
+    return await load();
+  }
+}
+
+export default Loader;
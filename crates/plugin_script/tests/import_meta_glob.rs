@@ -0,0 +1,26 @@
+use farmfe_core::plugin::ResolveKind;
+use farmfe_testing_helpers::fixture;
+
+use crate::common::build_module_deps;
+
+mod common;
+
+#[test]
+pub fn import_meta_glob() {
+  fixture!("tests/fixtures/import_meta_glob/index.ts", |path, base| {
+    let (_, deps) = build_module_deps(path, base);
+    let deps = deps
+      .into_iter()
+      .map(|dep| (dep.source, dep.kind))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      deps,
+      vec![
+        ("./pages/a.ts".to_string(), ResolveKind::Import),
+        ("./pages/a.ts".to_string(), ResolveKind::DynamicImport),
+        ("./pages/b.ts".to_string(), ResolveKind::DynamicImport),
+      ]
+    );
+  });
+}
@@ -130,6 +130,7 @@ fn load_parse_and_analyze_deps() {
           shebang: None,
           span: DUMMY_SP,
         },
+        comments: module.meta.as_script().comments.clone(),
       });
 
       let resources = plugin_script
@@ -0,0 +1,27 @@
+use farmfe_core::plugin::ResolveKind;
+use farmfe_testing_helpers::fixture;
+
+use crate::common::build_module_deps;
+
+mod common;
+
+#[test]
+pub fn require_context() {
+  fixture!("tests/fixtures/require_context/index.js", |path, base| {
+    let (_, deps) = build_module_deps(path, base);
+    let deps = deps
+      .into_iter()
+      .map(|dep| (dep.source, dep.kind))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      deps,
+      vec![
+        ("./icons/a.svg".to_string(), ResolveKind::Require),
+        ("./icons/sub/b.svg".to_string(), ResolveKind::Require),
+        ("./icons/a.svg".to_string(), ResolveKind::Require),
+        ("./icons/sub/b.svg".to_string(), ResolveKind::DynamicImport),
+      ]
+    );
+  });
+}
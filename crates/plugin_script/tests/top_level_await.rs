@@ -0,0 +1,17 @@
+use common::build_module;
+use farmfe_testing_helpers::fixture;
+
+mod common;
+
+#[test]
+fn top_level_await() {
+  fixture!("tests/fixtures/top_level_await/*.js", |path, base| {
+    let module = build_module(path.clone(), base);
+
+    // the awaits inside functions are not top level awaits
+    assert_eq!(
+      module.meta.as_script().top_level_await,
+      !path.ends_with("nested.js")
+    );
+  });
+}
@@ -0,0 +1,446 @@
+use farmfe_core::{
+  hashbrown::{HashMap, HashSet},
+  swc_common::Mark,
+  swc_ecma_ast::{
+    AssignExpr, AssignOp, CallExpr, Callee, Decl, Expr, ExprOrSpread, ExprStmt, Id, Ident, Lit,
+    MemberExpr, MemberProp, Module as SwcModule, ModuleItem, ObjectLit, ObjectPat, ObjectPatProp,
+    Pat, PatOrExpr, Prop, PropName, PropOrSpread, Stmt, UnaryOp, VarDeclarator,
+  },
+};
+use farmfe_toolkit::{
+  script::is_commonjs_require,
+  swc_ecma_visit::{Visit, VisitWith},
+};
+
+use crate::module::UsedExports;
+
+/// Analyze which exports of the required modules are used by a commonjs module, the recognized patterns are:
+/// ```js
+/// require('./a').foo;
+/// const { foo, bar: baz } = require('./a');
+/// const a = require('./a'); a.foo; // `a` is only used as `a.xxx`
+/// ```
+/// All exports of the required module are marked as used for any other usage, like `require('./a')` or `fn(a)`.
+/// The key of the returned map is the source of the require call.
+pub fn analyze_require_usages(
+  ast: &SwcModule,
+  unresolved_mark: Mark,
+  top_level_mark: Mark,
+) -> HashMap<String, UsedExports> {
+  let mut collector = RequireUsagesCollector {
+    unresolved_mark,
+    top_level_mark,
+    bindings: HashMap::new(),
+    usages: HashMap::new(),
+  };
+
+  // collect top level `const a = require('./a')`
+  for item in &ast.body {
+    if let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) = item {
+      for decl in &var_decl.decls {
+        if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
+          if let Some(source) = collector.require_source(init) {
+            collector.bindings.insert(ident.id.to_id(), source);
+          }
+        }
+      }
+    }
+  }
+
+  ast.visit_with(&mut collector);
+
+  collector.usages
+}
+
+struct RequireUsagesCollector {
+  unresolved_mark: Mark,
+  top_level_mark: Mark,
+  /// top level variables that are initialized by a require call, `const a = require('./a')`
+  bindings: HashMap<Id, String>,
+  usages: HashMap<String, UsedExports>,
+}
+
+impl RequireUsagesCollector {
+  fn require_source(&self, expr: &Expr) -> Option<String> {
+    if let Expr::Call(call_expr) = expr {
+      if is_commonjs_require(self.unresolved_mark, self.top_level_mark, call_expr) {
+        return require_call_source(call_expr);
+      }
+    }
+
+    None
+  }
+
+  /// mark `name` of the required module as used, all exports are used if `name` is None
+  fn add_usage(&mut self, source: &str, name: Option<String>) {
+    let used_exports = self
+      .usages
+      .entry(source.to_string())
+      .or_insert_with(|| UsedExports::Partial(vec![]));
+
+    match name {
+      Some(name) => used_exports.add_used_export(&name),
+      None => *used_exports = UsedExports::All,
+    }
+  }
+}
+
+impl Visit for RequireUsagesCollector {
+  fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+    if let Some(source) = decl
+      .init
+      .as_ref()
+      .and_then(|init| self.require_source(init))
+    {
+      match &decl.name {
+        Pat::Ident(ident) if self.bindings.get(&ident.id.to_id()) == Some(&source) => {
+          // the usages of the binding are collected when visiting the member exprs
+          self
+            .usages
+            .entry(source)
+            .or_insert_with(|| UsedExports::Partial(vec![]));
+          return;
+        }
+        Pat::Object(obj_pat) => {
+          if let Some(keys) = object_pat_keys(obj_pat) {
+            self
+              .usages
+              .entry(source.clone())
+              .or_insert_with(|| UsedExports::Partial(vec![]));
+
+            for key in keys {
+              self.add_usage(&source, Some(key));
+            }
+            // visit the default values of the pattern
+            obj_pat.visit_with(self);
+            return;
+          }
+        }
+        _ => {}
+      }
+    }
+
+    decl.visit_children_with(self);
+  }
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr) {
+    let source = match &*member_expr.obj {
+      Expr::Ident(ident) => self.bindings.get(&ident.to_id()).cloned(),
+      obj => self.require_source(obj),
+    };
+
+    if let Some(source) = source {
+      self.add_usage(&source, member_prop_name(&member_expr.prop));
+      member_expr.prop.visit_with(self);
+      return;
+    }
+
+    member_expr.visit_children_with(self);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+    if is_commonjs_require(self.unresolved_mark, self.top_level_mark, call_expr) {
+      if let Some(source) = require_call_source(call_expr) {
+        self.add_usage(&source, None);
+      }
+    }
+
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_ident(&mut self, ident: &Ident) {
+    if let Some(source) = self.bindings.get(&ident.to_id()).cloned() {
+      self.add_usage(&source, None);
+    }
+  }
+}
+
+/// Remove the unused exports of a commonjs module, the recognized patterns are:
+/// ```js
+/// exports.foo = function () {};
+/// module.exports.bar = 1;
+/// exports.a = exports.b = void 0;
+/// module.exports = { foo, bar: 1 };
+/// ```
+/// Only the exports whose values are side effects free are removed. Nothing is removed if `exports` or `module` is used in any other way,
+/// like `exports[key]` or `fn(module)`, as the used exports can not be known statically.
+pub fn remove_unused_exports(ast: &mut SwcModule, used_exports: &[String], unresolved_mark: Mark) {
+  let is_used = |name: &String| name == "__esModule" || used_exports.contains(name);
+  let mut stmts_to_remove = HashSet::new();
+  let mut props_to_remove = HashMap::new();
+
+  for (index, item) in ast.body.iter().enumerate() {
+    let mut finder = ExportsReferenceFinder {
+      unresolved_mark,
+      found: false,
+    };
+
+    match classify_stmt(item, unresolved_mark) {
+      CjsExportStmt::Assign(names, value) => {
+        value.visit_with(&mut finder);
+
+        if !names.iter().any(is_used) && is_pure_value(value, unresolved_mark) {
+          stmts_to_remove.insert(index);
+        }
+      }
+      CjsExportStmt::ModuleExports(obj) => {
+        obj.visit_with(&mut finder);
+
+        let unused_props = obj
+          .props
+          .iter()
+          .enumerate()
+          .filter(|(_, prop)| {
+            prop_key(prop).map_or(false, |key| !is_used(&key))
+              && is_pure_prop(prop, unresolved_mark)
+          })
+          .map(|(i, _)| i)
+          .collect::<HashSet<_>>();
+        props_to_remove.insert(index, unused_props);
+      }
+      CjsExportStmt::DefineEsModule => {}
+      CjsExportStmt::Other => item.visit_with(&mut finder),
+    }
+
+    // exports is accessed dynamically, bail out
+    if finder.found {
+      return;
+    }
+  }
+
+  for (index, unused_props) in props_to_remove {
+    if let ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+      expr: box Expr::Assign(AssignExpr {
+        right: box Expr::Object(obj),
+        ..
+      }),
+      ..
+    })) = &mut ast.body[index]
+    {
+      let mut i = 0;
+      obj.props.retain(|_| {
+        i += 1;
+        !unused_props.contains(&(i - 1))
+      });
+    }
+  }
+
+  let mut index = 0;
+  ast.body.retain(|_| {
+    index += 1;
+    !stmts_to_remove.contains(&(index - 1))
+  });
+}
+
+enum CjsExportStmt<'a> {
+  /// `exports.a = exports.b = value`
+  Assign(Vec<String>, &'a Expr),
+  /// `module.exports = { ... }`
+  ModuleExports(&'a ObjectLit),
+  /// `Object.defineProperty(exports, '__esModule', { value: true })`
+  DefineEsModule,
+  Other,
+}
+
+fn classify_stmt(item: &ModuleItem, unresolved_mark: Mark) -> CjsExportStmt {
+  let expr = if let ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) = item {
+    &**expr
+  } else {
+    return CjsExportStmt::Other;
+  };
+
+  match expr {
+    Expr::Assign(AssignExpr {
+      op: AssignOp::Assign,
+      left,
+      right: box Expr::Object(obj),
+      ..
+    }) if assign_target(left).map_or(false, |m| is_module_exports(m, unresolved_mark)) => {
+      CjsExportStmt::ModuleExports(obj)
+    }
+    Expr::Assign(_) => {
+      let mut names = vec![];
+      let mut current = expr;
+
+      while let Expr::Assign(AssignExpr {
+        op: AssignOp::Assign,
+        left,
+        right,
+        ..
+      }) = current
+      {
+        match assign_target(left).and_then(|m| export_name(m, unresolved_mark)) {
+          Some(name) => names.push(name),
+          None => return CjsExportStmt::Other,
+        }
+
+        current = right;
+      }
+
+      if names.is_empty() {
+        return CjsExportStmt::Other;
+      }
+
+      CjsExportStmt::Assign(names, current)
+    }
+    Expr::Call(CallExpr {
+      callee: Callee::Expr(box Expr::Member(callee)),
+      args,
+      ..
+    }) if is_define_es_module(callee, args, unresolved_mark) => CjsExportStmt::DefineEsModule,
+    _ => CjsExportStmt::Other,
+  }
+}
+
+/// Find the references of `exports` and `module` that are not recognized as exports
+struct ExportsReferenceFinder {
+  unresolved_mark: Mark,
+  found: bool,
+}
+
+impl Visit for ExportsReferenceFinder {
+  fn visit_ident(&mut self, ident: &Ident) {
+    if is_global(ident, "exports", self.unresolved_mark)
+      || is_global(ident, "module", self.unresolved_mark)
+    {
+      self.found = true;
+    }
+  }
+}
+
+fn is_global(ident: &Ident, name: &str, unresolved_mark: Mark) -> bool {
+  ident.sym == *name && ident.span.ctxt.outer() == unresolved_mark
+}
+
+fn assign_target(left: &PatOrExpr) -> Option<&MemberExpr> {
+  match left {
+    PatOrExpr::Expr(box Expr::Member(member_expr))
+    | PatOrExpr::Pat(box Pat::Expr(box Expr::Member(member_expr))) => Some(member_expr),
+    _ => None,
+  }
+}
+
+/// `module.exports`
+fn is_module_exports(member_expr: &MemberExpr, unresolved_mark: Mark) -> bool {
+  matches!(&*member_expr.obj, Expr::Ident(ident) if is_global(ident, "module", unresolved_mark))
+    && member_prop_name(&member_expr.prop).map_or(false, |name| name == "exports")
+}
+
+/// `a` of `exports.a` and `module.exports.a`
+fn export_name(member_expr: &MemberExpr, unresolved_mark: Mark) -> Option<String> {
+  let is_exports = match &*member_expr.obj {
+    Expr::Ident(ident) => is_global(ident, "exports", unresolved_mark),
+    Expr::Member(obj) => is_module_exports(obj, unresolved_mark),
+    _ => false,
+  };
+
+  if is_exports {
+    member_prop_name(&member_expr.prop)
+  } else {
+    None
+  }
+}
+
+fn is_define_es_module(callee: &MemberExpr, args: &[ExprOrSpread], unresolved_mark: Mark) -> bool {
+  let is_define_property = matches!(&*callee.obj, Expr::Ident(ident) if is_global(ident, "Object", unresolved_mark))
+    && member_prop_name(&callee.prop).map_or(false, |name| name == "defineProperty");
+  let is_exports = |arg: &ExprOrSpread| match &*arg.expr {
+    Expr::Ident(ident) => is_global(ident, "exports", unresolved_mark),
+    Expr::Member(member_expr) => is_module_exports(member_expr, unresolved_mark),
+    _ => false,
+  };
+
+  is_define_property
+    && args.len() == 3
+    && is_exports(&args[0])
+    && matches!(&*args[1].expr, Expr::Lit(Lit::Str(str)) if str.value == *"__esModule")
+    && matches!(&*args[2].expr, Expr::Object(obj) if obj.props.iter().all(|prop| is_pure_prop(prop, unresolved_mark)))
+}
+
+fn require_call_source(call_expr: &CallExpr) -> Option<String> {
+  match call_expr.args.first() {
+    Some(ExprOrSpread {
+      spread: None,
+      expr: box Expr::Lit(Lit::Str(str)),
+    }) => Some(str.value.to_string()),
+    _ => None,
+  }
+}
+
+fn member_prop_name(prop: &MemberProp) -> Option<String> {
+  match prop {
+    MemberProp::Ident(ident) => Some(ident.sym.to_string()),
+    MemberProp::Computed(computed) => match &*computed.expr {
+      Expr::Lit(Lit::Str(str)) => Some(str.value.to_string()),
+      _ => None,
+    },
+    MemberProp::PrivateName(_) => None,
+  }
+}
+
+/// `['a', 'b']` for `{ a, b: c }`, None if the pattern contains rest or computed keys
+fn object_pat_keys(obj_pat: &ObjectPat) -> Option<Vec<String>> {
+  obj_pat
+    .props
+    .iter()
+    .map(|prop| match prop {
+      ObjectPatProp::KeyValue(kv) => prop_name(&kv.key),
+      ObjectPatProp::Assign(assign) => Some(assign.key.sym.to_string()),
+      ObjectPatProp::Rest(_) => None,
+    })
+    .collect()
+}
+
+fn prop_name(key: &PropName) -> Option<String> {
+  match key {
+    PropName::Ident(ident) => Some(ident.sym.to_string()),
+    PropName::Str(str) => Some(str.value.to_string()),
+    _ => None,
+  }
+}
+
+fn prop_key(prop: &PropOrSpread) -> Option<String> {
+  match prop {
+    PropOrSpread::Prop(box Prop::Shorthand(ident)) => Some(ident.sym.to_string()),
+    PropOrSpread::Prop(box Prop::KeyValue(kv)) => prop_name(&kv.key),
+    PropOrSpread::Prop(box Prop::Method(method)) => prop_name(&method.key),
+    _ => None,
+  }
+}
+
+fn is_pure_prop(prop: &PropOrSpread, unresolved_mark: Mark) -> bool {
+  match prop {
+    PropOrSpread::Prop(box Prop::Shorthand(ident)) => is_pure_ident(ident, unresolved_mark),
+    PropOrSpread::Prop(box Prop::KeyValue(kv)) => {
+      prop_name(&kv.key).is_some() && is_pure_value(&kv.value, unresolved_mark)
+    }
+    PropOrSpread::Prop(box Prop::Method(method)) => prop_name(&method.key).is_some(),
+    _ => false,
+  }
+}
+
+/// reading an unresolved global may throw, except `undefined`
+fn is_pure_ident(ident: &Ident, unresolved_mark: Mark) -> bool {
+  ident.span.ctxt.outer() != unresolved_mark || ident.sym == *"undefined"
+}
+
+/// Whether evaluating the value has no side effects
+fn is_pure_value(expr: &Expr, unresolved_mark: Mark) -> bool {
+  match expr {
+    Expr::Lit(_) | Expr::Fn(_) | Expr::Arrow(_) => true,
+    Expr::Ident(ident) => is_pure_ident(ident, unresolved_mark),
+    Expr::Unary(unary) if unary.op == UnaryOp::Void => is_pure_value(&unary.arg, unresolved_mark),
+    Expr::Paren(paren) => is_pure_value(&paren.expr, unresolved_mark),
+    Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+    Expr::Object(obj) => obj
+      .props
+      .iter()
+      .all(|prop| is_pure_prop(prop, unresolved_mark)),
+    Expr::Array(arr) => arr.elems.iter().all(|elem| match elem {
+      Some(ExprOrSpread { spread: None, expr }) => is_pure_value(expr, unresolved_mark),
+      Some(_) => false,
+      None => true,
+    }),
+    _ => false,
+  }
+}
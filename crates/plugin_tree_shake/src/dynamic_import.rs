@@ -0,0 +1,208 @@
+use farmfe_core::{
+  hashbrown::{HashMap, HashSet},
+  swc_ecma_ast::{
+    CallExpr, Callee, Expr, ExprOrSpread, Lit, MemberExpr, MemberProp, ModuleItem, ObjectPatProp,
+    Pat, PropName, VarDeclarator,
+  },
+};
+use farmfe_toolkit::swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{module::UsedExports, namespace::analyze_namespace_usages};
+
+/// Analyze the exports used through the dynamic imports of `items`, the key of the returned map is the source of the dynamic import, for example:
+/// ```js
+/// import('./a').then(({ foo }) => foo());
+/// import('./b').then((b) => b.bar());
+/// const { baz } = await import('./c');
+/// const d = await import('./d');
+/// console.log(d.qux, (await import('./e')).quux);
+/// ```
+/// The used exports are derived from the destructuring patterns and the member accesses of the dynamic imported modules,
+/// other usages like `import('./a').then(fn)` or `const { ...rest } = await import('./a')` are treated as [UsedExports::All].
+pub fn analyze_dynamic_import_usages(items: &[&ModuleItem]) -> HashMap<String, UsedExports> {
+  let mut collector = DynamicImportUsagesCollector {
+    usages: HashMap::new(),
+    namespaces: HashMap::new(),
+  };
+  for item in items {
+    item.visit_with(&mut collector);
+  }
+
+  let DynamicImportUsagesCollector {
+    mut usages,
+    namespaces,
+  } = collector;
+
+  // the idents bound to the dynamic imported modules are analyzed like the namespace imports
+  let namespace_idents = namespaces.keys().cloned().collect::<HashSet<_>>();
+
+  for (namespace, used_exports) in analyze_namespace_usages(items, &namespace_idents) {
+    let source = &namespaces[&namespace];
+    add_used_exports(&mut usages, source, &used_exports);
+  }
+
+  usages
+}
+
+fn add_used_exports(
+  usages: &mut HashMap<String, UsedExports>,
+  source: &str,
+  used_exports: &UsedExports,
+) {
+  usages
+    .entry(source.to_string())
+    .or_insert_with(|| UsedExports::Partial(vec![]))
+    .merge(used_exports);
+}
+
+/// `import('./a')` returns `./a`
+fn dynamic_import_source(expr: &Expr) -> Option<&str> {
+  match expr {
+    Expr::Call(call) if matches!(call.callee, Callee::Import(_)) => match call.args.first() {
+      Some(ExprOrSpread {
+        spread: None,
+        expr: box Expr::Lit(Lit::Str(str)),
+      }) => Some(&str.value),
+      _ => None,
+    },
+    Expr::Paren(paren) => dynamic_import_source(&paren.expr),
+    _ => None,
+  }
+}
+
+/// `await import('./a')` returns `./a`
+fn awaited_dynamic_import_source(expr: &Expr) -> Option<&str> {
+  match expr {
+    Expr::Await(await_expr) => dynamic_import_source(&await_expr.arg),
+    Expr::Paren(paren) => awaited_dynamic_import_source(&paren.expr),
+    _ => None,
+  }
+}
+
+struct DynamicImportUsagesCollector {
+  usages: HashMap<String, UsedExports>,
+  /// the idents bound to the dynamic imported modules, e.g. `b` of `import('./b').then((b) => b.bar())`, the value is the source
+  namespaces: HashMap<String, String>,
+}
+
+impl DynamicImportUsagesCollector {
+  fn add_used_export(&mut self, source: &str, used_export: &str) {
+    add_used_exports(
+      &mut self.usages,
+      source,
+      &UsedExports::Partial(vec![used_export.to_string()]),
+    );
+  }
+
+  fn add_all(&mut self, source: &str) {
+    add_used_exports(&mut self.usages, source, &UsedExports::All);
+  }
+
+  /// `pat` is bound to the dynamic imported module
+  fn add_pat(&mut self, source: &str, pat: &Pat) {
+    match pat {
+      Pat::Ident(ident) => {
+        self
+          .namespaces
+          .insert(ident.id.to_string(), source.to_string());
+      }
+      Pat::Object(obj) => {
+        // make sure the module is recorded even if the pattern is empty
+        add_used_exports(&mut self.usages, source, &UsedExports::Partial(vec![]));
+
+        for prop in &obj.props {
+          match prop {
+            ObjectPatProp::KeyValue(kv) => match &kv.key {
+              PropName::Ident(ident) => self.add_used_export(source, &ident.sym),
+              PropName::Str(str) => self.add_used_export(source, &str.value),
+              _ => self.add_all(source),
+            },
+            ObjectPatProp::Assign(assign) => self.add_used_export(source, &assign.key.sym),
+            ObjectPatProp::Rest(_) => self.add_all(source),
+          }
+        }
+      }
+      _ => self.add_all(source),
+    }
+  }
+}
+
+impl Visit for DynamicImportUsagesCollector {
+  fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+    // import('./a').then(({ foo }) => {})
+    if let Callee::Expr(box Expr::Member(MemberExpr {
+      obj,
+      prop: MemberProp::Ident(prop),
+      ..
+    })) = &call_expr.callee
+    {
+      if let (Some(source), "then") = (dynamic_import_source(obj), &*prop.sym) {
+        let param = match call_expr.args.first() {
+          Some(ExprOrSpread { spread: None, expr }) => match &**expr {
+            Expr::Arrow(arrow) => arrow.params.first(),
+            Expr::Fn(func) => func.function.params.first().map(|param| &param.pat),
+            _ => None,
+          },
+          _ => None,
+        };
+
+        match param {
+          Some(param) => self.add_pat(source, param),
+          None => self.add_all(source),
+        }
+
+        call_expr.args.visit_with(self);
+        return;
+      }
+    }
+
+    // the dynamic imports that are not analyzable
+    if matches!(call_expr.callee, Callee::Import(_)) {
+      if let Some(ExprOrSpread {
+        spread: None,
+        expr: box Expr::Lit(Lit::Str(str)),
+      }) = call_expr.args.first()
+      {
+        self.add_all(&str.value);
+      }
+    }
+
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_var_declarator(&mut self, var_declarator: &VarDeclarator) {
+    // const { foo } = await import('./a')
+    if let Some(source) = var_declarator
+      .init
+      .as_ref()
+      .and_then(|init| awaited_dynamic_import_source(init))
+    {
+      self.add_pat(source, &var_declarator.name);
+      var_declarator.name.visit_with(self);
+      return;
+    }
+
+    var_declarator.visit_children_with(self);
+  }
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr) {
+    // (await import('./a')).foo
+    if let Some(source) = awaited_dynamic_import_source(&member_expr.obj) {
+      match &member_expr.prop {
+        MemberProp::Ident(prop) => self.add_used_export(source, &prop.sym),
+        MemberProp::Computed(computed) => match &*computed.expr {
+          Expr::Lit(Lit::Str(str)) => self.add_used_export(source, &str.value),
+          _ => {
+            self.add_all(source);
+            computed.visit_with(self);
+          }
+        },
+        MemberProp::PrivateName(_) => self.add_all(source),
+      }
+
+      return;
+    }
+
+    member_expr.visit_children_with(self);
+  }
+}
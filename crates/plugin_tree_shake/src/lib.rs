@@ -1,13 +1,28 @@
+#![feature(box_patterns)]
+
+use std::collections::VecDeque;
+
 use farmfe_core::{
   config::Config,
-  module::{module_graph::ModuleGraph, ModuleId},
-  plugin::Plugin,
+  hashbrown::{HashMap, HashSet},
+  module::{module_graph::ModuleGraph, ModuleId, ModuleSystem},
+  plugin::{Plugin, ResolveKind},
+  record::TreeShakeRetainedReason,
+  swc_common::{comments::SingleThreadedComments, Mark, GLOBALS},
+  swc_ecma_ast::{ModuleDecl, ModuleItem},
 };
 use module::{TreeShakeModule, UsedExports};
+use report::TreeShakeReport;
+use side_effects::SideEffectsAnalyzer;
 use statement_graph::{ExportInfo, ImportInfo};
 
+pub mod commonjs;
+pub mod dynamic_import;
 pub mod module;
+pub mod namespace;
 pub mod remove_useless_stmts;
+pub mod report;
+pub mod side_effects;
 pub mod statement_graph;
 
 pub struct FarmPluginTreeShake;
@@ -24,36 +39,42 @@ impl Plugin for FarmPluginTreeShake {
   }
 
   /// tree shake useless modules and code, steps:
-  /// 1. topo sort the module_graph, the cyclic modules will be marked as side_effects
-  /// 2. generate tree_shake_modules based on the topo sorted modules
-  /// 3. traverse the tree_shake_modules
-  ///   3.1 mark entry modules as side_effects
-  ///   3.2 if module is commonjs, mark all imported modules as [UsedExports::All]
-  ///   3.3 else if module is esm and the module has side effects, add imported identifiers to [UsedExports::Partial] of the imported modules
-  ///   3.4 else if module is esm and the module has no side effects, analyze the used statement based on the statement graph
+  /// 1. topo sort the module_graph, mark entry modules as side_effects
+  /// 2. generate tree_shake_modules based on the topo sorted modules, and analyze whether their statements have side effects
+  /// 3. analyze the tree_shake_modules in topo order until no more exports are used, a module is analyzed again when more of its exports are used
+  ///   3.1 if module is commonjs, add the exports used by `require('./a').xxx` to [UsedExports::Partial] of the required commonjs modules
+  ///   3.2 else if module is not esm, mark all imported modules as [UsedExports::All]
+  ///   3.3 else if module is esm, analyze the used statement based on the statement graph, the statements with side effects and the exports of
+  ///       the module that has side effects are always used
+  ///   3.4 add the exports used through the dynamic imports to the dynamic imported modules, e.g. `const { a } = await import('./a')`
+  /// 4. remove the unused esm modules and the unused statements of the used ones, remove the unused exports of the commonjs modules that have no side effects
+  /// 5. if `config.record` is enabled, the reasons why the modules are retained are recorded, see [TreeShakeReport]
   fn optimize_module_graph(
     &self,
     module_graph: &mut farmfe_core::module::module_graph::ModuleGraph,
-    _context: &std::sync::Arc<farmfe_core::context::CompilationContext>,
+    context: &std::sync::Arc<farmfe_core::context::CompilationContext>,
   ) -> farmfe_core::error::Result<Option<()>> {
-    // topo sort the module_graph, the cyclic modules will be marked as side_effects
-    let (topo_sorted_modules, cyclic_modules) = {
-      farmfe_core::farm_profile_scope!("tree shake toposort".to_string());
-      module_graph.toposort()
-    };
+    let mut report = TreeShakeReport::new(context.config.record);
 
-    // mark cyclic modules as side_effects
-    for chain in cyclic_modules {
-      for module_id in chain {
-        let mut module = module_graph.module_mut(&module_id).unwrap();
-        module.side_effects = true;
+    if report.is_enabled() {
+      for module in module_graph.modules() {
+        if module.side_effects {
+          report.add_side_effects(&module.id, None);
+        }
       }
     }
 
+    // topo sort the module_graph, the cyclic modules are analyzed until their used exports are stable
+    let (topo_sorted_modules, _) = {
+      farmfe_core::farm_profile_scope!("tree shake toposort".to_string());
+      module_graph.toposort()
+    };
+
     // mark entry modules as side_effects
     for (entry_module_id, _) in module_graph.entries.clone() {
       let mut module = module_graph.module_mut(&entry_module_id).unwrap();
       module.side_effects = true;
+      report.add_reason(&entry_module_id, TreeShakeRetainedReason::Entry);
     }
 
     let mut tree_shake_modules_ids = vec![];
@@ -74,6 +95,7 @@ impl Plugin for FarmPluginTreeShake {
             }
 
             dep_module.side_effects = true;
+            report.add_side_effects(&dep_id, Some(&module_id));
           }
         }
 
@@ -85,99 +107,147 @@ impl Plugin for FarmPluginTreeShake {
       tree_shake_modules_map.insert(tree_shake_module.module_id.clone(), tree_shake_module);
     }
 
-    let mut modules_to_remove = vec![];
+    // analyze the side effects of the statements of the esm modules, the dependencies are analyzed before their importers
+    // so the pure functions imported from them are known
+    let mut pure_exports_map = std::collections::HashMap::new();
+    // the modules that can be removed without losing side effects: they are always retained, or neither their top level statements nor their
+    // dependencies have side effects. Only the calls of the pure functions exported by them can be removed, as removing the calls may remove the modules
+    let mut side_effects_free_modules = HashSet::new();
 
-    // traverse the tree_shake_modules
-    for tree_shake_module_id in tree_shake_modules_ids {
-      let tree_shake_module = tree_shake_modules_map
-        .get_mut(&tree_shake_module_id)
-        .unwrap();
-
-      // if module is not esm, mark all imported modules as [UsedExports::All]
-      if !matches!(
-        tree_shake_module.module_system,
-        farmfe_core::module::ModuleSystem::EsModule
-      ) {
-        for (dep_id, _) in module_graph.dependencies(&tree_shake_module_id) {
-          let dep_tree_shake_module = tree_shake_modules_map.get_mut(&dep_id);
+    GLOBALS.set(&context.meta.script.globals, || {
+      for module_id in tree_shake_modules_ids.iter().rev() {
+        let tree_shake_module = tree_shake_modules_map.get_mut(module_id).unwrap();
 
-          if let Some(dep_tree_shake_module) = dep_tree_shake_module {
-            dep_tree_shake_module.used_exports = UsedExports::All;
-          }
+        if tree_shake_module.module_system != ModuleSystem::EsModule {
+          continue;
         }
-      } else {
-        // if module is esm and the module has side effects, add imported identifiers to [UsedExports::Partial] of the imported modules
-        if tree_shake_module.side_effects {
-          let imports = tree_shake_module.imports();
-          let exports = tree_shake_module.exports();
-
-          for import_info in &imports {
-            add_used_exports_by_import_info(
-              &mut tree_shake_modules_map,
-              &*module_graph,
-              &tree_shake_module_id,
-              import_info,
-            );
-          }
 
-          for export_info in &exports {
-            add_used_exports_by_export_info(
-              &mut tree_shake_modules_map,
-              &*module_graph,
-              &tree_shake_module_id,
-              export_info,
-            );
-          }
-        } else {
-          let tree_shake_module = tree_shake_modules_map
-            .get_mut(&tree_shake_module_id)
-            .unwrap();
-
-          if tree_shake_module.used_exports.is_empty() {
-            // if the module's used_exports is empty, means this module is not used and should be removed
-            modules_to_remove.push(tree_shake_module_id.clone());
-            continue;
-          }
+        let script_meta = module_graph.module(module_id).unwrap().meta.as_script();
+        let comments: SingleThreadedComments = script_meta.comments.clone().into();
+        let dep_pure_exports = |source: &str| {
+          module_graph
+            .get_dep_by_source_optional(module_id, source)
+            .and_then(|dep_id| pure_exports_map.get(&dep_id))
+        };
+        let analyzer = SideEffectsAnalyzer::new(
+          &script_meta.ast,
+          Mark::from_u32(script_meta.unresolved_mark),
+          &comments,
+          &dep_pure_exports,
+        );
+
+        for (index, item) in script_meta.ast.body.iter().enumerate() {
+          // the imports of the dependencies that have side effects are preserved even if no imported ident is used
+          let is_side_effects_import = matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))
+            if module_graph
+              .get_dep_by_source_optional(module_id, &import_decl.src.value)
+              .map_or(false, |dep_id| module_graph.module(&dep_id).unwrap().side_effects));
+
+          tree_shake_module
+            .stmt_graph
+            .stmt_mut(&index)
+            .is_self_executed = is_side_effects_import || analyzer.has_side_effects(item);
+        }
 
-          let module = module_graph
-            .module_mut(&tree_shake_module.module_id)
-            .unwrap();
-          let swc_module = &mut module.meta.as_script_mut().ast;
-
-          // remove useless statements and useless imports/exports identifiers, then all preserved import info and export info will be added to the used_exports.
-          let (used_imports, used_exports_from) =
-            remove_useless_stmts::remove_useless_stmts(tree_shake_module, swc_module);
-
-          for import_info in used_imports {
-            add_used_exports_by_import_info(
-              &mut tree_shake_modules_map,
-              &*module_graph,
-              &tree_shake_module_id,
-              &import_info,
-            );
-          }
+        let is_side_effects_free = tree_shake_module.side_effects
+          || (tree_shake_module
+            .stmt_graph
+            .stmts()
+            .iter()
+            .all(|stmt| !stmt.is_self_executed)
+            && module_graph
+              .dependencies_ids(module_id)
+              .iter()
+              .all(|dep_id| side_effects_free_modules.contains(dep_id)));
+
+        if is_side_effects_free {
+          let pure_exports = analyzer.pure_exports(&script_meta.ast, &dep_pure_exports);
+          pure_exports_map.insert(module_id.clone(), pure_exports);
+          side_effects_free_modules.insert(module_id.clone());
+        }
+      }
+    });
 
-          for export_info in used_exports_from {
-            add_used_exports_by_export_info(
-              &mut tree_shake_modules_map,
-              &*module_graph,
-              &tree_shake_module_id,
-              &export_info,
-            );
-          }
+    // analyze the used exports of the tree_shake_modules until no more exports are used. the modules are analyzed in topo order first,
+    // and a module is analyzed again when more of its exports are used or it's marked as side effects after it's analyzed, e.g. in cycles
+    let mut queue = tree_shake_modules_ids
+      .iter()
+      .cloned()
+      .collect::<VecDeque<_>>();
+    let mut queued = tree_shake_modules_ids
+      .iter()
+      .cloned()
+      .collect::<HashSet<_>>();
+
+    while let Some(tree_shake_module_id) = queue.pop_front() {
+      queued.remove(&tree_shake_module_id);
+
+      let deps = module_graph.dependencies_ids(&tree_shake_module_id);
+      let deps_states = deps
+        .iter()
+        .map(|dep| tree_shake_module_state(&tree_shake_modules_map, dep))
+        .collect::<Vec<_>>();
+
+      analyze_used_exports_of_deps(
+        &tree_shake_module_id,
+        &mut tree_shake_modules_map,
+        module_graph,
+        context,
+        &mut report,
+      );
+
+      for (dep, dep_state) in deps.into_iter().zip(deps_states) {
+        if tree_shake_module_state(&tree_shake_modules_map, &dep) != dep_state
+          && !queued.contains(&dep)
+        {
+          queued.insert(dep.clone());
+          queue.push_back(dep);
         }
       }
+    }
+
+    // remove the unused modules, statements and commonjs exports
+    let mut modules_to_remove = vec![];
 
-      // add all dynamic imported dependencies as [UsedExports::All]
-      for (dep, edge) in module_graph.dependencies(&tree_shake_module_id) {
-        if edge.is_dynamic() {
-          let tree_shake_module = tree_shake_modules_map.get_mut(&dep).unwrap();
-          tree_shake_module.side_effects = true;
-          tree_shake_module.used_exports = UsedExports::All;
+    for tree_shake_module_id in &tree_shake_modules_ids {
+      let tree_shake_module = tree_shake_modules_map
+        .get_mut(tree_shake_module_id)
+        .unwrap();
+      let module = module_graph.module_mut(tree_shake_module_id).unwrap();
+      let script_meta = module.meta.as_script_mut();
+
+      if tree_shake_module.module_system == ModuleSystem::CommonJs {
+        if let Some(used_exports) = commonjs_removable_used_exports(tree_shake_module) {
+          GLOBALS.set(&context.meta.script.globals, || {
+            commonjs::remove_unused_exports(
+              &mut script_meta.ast,
+              used_exports,
+              Mark::from_u32(script_meta.unresolved_mark),
+            );
+          });
+        }
+      } else if tree_shake_module.module_system == ModuleSystem::EsModule {
+        if !tree_shake_module.side_effects && tree_shake_module.used_exports.is_empty() {
+          // if the module's used_exports is empty, means this module is not used and should be removed
+          modules_to_remove.push(tree_shake_module_id.clone());
+        } else {
+          // remove useless statements and useless imports/exports identifiers
+          remove_useless_stmts::remove_useless_stmts(tree_shake_module, &mut script_meta.ast);
         }
       }
     }
 
+    if report.is_enabled() {
+      let retained_modules = tree_shake_modules_ids
+        .iter()
+        .filter(|module_id| !modules_to_remove.contains(module_id))
+        .cloned()
+        .collect::<Vec<_>>();
+      context
+        .record_manager
+        .set_tree_shake_records(report.into_records(&retained_modules));
+    }
+
     // remove the unused modules
     for module_id in modules_to_remove {
       module_graph.remove_module(&module_id);
@@ -187,12 +257,201 @@ impl Plugin for FarmPluginTreeShake {
   }
 }
 
+/// Add the exports used by the module to its dependencies, the module is analyzed based on its current used exports
+fn analyze_used_exports_of_deps(
+  tree_shake_module_id: &ModuleId,
+  tree_shake_modules_map: &mut std::collections::HashMap<ModuleId, TreeShakeModule>,
+  module_graph: &ModuleGraph,
+  context: &std::sync::Arc<farmfe_core::context::CompilationContext>,
+  report: &mut TreeShakeReport,
+) {
+  let tree_shake_module = tree_shake_modules_map
+    .get_mut(tree_shake_module_id)
+    .unwrap();
+  // the exports used through the dynamic imports of the esm module, the key is the source of the dynamic import
+  let mut dynamic_import_usages = HashMap::new();
+
+  if tree_shake_module.module_system == ModuleSystem::CommonJs {
+    let script_meta = module_graph
+      .module(tree_shake_module_id)
+      .unwrap()
+      .meta
+      .as_script();
+    let unresolved_mark = Mark::from_u32(script_meta.unresolved_mark);
+    let top_level_mark = Mark::from_u32(script_meta.top_level_mark);
+
+    let require_usages = GLOBALS.set(&context.meta.script.globals, || {
+      // the requires in the unused exports are not usages, the unused exports are removed from a copy of the ast
+      // as more exports of the module may be used later
+      if let Some(used_exports) = commonjs_removable_used_exports(tree_shake_module) {
+        let mut ast = script_meta.ast.clone();
+        commonjs::remove_unused_exports(&mut ast, used_exports, unresolved_mark);
+
+        commonjs::analyze_require_usages(&ast, unresolved_mark, top_level_mark)
+      } else {
+        commonjs::analyze_require_usages(&script_meta.ast, unresolved_mark, top_level_mark)
+      }
+    });
+
+    for (dep_id, edge) in module_graph.dependencies(tree_shake_module_id) {
+      if let Some(dep_tree_shake_module) = tree_shake_modules_map.get_mut(&dep_id) {
+        // esm modules without used exports are removed, so only the required commonjs modules are shaken by the require usages
+        if dep_tree_shake_module.module_system != ModuleSystem::CommonJs
+          || edge.iter().any(|item| item.kind != ResolveKind::Require)
+        {
+          dep_tree_shake_module.used_exports = UsedExports::All;
+          report.add_commonjs_bailout(&dep_id, tree_shake_module_id);
+          continue;
+        }
+
+        for item in edge.iter() {
+          match require_usages.get(&item.source) {
+            Some(used_exports @ UsedExports::Partial(_)) => {
+              dep_tree_shake_module.used_exports.merge(used_exports);
+              report.add_used_exports(&dep_id, tree_shake_module_id, used_exports);
+            }
+            _ => {
+              dep_tree_shake_module.used_exports = UsedExports::All;
+              report.add_commonjs_bailout(&dep_id, tree_shake_module_id);
+            }
+          }
+        }
+      }
+    }
+  } else if tree_shake_module.module_system != ModuleSystem::EsModule {
+    // if module is not esm, mark all imported modules as [UsedExports::All]
+    for (dep_id, _) in module_graph.dependencies(tree_shake_module_id) {
+      let dep_tree_shake_module = tree_shake_modules_map.get_mut(&dep_id);
+
+      if let Some(dep_tree_shake_module) = dep_tree_shake_module {
+        dep_tree_shake_module.used_exports = UsedExports::All;
+        report.add_commonjs_bailout(&dep_id, tree_shake_module_id);
+      }
+    }
+  } else {
+    if !tree_shake_module.side_effects && tree_shake_module.used_exports.is_empty() {
+      // the module is not used for now, it will be analyzed again if any of its exports is used later
+      return;
+    }
+
+    let swc_module = &module_graph
+      .module(tree_shake_module_id)
+      .unwrap()
+      .meta
+      .as_script()
+      .ast;
+
+    // analyze the used statements and the used imports/exports identifiers, then all used import info and export info will be added to the used_exports.
+    // for the module that has side effects, all its exports and statements with side effects are used, only the unused pure statements are not.
+    let used_stmts = remove_useless_stmts::analyze_used_stmts(tree_shake_module, swc_module);
+    let used_items = used_stmts
+      .iter()
+      .map(|used_stmt| &swc_module.body[used_stmt.id])
+      .collect::<Vec<_>>();
+    let (used_imports, used_exports_from) =
+      remove_useless_stmts::used_imports_and_exports_from(&used_stmts);
+
+    // the exports used through the namespace imports are analyzed by the member accesses of the namespaces
+    let namespaces = used_imports
+      .iter()
+      .flat_map(|import_info| &import_info.specifiers)
+      .filter_map(|sp| match sp {
+        statement_graph::ImportSpecifierInfo::Namespace(local) => Some(local.clone()),
+        _ => None,
+      })
+      .collect();
+    let namespace_usages = namespace::analyze_namespace_usages(&used_items, &namespaces);
+    dynamic_import_usages = dynamic_import::analyze_dynamic_import_usages(&used_items);
+
+    for import_info in used_imports {
+      add_used_exports_by_import_info(
+        tree_shake_modules_map,
+        module_graph,
+        tree_shake_module_id,
+        &import_info,
+        &namespace_usages,
+        report,
+      );
+    }
+
+    for export_info in used_exports_from {
+      add_used_exports_by_export_info(
+        tree_shake_modules_map,
+        module_graph,
+        tree_shake_module_id,
+        &export_info,
+        report,
+      );
+    }
+  }
+
+  // add the exports used through the dynamic imports to the dynamic imported dependencies, the dependencies of the dynamic imports
+  // that are not analyzable and the assets referenced by `new URL('./a.png', import.meta.url)` are marked as [UsedExports::All]
+  for (dep, edge) in module_graph.dependencies(tree_shake_module_id) {
+    if let Some(dep_tree_shake_module) = tree_shake_modules_map.get_mut(&dep) {
+      for item in edge.iter() {
+        let used_exports = if item.kind.is_dynamic() {
+          dynamic_import_usages.get(&item.source)
+        } else if item.kind == ResolveKind::ImportMetaUrl {
+          None
+        } else {
+          continue;
+        };
+
+        match used_exports {
+          Some(used_exports @ UsedExports::Partial(_)) if !used_exports.is_empty() => {
+            dep_tree_shake_module.used_exports.merge(used_exports);
+            report.add_used_exports(&dep, tree_shake_module_id, used_exports);
+          }
+          _ => {
+            dep_tree_shake_module.side_effects = true;
+            dep_tree_shake_module.used_exports = UsedExports::All;
+            report.add_side_effects(&dep, Some(tree_shake_module_id));
+          }
+        }
+      }
+    }
+  }
+}
+
+/// The used exports of the commonjs module if its unused exports can be removed.
+/// `default` of a commonjs module is the whole `module.exports`, so nothing can be removed if it's used
+fn commonjs_removable_used_exports(tree_shake_module: &TreeShakeModule) -> Option<&Vec<String>> {
+  match &tree_shake_module.used_exports {
+    UsedExports::Partial(used_exports)
+      if !tree_shake_module.side_effects && !used_exports.iter().any(|e| e == "default") =>
+    {
+      Some(used_exports)
+    }
+    _ => None,
+  }
+}
+
+/// The state of the tree shake module that affects the analysis of it, it's None if the module is not a tree shake module
+fn tree_shake_module_state(
+  tree_shake_modules_map: &std::collections::HashMap<ModuleId, TreeShakeModule>,
+  module_id: &ModuleId,
+) -> Option<(Option<usize>, bool)> {
+  tree_shake_modules_map
+    .get(module_id)
+    .map(|tree_shake_module| {
+      let used_exports_count = match &tree_shake_module.used_exports {
+        UsedExports::All => None,
+        UsedExports::Partial(used_exports) => Some(used_exports.len()),
+      };
+
+      (used_exports_count, tree_shake_module.side_effects)
+    })
+}
+
 // Add all imported to used_exports
 fn add_used_exports_by_import_info(
   tree_shake_modules_map: &mut std::collections::HashMap<ModuleId, TreeShakeModule>,
   module_graph: &ModuleGraph,
   tree_shake_module_id: &ModuleId,
   import_info: &ImportInfo,
+  namespace_usages: &HashMap<String, UsedExports>,
+  report: &mut TreeShakeReport,
 ) {
   let imported_module_id =
     module_graph.get_dep_by_source(tree_shake_module_id, &import_info.source);
@@ -211,38 +470,40 @@ fn add_used_exports_by_import_info(
   if import_info.specifiers.is_empty() {
     imported_tree_shake_module.used_exports = module::UsedExports::All;
     imported_tree_shake_module.side_effects = true;
+    report.add_side_effects(&imported_module_id, Some(tree_shake_module_id));
     return;
   }
 
+  // the exports used by this import
+  let mut used_exports = module::UsedExports::Partial(vec![]);
+
   for sp in &import_info.specifiers {
     match sp {
-      statement_graph::ImportSpecifierInfo::Namespace(_) => {
-        imported_tree_shake_module.used_exports = module::UsedExports::All;
-      }
+      statement_graph::ImportSpecifierInfo::Namespace(local) => match namespace_usages.get(local) {
+        Some(namespace_used_exports @ UsedExports::Partial(_)) => {
+          used_exports.merge(namespace_used_exports);
+        }
+        _ => used_exports = module::UsedExports::All,
+      },
       statement_graph::ImportSpecifierInfo::Named { local, imported } => {
         if let Some(ident) = imported {
           if *ident == "default" {
-            imported_tree_shake_module
-              .used_exports
-              .add_used_export(&module::UsedIdent::Default);
+            used_exports.add_used_export(&module::UsedIdent::Default);
           } else {
-            imported_tree_shake_module
-              .used_exports
-              .add_used_export(&module::UsedIdent::SwcIdent(strip_context(ident)));
+            used_exports.add_used_export(&module::UsedIdent::SwcIdent(strip_context(ident)));
           }
         } else {
-          imported_tree_shake_module
-            .used_exports
-            .add_used_export(&module::UsedIdent::SwcIdent(strip_context(local)));
+          used_exports.add_used_export(&module::UsedIdent::SwcIdent(strip_context(local)));
         }
       }
       statement_graph::ImportSpecifierInfo::Default(_) => {
-        imported_tree_shake_module
-          .used_exports
-          .add_used_export(&module::UsedIdent::Default);
+        used_exports.add_used_export(&module::UsedIdent::Default);
       }
     }
   }
+
+  report.add_used_exports(&imported_module_id, tree_shake_module_id, &used_exports);
+  imported_tree_shake_module.used_exports.merge(&used_exports);
 }
 
 /// All all exported to used_exports
@@ -251,6 +512,7 @@ fn add_used_exports_by_export_info(
   module_graph: &ModuleGraph,
   tree_shake_module_id: &ModuleId,
   export_info: &ExportInfo,
+  report: &mut TreeShakeReport,
 ) {
   if let Some(source) = &export_info.source {
     let exported_module_id = module_graph.get_dep_by_source(tree_shake_module_id, source);
@@ -261,45 +523,42 @@ fn add_used_exports_by_export_info(
     }
 
     let exported_tree_shake_module = tree_shake_modules_map.get_mut(&exported_module_id).unwrap();
+    // the exports used by this re-export
+    let mut used_exports = module::UsedExports::Partial(vec![]);
 
     for sp in &export_info.specifiers {
       match sp {
         statement_graph::ExportSpecifierInfo::Namespace(_) => {
-          exported_tree_shake_module.used_exports = module::UsedExports::All;
+          used_exports = module::UsedExports::All;
         }
         statement_graph::ExportSpecifierInfo::Named { local, .. } => {
           if local == &"default".to_string() {
-            exported_tree_shake_module
-              .used_exports
-              .add_used_export(&module::UsedIdent::Default);
+            used_exports.add_used_export(&module::UsedIdent::Default);
           } else {
-            exported_tree_shake_module
-              .used_exports
-              .add_used_export(&module::UsedIdent::SwcIdent(strip_context(local)));
+            used_exports.add_used_export(&module::UsedIdent::SwcIdent(strip_context(local)));
           }
         }
         statement_graph::ExportSpecifierInfo::Default => {
-          exported_tree_shake_module
-            .used_exports
-            .add_used_export(&module::UsedIdent::Default);
+          used_exports.add_used_export(&module::UsedIdent::Default);
         }
         statement_graph::ExportSpecifierInfo::All(used_idents) => {
           if let Some(used_idents) = used_idents {
             for ident in used_idents {
               if ident == "*" {
-                exported_tree_shake_module.used_exports = module::UsedExports::All;
+                used_exports = module::UsedExports::All;
               } else {
-                exported_tree_shake_module
-                  .used_exports
-                  .add_used_export(&strip_context(ident));
+                used_exports.add_used_export(&strip_context(ident));
               }
             }
           } else {
-            exported_tree_shake_module.used_exports = module::UsedExports::All;
+            used_exports = module::UsedExports::All;
           }
         }
       }
     }
+
+    report.add_used_exports(&exported_module_id, tree_shake_module_id, &used_exports);
+    exported_tree_shake_module.used_exports.merge(&used_exports);
   }
 }
 
@@ -42,7 +42,24 @@ impl UsedExports {
       UsedExports::All => {
         *self = UsedExports::All;
       }
-      UsedExports::Partial(self_used_exports) => self_used_exports.push(used_export.to_string()),
+      UsedExports::Partial(self_used_exports) => {
+        let used_export = used_export.to_string();
+
+        if !self_used_exports.contains(&used_export) {
+          self_used_exports.push(used_export);
+        }
+      }
+    }
+  }
+
+  pub fn merge(&mut self, other: &UsedExports) {
+    match other {
+      UsedExports::All => *self = UsedExports::All,
+      UsedExports::Partial(used_exports) => {
+        for used_export in used_exports {
+          self.add_used_export(used_export);
+        }
+      }
     }
   }
 
@@ -142,9 +159,11 @@ impl TreeShakeModule {
       ));
       for stmt in self.stmt_graph.stmts() {
         if stmt.is_self_executed {
+          // all declarations of a self executed statement are preserved, e.g. `export const a = sideEffect(), b = 1`
           stmt_used_idents_map
             .entry(stmt.id)
-            .or_insert(HashSet::new());
+            .or_insert(HashSet::new())
+            .extend(stmt.defined_idents.iter().cloned().map(UsedIdent::SwcIdent));
 
           let dep_stmts = self.stmt_graph.dependencies(&stmt.id);
 
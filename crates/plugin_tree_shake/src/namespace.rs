@@ -0,0 +1,79 @@
+use farmfe_core::{
+  hashbrown::{HashMap, HashSet},
+  swc_ecma_ast::{BindingIdent, Expr, Ident, ImportDecl, Lit, MemberExpr, MemberProp, ModuleItem},
+};
+use farmfe_toolkit::swc_ecma_visit::{Visit, VisitWith};
+
+use crate::module::UsedExports;
+
+/// Analyze the exports used through the namespace imports, for example:
+/// ```js
+/// import * as ns from './foo';
+/// ns.a();
+/// console.log(ns['b']);
+/// ```
+/// `a` and `b` of `./foo` are used. If the namespace escapes, like `fn(ns)`, `ns[key]` or `export { ns }`, all exports are used.
+/// `items` are the statements to analyze, usually the used statements of the module.
+/// `namespaces` are the locals of the namespace imports, the key of the returned map is the local of the namespace.
+pub fn analyze_namespace_usages(
+  items: &[&ModuleItem],
+  namespaces: &HashSet<String>,
+) -> HashMap<String, UsedExports> {
+  let mut collector = NamespaceUsagesCollector {
+    namespaces,
+    usages: namespaces
+      .iter()
+      .map(|ns| (ns.clone(), UsedExports::Partial(vec![])))
+      .collect(),
+  };
+  for item in items {
+    item.visit_with(&mut collector);
+  }
+
+  collector.usages
+}
+
+struct NamespaceUsagesCollector<'a> {
+  namespaces: &'a HashSet<String>,
+  usages: HashMap<String, UsedExports>,
+}
+
+impl<'a> Visit for NamespaceUsagesCollector<'a> {
+  // the locals of the import specifiers are not usages
+  fn visit_import_decl(&mut self, _: &ImportDecl) {}
+
+  // the declarations of the namespaces are not usages either, e.g. `m` of `const m = await import('./a')`
+  fn visit_binding_ident(&mut self, _: &BindingIdent) {}
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr) {
+    if let Expr::Ident(obj) = &*member_expr.obj {
+      let ns = obj.to_string();
+
+      if let Some(used_exports) = self.usages.get_mut(&ns) {
+        match &member_expr.prop {
+          MemberProp::Ident(prop) => used_exports.add_used_export(&prop.sym),
+          MemberProp::Computed(computed) => match &*computed.expr {
+            Expr::Lit(Lit::Str(str)) => used_exports.add_used_export(&str.value),
+            _ => {
+              *used_exports = UsedExports::All;
+              computed.visit_with(self);
+            }
+          },
+          MemberProp::PrivateName(_) => {}
+        }
+
+        return;
+      }
+    }
+
+    member_expr.visit_children_with(self);
+  }
+
+  fn visit_ident(&mut self, ident: &Ident) {
+    let ns = ident.to_string();
+
+    if self.namespaces.contains(&ns) {
+      self.usages.insert(ns, UsedExports::All);
+    }
+  }
+}
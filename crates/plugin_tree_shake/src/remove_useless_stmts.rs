@@ -8,18 +8,23 @@ use crate::{
   statement_graph::{
     analyze_imports_and_exports::analyze_imports_and_exports,
     defined_idents_collector::DefinedIdentsCollector, ExportInfo, ExportSpecifierInfo, ImportInfo,
-    ImportSpecifierInfo,
+    ImportSpecifierInfo, StatementId,
   },
 };
 
-pub fn remove_useless_stmts(
-  tree_shake_module: &mut TreeShakeModule,
-  swc_module: &mut SwcModule,
-) -> (Vec<ImportInfo>, Vec<ExportInfo>) {
-  farmfe_core::farm_profile_function!(format!(
-    "remove_useless_stmts {:?}",
-    tree_shake_module.module_id.to_string()
-  ));
+/// A used statement of the module, the import info and export info only contain the used specifiers
+pub struct UsedStmt {
+  pub id: StatementId,
+  pub import_info: Option<ImportInfo>,
+  /// for `export * from 'xxx'`, the specifier is [ExportSpecifierInfo::All] with the used idents
+  pub export_info: Option<ExportInfo>,
+}
+
+/// Analyze the used statements of the module based on its used exports without modifying the ast, the result is sorted by the statement id
+pub fn analyze_used_stmts(
+  tree_shake_module: &TreeShakeModule,
+  swc_module: &SwcModule,
+) -> Vec<UsedStmt> {
   // analyze the statement graph start from the used statements
   let mut used_stmts = tree_shake_module
     .used_statements()
@@ -28,59 +33,101 @@ pub fn remove_useless_stmts(
   // sort used_stmts
   used_stmts.sort_by_key(|a| a.0);
 
-  let mut used_import_infos = vec![];
-  let mut used_export_from_infos = vec![];
+  used_stmts
+    .into_iter()
+    .map(|(stmt_id, used_defined_idents)| {
+      let module_item = &swc_module.body[stmt_id];
+
+      let (import_info, mut export_info, ..) =
+        analyze_imports_and_exports(&stmt_id, module_item, Some(used_defined_idents.clone()));
+
+      if let Some(export_info) = &mut export_info {
+        // if this export statement is export * from 'xxx'
+        if export_info.source.is_some()
+          && matches!(
+            export_info.specifiers.first(),
+            Some(ExportSpecifierInfo::All(_))
+          )
+        {
+          export_info.specifiers[0] =
+            ExportSpecifierInfo::All(Some(used_defined_idents.into_iter().collect()));
+        }
+      }
+
+      UsedStmt {
+        id: stmt_id,
+        import_info,
+        export_info,
+      }
+    })
+    .collect()
+}
 
-  // remove unused specifiers in export statement and import statement
-  for (stmt_id, used_defined_idents) in &used_stmts {
-    let module_item = &mut swc_module.body[*stmt_id];
+/// Get the import info and the `export from` info of the used statements, they are used to add the used exports of the dependencies
+pub fn used_imports_and_exports_from(
+  used_stmts: &[UsedStmt],
+) -> (Vec<ImportInfo>, Vec<ExportInfo>) {
+  let used_import_infos = used_stmts
+    .iter()
+    .filter_map(|stmt| stmt.import_info.clone())
+    .collect();
+  let used_export_from_infos = used_stmts
+    .iter()
+    .filter_map(|stmt| stmt.export_info.clone())
+    .filter(|export_info| export_info.source.is_some() && !export_info.specifiers.is_empty())
+    .collect();
+
+  (used_import_infos, used_export_from_infos)
+}
 
-    let (import_info, export_info, ..) =
-      analyze_imports_and_exports(stmt_id, module_item, Some(used_defined_idents.clone()));
+pub fn remove_useless_stmts(
+  tree_shake_module: &mut TreeShakeModule,
+  swc_module: &mut SwcModule,
+) -> (Vec<ImportInfo>, Vec<ExportInfo>) {
+  farmfe_core::farm_profile_function!(format!(
+    "remove_useless_stmts {:?}",
+    tree_shake_module.module_id.to_string()
+  ));
+  let used_stmts = analyze_used_stmts(tree_shake_module, swc_module);
 
-    if let Some(import_info) = import_info {
-      used_import_infos.push(import_info.clone());
+  // remove unused specifiers in export statement and import statement
+  for used_stmt in &used_stmts {
+    let module_item = &mut swc_module.body[used_stmt.id];
 
-      let mut remover = UselessImportStmtsRemover { import_info };
+    if let Some(import_info) = &used_stmt.import_info {
+      let mut remover = UselessImportStmtsRemover {
+        import_info: import_info.clone(),
+      };
 
       module_item.visit_mut_with(&mut remover);
     }
 
-    if let Some(mut export_info) = export_info {
-      if export_info.specifiers.is_empty() {
+    if let Some(export_info) = &used_stmt.export_info {
+      if export_info.specifiers.is_empty()
+        || (export_info.source.is_some()
+          && matches!(export_info.specifiers[0], ExportSpecifierInfo::All(_)))
+      {
         continue;
       }
 
-      // if this export statement is export * from 'xxx'
-      if export_info.source.is_some()
-        && matches!(export_info.specifiers[0], ExportSpecifierInfo::All(_))
-      {
-        export_info.specifiers[0] =
-          ExportSpecifierInfo::All(Some(used_defined_idents.clone().into_iter().collect()));
-        used_export_from_infos.push(export_info.clone());
-      } else {
-        if export_info.source.is_some() {
-          used_export_from_infos.push(export_info.clone());
-        }
-
-        let mut remover = UselessExportStmtRemover { export_info };
+      let mut remover = UselessExportStmtRemover {
+        export_info: export_info.clone(),
+      };
 
-        module_item.visit_mut_with(&mut remover);
-      }
+      module_item.visit_mut_with(&mut remover);
     }
   }
 
   let mut stmts_to_remove = vec![];
-  // TODO recognize the self-executed statements and preserve all the related statements
 
   let used_stmts_indexes = used_stmts
     .iter()
-    .map(|(index, _)| index)
+    .map(|used_stmt| used_stmt.id)
     .collect::<Vec<_>>();
 
   // remove the unused statements from the module
   for (index, _) in swc_module.body.iter().enumerate() {
-    if !used_stmts_indexes.contains(&&index) {
+    if !used_stmts_indexes.contains(&index) {
       stmts_to_remove.push(index);
     }
   }
@@ -92,7 +139,7 @@ pub fn remove_useless_stmts(
     swc_module.body.remove(stmt);
   }
 
-  (used_import_infos, used_export_from_infos)
+  used_imports_and_exports_from(&used_stmts)
 }
 
 pub struct UselessImportStmtsRemover {
@@ -182,7 +229,17 @@ impl VisitMut for UselessExportStmtRemover {
             farmfe_core::swc_ecma_ast::ExportSpecifier::Named(named_specifier) => {
               match &named_specifier.orig {
                 ModuleExportName::Ident(ident) => ident.to_string() == *local,
-                _ => false,
+                ModuleExportName::Str(str) => str.value == **local,
+              }
+            }
+            _ => false,
+          },
+          // export * as foo from 'foo';
+          ExportSpecifierInfo::Namespace(ns) => match specifier {
+            farmfe_core::swc_ecma_ast::ExportSpecifier::Namespace(namespace_specifier) => {
+              match &namespace_specifier.name {
+                ModuleExportName::Ident(ident) => ident.to_string() == *ns,
+                ModuleExportName::Str(str) => str.value == **ns,
               }
             }
             _ => false,
@@ -0,0 +1,131 @@
+use farmfe_core::{
+  hashbrown::HashMap,
+  module::ModuleId,
+  record::{TreeShakeRecord, TreeShakeRetainedReason},
+};
+
+use crate::module::UsedExports;
+
+/// Collect the reasons why the modules are retained after tree shaking, only works when `config.record` is enabled.
+/// The report is written to [farmfe_core::record::RecordManager] as [TreeShakeRecord]s.
+pub struct TreeShakeReport {
+  enabled: bool,
+  reasons: HashMap<ModuleId, Vec<TreeShakeRetainedReason>>,
+}
+
+impl TreeShakeReport {
+  pub fn new(enabled: bool) -> Self {
+    Self {
+      enabled,
+      reasons: HashMap::new(),
+    }
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    self.enabled
+  }
+
+  pub fn add_reason(&mut self, module_id: &ModuleId, reason: TreeShakeRetainedReason) {
+    if !self.enabled {
+      return;
+    }
+
+    let reasons = self.reasons.entry(module_id.clone()).or_default();
+
+    // the same importer may use the module in several statements, merge the used exports of them
+    if let TreeShakeRetainedReason::UsedExports { importer, exports } = &reason {
+      let existing = reasons.iter_mut().find_map(|r| match r {
+        TreeShakeRetainedReason::UsedExports {
+          importer: existing_importer,
+          exports,
+        } if existing_importer == importer => Some(exports),
+        _ => None,
+      });
+
+      if let Some(existing) = existing {
+        for export in exports {
+          if !existing.contains(export) {
+            existing.push(export.clone());
+          }
+        }
+
+        return;
+      }
+    }
+
+    if !reasons.contains(&reason) {
+      reasons.push(reason);
+    }
+  }
+
+  /// `importer` is None if the module has side effects itself
+  pub fn add_side_effects(&mut self, module_id: &ModuleId, importer: Option<&ModuleId>) {
+    if self.enabled {
+      self.add_reason(
+        module_id,
+        TreeShakeRetainedReason::SideEffects {
+          importer: importer.map(|importer| importer.to_string()),
+        },
+      );
+    }
+  }
+
+  pub fn add_commonjs_bailout(&mut self, module_id: &ModuleId, importer: &ModuleId) {
+    if self.enabled {
+      self.add_reason(
+        module_id,
+        TreeShakeRetainedReason::CommonJsBailout {
+          importer: importer.to_string(),
+        },
+      );
+    }
+  }
+
+  pub fn add_used_exports(
+    &mut self,
+    module_id: &ModuleId,
+    importer: &ModuleId,
+    used_exports: &UsedExports,
+  ) {
+    if !self.enabled || used_exports.is_empty() {
+      return;
+    }
+
+    let exports = match used_exports {
+      UsedExports::All => vec!["*".to_string()],
+      UsedExports::Partial(used_exports) => {
+        let mut exports = vec![];
+
+        for export in used_exports {
+          if !exports.contains(export) {
+            exports.push(export.clone());
+          }
+        }
+
+        exports
+      }
+    };
+
+    self.add_reason(
+      module_id,
+      TreeShakeRetainedReason::UsedExports {
+        importer: importer.to_string(),
+        exports,
+      },
+    );
+  }
+
+  /// Generate the records of the `retained_modules`, sorted by module id
+  pub fn into_records(mut self, retained_modules: &[ModuleId]) -> Vec<TreeShakeRecord> {
+    let mut records = retained_modules
+      .iter()
+      .map(|module_id| TreeShakeRecord {
+        module_id: module_id.to_string(),
+        reasons: self.reasons.remove(module_id).unwrap_or_default(),
+      })
+      .collect::<Vec<_>>();
+    records.sort_by(|a, b| a.module_id.cmp(&b.module_id));
+
+    records
+  }
+}
@@ -0,0 +1,611 @@
+use farmfe_core::{
+  hashbrown::{HashMap, HashSet},
+  swc_common::{
+    comments::{Comments, SingleThreadedComments},
+    BytePos, Mark,
+  },
+  swc_ecma_ast::{
+    ArrowExpr, AssignExpr, AwaitExpr, CallExpr, Callee, Class, ClassMember, Decl, DefaultDecl,
+    ExportDecl, ExportSpecifier, Expr, ForHead, ForOfStmt, Function, GetterProp, Id, Ident,
+    ImportSpecifier, Lit, MemberProp, Module as SwcModule, ModuleDecl, ModuleExportName,
+    ModuleItem, NewExpr, OptCall, Pat, PatOrExpr, PropName, SetterProp, Stmt, TaggedTpl, ThrowStmt,
+    UnaryExpr, UnaryOp, UpdateExpr, VarDeclKind, YieldExpr,
+  },
+};
+use farmfe_toolkit::{
+  swc_ecma_utils::find_pat_ids,
+  swc_ecma_visit::{Visit, VisitWith},
+};
+
+/// global functions that can be called without side effects
+const PURE_GLOBAL_FUNCTIONS: [&str; 11] = [
+  "Symbol",
+  "String",
+  "Number",
+  "Boolean",
+  "BigInt",
+  "parseInt",
+  "parseFloat",
+  "isNaN",
+  "isFinite",
+  "encodeURIComponent",
+  "decodeURIComponent",
+];
+
+/// global constructors that can be called by `new` without side effects
+const PURE_GLOBAL_CONSTRUCTORS: [&str; 13] = [
+  "Object",
+  "Array",
+  "Map",
+  "Set",
+  "WeakMap",
+  "WeakSet",
+  "Date",
+  "RegExp",
+  "Error",
+  "TypeError",
+  "RangeError",
+  "SyntaxError",
+  "Promise",
+];
+
+/// static methods of the global objects that can be called without side effects, `Math.*` are all pure
+const PURE_GLOBAL_METHODS: [(&str, &str); 12] = [
+  ("Object", "freeze"),
+  ("Object", "create"),
+  ("Object", "keys"),
+  ("Object", "values"),
+  ("Object", "entries"),
+  ("Object", "getPrototypeOf"),
+  ("Object", "getOwnPropertyNames"),
+  ("Array", "isArray"),
+  ("Array", "of"),
+  ("Number", "isInteger"),
+  ("Number", "isNaN"),
+  ("Symbol", "for"),
+];
+
+/// Analyze whether the top level statements of a esm module have side effects, a statement is considered pure if:
+/// * it only declares functions, or classes and variables whose initializers are pure
+/// * the calls in it are annotated by `/*#__PURE__*/`, or the callees are known pure globals like `Object.freeze` or functions proven pure
+///
+/// A function is proven pure if calling it does not modify anything outside the function and it's never reassigned, the functions imported
+/// from other modules are pure if they are proven pure in the modules that export them.
+pub struct SideEffectsAnalyzer<'a> {
+  unresolved_mark: Mark,
+  comments: &'a SingleThreadedComments,
+  /// the variables, functions, classes and imports declared in the top level scope
+  top_level_idents: HashSet<Id>,
+  /// the top level functions and the imported functions that are proven pure
+  pure_functions: HashSet<Id>,
+}
+
+impl<'a> SideEffectsAnalyzer<'a> {
+  /// `dep_pure_exports` returns the pure exported functions of the module imported by `source`
+  pub fn new<'b>(
+    ast: &SwcModule,
+    unresolved_mark: Mark,
+    comments: &'a SingleThreadedComments,
+    dep_pure_exports: &dyn Fn(&str) -> Option<&'b HashSet<String>>,
+  ) -> Self {
+    let mut analyzer = Self {
+      unresolved_mark,
+      comments,
+      top_level_idents: HashSet::new(),
+      pure_functions: HashSet::new(),
+    };
+
+    // the imported functions that are pure in the dependencies
+    for item in &ast.body {
+      if let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item {
+        analyzer
+          .top_level_idents
+          .extend(
+            import_decl
+              .specifiers
+              .iter()
+              .map(|specifier| match specifier {
+                ImportSpecifier::Named(named) => named.local.to_id(),
+                ImportSpecifier::Default(default) => default.local.to_id(),
+                ImportSpecifier::Namespace(ns) => ns.local.to_id(),
+              }),
+          );
+
+        if let Some(pure_exports) = dep_pure_exports(&import_decl.src.value) {
+          for specifier in &import_decl.specifiers {
+            let (local, imported) = match specifier {
+              ImportSpecifier::Named(named) => (
+                &named.local,
+                named
+                  .imported
+                  .as_ref()
+                  .map(module_export_name)
+                  .unwrap_or_else(|| named.local.sym.to_string()),
+              ),
+              ImportSpecifier::Default(default) => (&default.local, "default".to_string()),
+              ImportSpecifier::Namespace(_) => continue,
+            };
+
+            if pure_exports.contains(&imported) {
+              analyzer.pure_functions.insert(local.to_id());
+            }
+          }
+        }
+      }
+    }
+
+    // the top level functions, a function is pure if it only calls pure functions, so repeat until no more pure functions are found
+    let mut candidates = HashMap::new();
+
+    for item in &ast.body {
+      match item {
+        ModuleItem::Stmt(Stmt::Decl(decl))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => match decl {
+          Decl::Fn(fn_decl) => {
+            analyzer.top_level_idents.insert(fn_decl.ident.to_id());
+            candidates.insert(fn_decl.ident.to_id(), FnRef::Function(&fn_decl.function));
+          }
+          Decl::Class(class_decl) => {
+            analyzer.top_level_idents.insert(class_decl.ident.to_id());
+          }
+          Decl::Var(var_decl) => {
+            analyzer
+              .top_level_idents
+              .extend(find_pat_ids::<_, Id>(&var_decl.decls));
+
+            // `let` and `var` may be reassigned
+            if var_decl.kind != VarDeclKind::Const {
+              continue;
+            }
+
+            for decl in &var_decl.decls {
+              if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
+                if let Some(fn_ref) = FnRef::from_expr(init) {
+                  candidates.insert(ident.id.to_id(), fn_ref);
+                }
+              }
+            }
+          }
+          _ => {}
+        },
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_default_decl)) => {
+          match &export_default_decl.decl {
+            DefaultDecl::Fn(fn_expr) => {
+              if let Some(ident) = &fn_expr.ident {
+                analyzer.top_level_idents.insert(ident.to_id());
+                candidates.insert(ident.to_id(), FnRef::Function(&fn_expr.function));
+              }
+            }
+            DefaultDecl::Class(class_expr) => {
+              if let Some(ident) = &class_expr.ident {
+                analyzer.top_level_idents.insert(ident.to_id());
+              }
+            }
+            _ => {}
+          }
+        }
+        _ => {}
+      }
+    }
+
+    // the functions may be replaced by impure ones, e.g. `function a() {}; a = () => console.log(1);`
+    let mut reassigned_idents_collector = ReassignedIdentsCollector::default();
+    ast.visit_with(&mut reassigned_idents_collector);
+    candidates.retain(|id, _| !reassigned_idents_collector.idents.contains(id));
+
+    loop {
+      let pure_candidates = candidates
+        .iter()
+        .filter(|(_, fn_ref)| analyzer.is_pure_fn(fn_ref))
+        .map(|(id, _)| id.clone())
+        .collect::<Vec<_>>();
+
+      if pure_candidates.is_empty() {
+        break;
+      }
+
+      for id in pure_candidates {
+        candidates.remove(&id);
+        analyzer.pure_functions.insert(id);
+      }
+    }
+
+    analyzer
+  }
+
+  /// Whether executing the top level statement has side effects.
+  pub fn has_side_effects(&self, item: &ModuleItem) -> bool {
+    let mut finder = SideEffectsFinder {
+      analyzer: self,
+      found: false,
+    };
+
+    match item {
+      ModuleItem::ModuleDecl(module_decl) => match module_decl {
+        ModuleDecl::Import(import_decl) => return import_decl.specifiers.is_empty(),
+        ModuleDecl::ExportDecl(export_decl) => export_decl.decl.visit_with(&mut finder),
+        ModuleDecl::ExportDefaultDecl(export_default_decl) => {
+          if let DefaultDecl::Class(class_expr) = &export_default_decl.decl {
+            class_expr.class.visit_with(&mut finder);
+          }
+        }
+        ModuleDecl::ExportDefaultExpr(export_default_expr) => {
+          export_default_expr.expr.visit_with(&mut finder)
+        }
+        _ => {}
+      },
+      ModuleItem::Stmt(stmt) => match stmt {
+        Stmt::Decl(_) | Stmt::Expr(_) => stmt.visit_with(&mut finder),
+        Stmt::Empty(_) | Stmt::Debugger(_) => {}
+        // the control flow statements are always executed, the `var` declared in them are hoisted to the top level scope
+        _ => return true,
+      },
+    }
+
+    finder.found
+  }
+
+  /// The exported names of the functions that are proven pure, including the pure functions re-exported from `dep_pure_exports`.
+  pub fn pure_exports<'b>(
+    &self,
+    ast: &SwcModule,
+    dep_pure_exports: &dyn Fn(&str) -> Option<&'b HashSet<String>>,
+  ) -> HashSet<String> {
+    let mut pure_exports = HashSet::new();
+
+    for item in &ast.body {
+      if let ModuleItem::ModuleDecl(module_decl) = item {
+        match module_decl {
+          ModuleDecl::ExportDecl(export_decl) => match &export_decl.decl {
+            Decl::Fn(fn_decl) if self.pure_functions.contains(&fn_decl.ident.to_id()) => {
+              pure_exports.insert(fn_decl.ident.sym.to_string());
+            }
+            Decl::Var(var_decl) => {
+              for decl in &var_decl.decls {
+                if let Pat::Ident(ident) = &decl.name {
+                  if self.pure_functions.contains(&ident.id.to_id()) {
+                    pure_exports.insert(ident.id.sym.to_string());
+                  }
+                }
+              }
+            }
+            _ => {}
+          },
+          ModuleDecl::ExportDefaultDecl(export_default_decl) => {
+            if let DefaultDecl::Fn(fn_expr) = &export_default_decl.decl {
+              let is_pure = match &fn_expr.ident {
+                Some(ident) => self.pure_functions.contains(&ident.to_id()),
+                None => self.is_pure_fn(&FnRef::Function(&fn_expr.function)),
+              };
+
+              if is_pure {
+                pure_exports.insert("default".to_string());
+              }
+            }
+          }
+          ModuleDecl::ExportDefaultExpr(export_default_expr) => {
+            let is_pure = match &*export_default_expr.expr {
+              Expr::Ident(ident) => self.pure_functions.contains(&ident.to_id()),
+              expr => FnRef::from_expr(expr).map_or(false, |fn_ref| self.is_pure_fn(&fn_ref)),
+            };
+
+            if is_pure {
+              pure_exports.insert("default".to_string());
+            }
+          }
+          ModuleDecl::ExportNamed(export_named) => {
+            let dep_pure_exports = export_named
+              .src
+              .as_ref()
+              .and_then(|src| dep_pure_exports(&src.value));
+
+            for specifier in &export_named.specifiers {
+              if let ExportSpecifier::Named(named) = specifier {
+                let exported = named.exported.as_ref().unwrap_or(&named.orig);
+                let is_pure = match (&named.orig, dep_pure_exports) {
+                  (orig, Some(dep_pure_exports)) => {
+                    dep_pure_exports.contains(&module_export_name(orig))
+                  }
+                  (ModuleExportName::Ident(ident), None) if export_named.src.is_none() => {
+                    self.pure_functions.contains(&ident.to_id())
+                  }
+                  _ => false,
+                };
+
+                if is_pure {
+                  pure_exports.insert(module_export_name(exported));
+                }
+              }
+            }
+          }
+          ModuleDecl::ExportAll(export_all) => {
+            if let Some(dep_pure_exports) = dep_pure_exports(&export_all.src.value) {
+              pure_exports.extend(
+                dep_pure_exports
+                  .iter()
+                  .filter(|name| *name != "default")
+                  .cloned(),
+              );
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+
+    pure_exports
+  }
+
+  fn is_pure_fn(&self, fn_ref: &FnRef) -> bool {
+    let mut finder = SideEffectsFinder {
+      analyzer: self,
+      found: false,
+    };
+
+    match fn_ref {
+      // calling a generator function only creates the generator
+      FnRef::Function(function) if function.is_generator => return true,
+      FnRef::Function(function) => {
+        function.params.visit_with(&mut finder);
+        function.body.visit_with(&mut finder);
+      }
+      FnRef::Arrow(arrow) => {
+        arrow.params.visit_with(&mut finder);
+        arrow.body.visit_with(&mut finder);
+      }
+    }
+
+    !finder.found
+  }
+
+  fn is_pure_annotated(&self, pos: BytePos) -> bool {
+    self.comments.get_leading(pos).map_or(false, |comments| {
+      comments
+        .iter()
+        .any(|c| c.text.contains("#__PURE__") || c.text.contains("@__PURE__"))
+    })
+  }
+
+  fn is_global(&self, ident: &Ident) -> bool {
+    ident.span.ctxt.outer() == self.unresolved_mark
+  }
+
+  fn is_pure_callee(&self, callee: &Expr) -> bool {
+    match callee {
+      Expr::Ident(ident) if self.is_global(ident) => PURE_GLOBAL_FUNCTIONS.contains(&&*ident.sym),
+      Expr::Ident(ident) => self.pure_functions.contains(&ident.to_id()),
+      Expr::Member(member_expr) => match (&*member_expr.obj, &member_expr.prop) {
+        (Expr::Ident(obj), MemberProp::Ident(prop)) if self.is_global(obj) => {
+          obj.sym == *"Math"
+            || PURE_GLOBAL_METHODS
+              .iter()
+              .any(|(o, p)| obj.sym == **o && prop.sym == **p)
+        }
+        // `"a".concat(b)` generated by the template literal transform
+        (Expr::Lit(Lit::Str(_)), MemberProp::Ident(prop)) => prop.sym == *"concat",
+        _ => false,
+      },
+      Expr::Paren(paren) => self.is_pure_callee(&paren.expr),
+      _ => false,
+    }
+  }
+
+  /// the variables declared in functions or blocks, assigning to them does not affect others
+  fn is_local(&self, ident: &Ident) -> bool {
+    !self.is_global(ident) && !self.top_level_idents.contains(&ident.to_id())
+  }
+}
+
+enum FnRef<'a> {
+  Function(&'a Function),
+  Arrow(&'a ArrowExpr),
+}
+
+impl<'a> FnRef<'a> {
+  fn from_expr(expr: &'a Expr) -> Option<Self> {
+    match expr {
+      Expr::Fn(fn_expr) => Some(Self::Function(&fn_expr.function)),
+      Expr::Arrow(arrow) => Some(Self::Arrow(arrow)),
+      Expr::Paren(paren) => Self::from_expr(&paren.expr),
+      _ => None,
+    }
+  }
+}
+
+struct SideEffectsFinder<'a, 'b> {
+  analyzer: &'b SideEffectsAnalyzer<'a>,
+  found: bool,
+}
+
+impl<'a, 'b> Visit for SideEffectsFinder<'a, 'b> {
+  fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+    let is_pure = match &call_expr.callee {
+      Callee::Expr(callee) => {
+        self.analyzer.is_pure_annotated(call_expr.span.lo) || self.analyzer.is_pure_callee(callee)
+      }
+      _ => false,
+    };
+
+    if !is_pure {
+      self.found = true;
+      return;
+    }
+
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_opt_call(&mut self, opt_call: &OptCall) {
+    if !self.analyzer.is_pure_callee(&opt_call.callee) {
+      self.found = true;
+      return;
+    }
+
+    opt_call.visit_children_with(self);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr) {
+    let is_pure = self.analyzer.is_pure_annotated(new_expr.span.lo)
+      || matches!(&*new_expr.callee, Expr::Ident(ident) if self.analyzer.is_global(ident) && PURE_GLOBAL_CONSTRUCTORS.contains(&&*ident.sym));
+
+    if !is_pure {
+      self.found = true;
+      return;
+    }
+
+    new_expr.visit_children_with(self);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+    let is_local = match &assign_expr.left {
+      PatOrExpr::Expr(box Expr::Ident(ident)) => self.analyzer.is_local(ident),
+      PatOrExpr::Pat(box Pat::Ident(binding)) => self.analyzer.is_local(&binding.id),
+      _ => false,
+    };
+
+    if !is_local {
+      self.found = true;
+      return;
+    }
+
+    assign_expr.right.visit_with(self);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr) {
+    if !matches!(&*update_expr.arg, Expr::Ident(ident) if self.analyzer.is_local(ident)) {
+      self.found = true;
+    }
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr) {
+    match unary_expr.op {
+      UnaryOp::Delete => self.found = true,
+      // `typeof window` does not throw even if `window` is not defined
+      UnaryOp::TypeOf if matches!(&*unary_expr.arg, Expr::Ident(_)) => {}
+      _ => unary_expr.visit_children_with(self),
+    }
+  }
+
+  fn visit_ident(&mut self, ident: &Ident) {
+    // reading a undefined global variable throws
+    if self.analyzer.is_global(ident) && !is_known_global(&ident.sym) {
+      self.found = true;
+    }
+  }
+
+  fn visit_prop_name(&mut self, prop_name: &PropName) {
+    if let PropName::Computed(computed) = prop_name {
+      computed.visit_with(self);
+    }
+  }
+
+  fn visit_member_prop(&mut self, prop: &MemberProp) {
+    if let MemberProp::Computed(computed) = prop {
+      computed.visit_with(self);
+    }
+  }
+
+  fn visit_class(&mut self, class: &Class) {
+    class.super_class.visit_with(self);
+
+    for member in &class.body {
+      match member {
+        ClassMember::Method(method) => method.key.visit_with(self),
+        ClassMember::ClassProp(prop) => {
+          prop.key.visit_with(self);
+
+          if prop.is_static {
+            prop.value.visit_with(self);
+          }
+        }
+        ClassMember::PrivateProp(prop) if prop.is_static => prop.value.visit_with(self),
+        ClassMember::StaticBlock(block) => block.visit_with(self),
+        _ => {}
+      }
+    }
+  }
+
+  // defining a function does not execute it
+  fn visit_function(&mut self, _: &Function) {}
+
+  fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+
+  fn visit_getter_prop(&mut self, getter: &GetterProp) {
+    getter.key.visit_with(self);
+  }
+
+  fn visit_setter_prop(&mut self, setter: &SetterProp) {
+    setter.key.visit_with(self);
+  }
+
+  fn visit_await_expr(&mut self, _: &AwaitExpr) {
+    self.found = true;
+  }
+
+  fn visit_yield_expr(&mut self, _: &YieldExpr) {
+    self.found = true;
+  }
+
+  fn visit_tagged_tpl(&mut self, _: &TaggedTpl) {
+    self.found = true;
+  }
+
+  fn visit_throw_stmt(&mut self, _: &ThrowStmt) {
+    self.found = true;
+  }
+
+  fn visit_for_of_stmt(&mut self, _: &ForOfStmt) {
+    self.found = true;
+  }
+}
+
+/// Collect the variables that are reassigned anywhere in the module, e.g. `a = 1`, `[a] = arr`, `a++` and `for (a in obj)`
+#[derive(Default)]
+struct ReassignedIdentsCollector {
+  idents: HashSet<Id>,
+}
+
+impl Visit for ReassignedIdentsCollector {
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+    match &assign_expr.left {
+      PatOrExpr::Pat(pat) => self.idents.extend(find_pat_ids::<_, Id>(pat)),
+      PatOrExpr::Expr(box Expr::Ident(ident)) => {
+        self.idents.insert(ident.to_id());
+      }
+      _ => {}
+    }
+
+    assign_expr.visit_children_with(self);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr) {
+    if let Expr::Ident(ident) = &*update_expr.arg {
+      self.idents.insert(ident.to_id());
+    }
+
+    update_expr.visit_children_with(self);
+  }
+
+  fn visit_for_head(&mut self, for_head: &ForHead) {
+    if let ForHead::Pat(pat) = for_head {
+      self.idents.extend(find_pat_ids::<_, Id>(pat));
+    }
+
+    for_head.visit_children_with(self);
+  }
+}
+
+/// global variables that always exist, reading them does not throw
+fn is_known_global(sym: &str) -> bool {
+  matches!(
+    sym,
+    "undefined" | "NaN" | "Infinity" | "globalThis" | "Math" | "JSON" | "Reflect" | "Intl"
+  ) || PURE_GLOBAL_FUNCTIONS.contains(&sym)
+    || PURE_GLOBAL_CONSTRUCTORS.contains(&sym)
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
+}
@@ -79,10 +79,7 @@ pub fn analyze_imports_and_exports(
 
               specifiers.push(ImportSpecifierInfo::Named {
                 local: named.local.to_string(),
-                imported: named.imported.as_ref().map(|i| match i {
-                  ModuleExportName::Ident(i) => i.to_string(),
-                  _ => panic!("non-ident imported is not supported when tree shaking"),
-                }),
+                imported: named.imported.as_ref().map(module_export_name),
               });
               defined_idents.insert(named.local.to_string());
             }
@@ -211,38 +208,28 @@ pub fn analyze_imports_and_exports(
         for specifier in &export_named.specifiers {
           match specifier {
             swc_ecma_ast::ExportSpecifier::Named(named) => {
-              let local = match &named.orig {
-                ModuleExportName::Ident(i) => i.clone(),
-                ModuleExportName::Str(_) => unimplemented!("exporting a string is not supported"),
-              };
+              // the local can only be a string when re-exporting, e.g. `export { "a-b" as c } from './foo'`
+              let local = module_export_name(&named.orig);
 
-              if !is_ident_used(&local.to_string()) {
+              if !is_ident_used(&local) {
                 continue;
               }
 
               if export_named.src.is_none() {
-                used_idents.insert(local.to_string());
-                defined_idents_map.insert(local.to_string(), [local.to_string()].into());
+                used_idents.insert(local.clone());
+                defined_idents_map.insert(local.clone(), [local.clone()].into());
               }
 
               specifiers.push(ExportSpecifierInfo::Named {
-                local: local.to_string(),
-                exported: named.exported.as_ref().map(|i| match i {
-                  ModuleExportName::Ident(i) => i.to_string(),
-                  _ => panic!("non-ident exported is not supported when tree shaking"),
-                }),
+                local,
+                exported: named.exported.as_ref().map(module_export_name),
               });
             }
             swc_ecma_ast::ExportSpecifier::Default(_) => {
               unreachable!("ExportSpecifier::Default is not valid esm syntax")
             }
             swc_ecma_ast::ExportSpecifier::Namespace(ns) => {
-              let ident = match &ns.name {
-                ModuleExportName::Ident(ident) => ident.to_string(),
-                ModuleExportName::Str(_) => unreachable!("exporting a string is not supported"),
-              };
-
-              specifiers.push(ExportSpecifierInfo::Namespace(ident));
+              specifiers.push(ExportSpecifierInfo::Namespace(module_export_name(&ns.name)));
             }
           }
         }
@@ -367,5 +354,13 @@ pub fn analyze_imports_and_exports(
   )
 }
 
+/// `a#1` for ident `a` and `a-b` for string `"a-b"`
+fn module_export_name(name: &ModuleExportName) -> String {
+  match name {
+    ModuleExportName::Ident(ident) => ident.to_string(),
+    ModuleExportName::Str(str) => str.value.to_string(),
+  }
+}
+
 #[cfg(test)]
 mod test;
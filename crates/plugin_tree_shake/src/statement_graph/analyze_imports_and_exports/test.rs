@@ -20,7 +20,8 @@ fn parse_module_item(stmt: &str) -> ModuleItem {
     farmfe_core::swc_ecma_ast::EsVersion::Es2015,
     Arc::new(SourceMap::new(FilePathMapping::empty())),
   )
-  .unwrap();
+  .unwrap()
+  .ast;
   module.body[0].clone()
 }
 
@@ -17,7 +17,8 @@ pub fn parse_module(code: &str) -> (SwcModule, Arc<SourceMap>) {
     EsVersion::EsNext,
     cm.clone(),
   )
-  .unwrap();
+  .unwrap()
+  .ast;
 
   swc_module.body.visit_mut_with(&mut resolver(
     Mark::fresh(Mark::root()),
@@ -37,6 +38,9 @@ pub fn create_module(code: &str) -> (Module, Arc<SourceMap>) {
     unresolved_mark: 0,
     module_system: farmfe_core::module::ModuleSystem::EsModule,
     hmr_accepted: false,
+    comments: Default::default(),
+    dynamic_import_magic_comments: vec![],
+    top_level_await: false,
   });
   (module, cm)
 }
@@ -51,6 +55,9 @@ pub fn create_module_with_globals(code: &str) -> Module {
       unresolved_mark: 0,
       module_system: farmfe_core::module::ModuleSystem::EsModule,
       hmr_accepted: false,
+      comments: Default::default(),
+      dynamic_import_magic_comments: vec![],
+      top_level_await: false,
     });
     module
   })
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use farmfe_core::{
+  swc_common::{FilePathMapping, Globals, Mark, SourceMap, GLOBALS},
+  swc_ecma_ast::{EsVersion, Module as SwcModule},
+  swc_ecma_parser::Syntax,
+};
+use farmfe_plugin_tree_shake::{
+  commonjs::{analyze_require_usages, remove_unused_exports},
+  module::UsedExports,
+};
+use farmfe_toolkit::{
+  script::codegen_module, swc_ecma_transforms::resolver, swc_ecma_visit::VisitMutWith,
+};
+
+fn parse_cjs_module(code: &str) -> (SwcModule, Arc<SourceMap>, Mark, Mark) {
+  let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+  let mut ast = farmfe_toolkit::script::parse_module(
+    "any",
+    code,
+    Syntax::Es(Default::default()),
+    EsVersion::EsNext,
+    cm.clone(),
+  )
+  .unwrap()
+  .ast;
+
+  let unresolved_mark = Mark::new();
+  let top_level_mark = Mark::new();
+  ast.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+
+  (ast, cm, unresolved_mark, top_level_mark)
+}
+
+fn partial(used_exports: Option<&UsedExports>) -> Option<Vec<String>> {
+  match used_exports {
+    Some(UsedExports::Partial(used_exports)) => {
+      let mut used_exports = used_exports.clone();
+      used_exports.sort();
+      Some(used_exports)
+    }
+    _ => None,
+  }
+}
+
+#[test]
+fn analyze_require_usages_basic() {
+  let code = r#"
+const a = require('./a');
+const { b1, b2: renamed } = require('./b');
+const c = require('./c').c1;
+const d = require('./d');
+const { ...e } = require('./e');
+require('./f');
+
+a.a1();
+console.log(a.a2, renamed, d);
+
+function foo() {
+  const a = 1;
+  return a + require('./g')['g1'];
+}
+  "#;
+
+  GLOBALS.set(&Globals::new(), || {
+    let (ast, _, unresolved_mark, top_level_mark) = parse_cjs_module(code);
+    let usages = analyze_require_usages(&ast, unresolved_mark, top_level_mark);
+
+    assert_eq!(
+      partial(usages.get("./a")),
+      Some(vec!["a1".to_string(), "a2".to_string()])
+    );
+    assert_eq!(
+      partial(usages.get("./b")),
+      Some(vec!["b1".to_string(), "b2".to_string()])
+    );
+    assert_eq!(partial(usages.get("./c")), Some(vec!["c1".to_string()]));
+    assert_eq!(partial(usages.get("./g")), Some(vec!["g1".to_string()]));
+    // `d` is used as a whole, `e` is a rest pattern and `./f` is required for side effects
+    assert!(matches!(usages.get("./d"), Some(UsedExports::All)));
+    assert!(matches!(usages.get("./e"), Some(UsedExports::All)));
+    assert!(matches!(usages.get("./f"), Some(UsedExports::All)));
+  });
+}
+
+#[test]
+fn remove_unused_exports_basic() {
+  let code = r#"
+Object.defineProperty(exports, "__esModule", { value: true });
+exports.b = exports.a = void 0;
+exports.a = function() {};
+exports.b = 1;
+module.exports.c = () => 2;
+exports.d = sideEffect();
+module.exports = { e: 1, f, g() {}, h: sideEffect() };
+function f() {}
+  "#;
+
+  GLOBALS.set(&Globals::new(), || {
+    let (mut ast, cm, unresolved_mark, _) = parse_cjs_module(code);
+    remove_unused_exports(
+      &mut ast,
+      &["a".to_string(), "f".to_string()],
+      unresolved_mark,
+    );
+
+    let bytes = codegen_module(&ast, EsVersion::EsNext, cm, None, false, None).unwrap();
+    let result = String::from_utf8(bytes).unwrap();
+
+    assert_eq!(
+      result,
+      r#"Object.defineProperty(exports, "__esModule", {
+    value: true
+});
+exports.b = exports.a = void 0;
+exports.a = function() {};
+exports.d = sideEffect();
+module.exports = {
+    f,
+    h: sideEffect()
+};
+function f() {}
+"#
+    );
+  });
+}
+
+#[test]
+fn remove_unused_exports_bail_out() {
+  let code = r#"
+exports.a = 1;
+exports.b = 2;
+exports.c = function() {
+  return exports[key];
+};
+  "#;
+
+  GLOBALS.set(&Globals::new(), || {
+    let (mut ast, cm, unresolved_mark, _) = parse_cjs_module(code);
+    remove_unused_exports(&mut ast, &["a".to_string()], unresolved_mark);
+
+    let bytes = codegen_module(&ast, EsVersion::EsNext, cm, None, false, None).unwrap();
+    let result = String::from_utf8(bytes).unwrap();
+
+    assert_eq!(
+      result,
+      r#"exports.a = 1;
+exports.b = 2;
+exports.c = function() {
+    return exports[key];
+};
+"#
+    );
+  });
+}
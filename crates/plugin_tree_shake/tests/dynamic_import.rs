@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use farmfe_core::{
+  swc_common::{FilePathMapping, Globals, Mark, SourceMap, GLOBALS},
+  swc_ecma_ast::EsVersion,
+  swc_ecma_parser::Syntax,
+};
+use farmfe_plugin_tree_shake::{
+  dynamic_import::analyze_dynamic_import_usages, module::UsedExports,
+};
+use farmfe_toolkit::{swc_ecma_transforms::resolver, swc_ecma_visit::VisitMutWith};
+
+#[test]
+fn analyze_dynamic_import_usages_basic() {
+  let code = r#"
+import('./a').then(({ a1, a2: renamed, 'a-3': a3 }) => console.log(a1, renamed, a3));
+import('./b').then(function (b) {
+  return b.b1 + b['b2'];
+});
+const { c1 } = await import('./c');
+const d = await import('./d');
+console.log(d.d1, (await import('./e')).e1);
+import('./f').then(console.log);
+const { ...g } = await import('./g');
+const h = await import('./h');
+export { h };
+import('./i');
+  "#;
+
+  GLOBALS.set(&Globals::new(), || {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let mut ast = farmfe_toolkit::script::parse_module(
+      "any",
+      code,
+      Syntax::Es(Default::default()),
+      EsVersion::EsNext,
+      cm,
+    )
+    .unwrap()
+    .ast;
+    ast.visit_mut_with(&mut resolver(Mark::new(), Mark::new(), false));
+
+    let usages = analyze_dynamic_import_usages(&ast.body.iter().collect::<Vec<_>>());
+    let partial = |source: &str| match usages.get(source) {
+      Some(UsedExports::Partial(used_exports)) => Some(used_exports.clone()),
+      _ => None,
+    };
+
+    assert_eq!(
+      partial("./a"),
+      Some(vec!["a1".to_string(), "a2".to_string(), "a-3".to_string()])
+    );
+    assert_eq!(
+      partial("./b"),
+      Some(vec!["b1".to_string(), "b2".to_string()])
+    );
+    assert_eq!(partial("./c"), Some(vec!["c1".to_string()]));
+    assert_eq!(partial("./d"), Some(vec!["d1".to_string()]));
+    assert_eq!(partial("./e"), Some(vec!["e1".to_string()]));
+    // the callback is not analyzable, `g` is a rest pattern, `h` escapes and `./i` is imported for side effects
+    assert!(matches!(usages.get("./f"), Some(UsedExports::All)));
+    assert!(matches!(usages.get("./g"), Some(UsedExports::All)));
+    assert!(matches!(usages.get("./h"), Some(UsedExports::All)));
+    assert!(matches!(usages.get("./i"), Some(UsedExports::All)));
+  });
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use farmfe_core::{
+  hashbrown::HashSet,
+  swc_common::{FilePathMapping, SourceMap},
+  swc_ecma_ast::EsVersion,
+  swc_ecma_parser::Syntax,
+};
+use farmfe_plugin_tree_shake::{module::UsedExports, namespace::analyze_namespace_usages};
+
+#[test]
+fn analyze_namespace_usages_basic() {
+  let code = r#"
+import * as a from './a';
+import * as b from './b';
+import * as c from './c';
+import * as d from './d';
+
+a.foo();
+console.log(a['bar'], b[key], c);
+export { d };
+  "#;
+  let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+  let ast = farmfe_toolkit::script::parse_module(
+    "any",
+    code,
+    Syntax::Es(Default::default()),
+    EsVersion::EsNext,
+    cm,
+  )
+  .unwrap()
+  .ast;
+
+  // the locals are formatted with their syntax context, like the import specifiers
+  let namespaces = HashSet::from([
+    "a#0".to_string(),
+    "b#0".to_string(),
+    "c#0".to_string(),
+    "d#0".to_string(),
+  ]);
+  let usages = analyze_namespace_usages(&ast.body.iter().collect::<Vec<_>>(), &namespaces);
+
+  match usages.get("a#0") {
+    Some(UsedExports::Partial(used_exports)) => {
+      assert_eq!(used_exports, &vec!["foo".to_string(), "bar".to_string()])
+    }
+    _ => panic!("the namespace a should be partially used"),
+  }
+  // `b[key]` is a dynamic access, `c` and `d` escape
+  assert!(matches!(usages.get("b#0"), Some(UsedExports::All)));
+  assert!(matches!(usages.get("c#0"), Some(UsedExports::All)));
+  assert!(matches!(usages.get("d#0"), Some(UsedExports::All)));
+}
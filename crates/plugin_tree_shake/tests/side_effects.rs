@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use farmfe_core::{
+  hashbrown::HashSet,
+  swc_common::{
+    comments::SingleThreadedComments, FilePathMapping, Globals, Mark, SourceMap, GLOBALS,
+  },
+  swc_ecma_ast::{EsVersion, Module as SwcModule},
+  swc_ecma_parser::Syntax,
+};
+use farmfe_plugin_tree_shake::side_effects::SideEffectsAnalyzer;
+use farmfe_toolkit::{swc_ecma_transforms::resolver, swc_ecma_visit::VisitMutWith};
+
+fn parse_module(code: &str) -> (SwcModule, SingleThreadedComments, Mark) {
+  let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+  let result = farmfe_toolkit::script::parse_module(
+    "any",
+    code,
+    Syntax::Es(Default::default()),
+    EsVersion::EsNext,
+    cm,
+  )
+  .unwrap();
+  let mut ast = result.ast;
+
+  let unresolved_mark = Mark::new();
+  ast.visit_mut_with(&mut resolver(unresolved_mark, Mark::new(), false));
+
+  (ast, result.comments, unresolved_mark)
+}
+
+fn stmts_with_side_effects(code: &str, dep_pure_exports: &HashSet<String>) -> Vec<usize> {
+  let (ast, comments, unresolved_mark) = parse_module(code);
+  let dep_pure_exports = |_: &str| Some(dep_pure_exports);
+  let analyzer = SideEffectsAnalyzer::new(&ast, unresolved_mark, &comments, &dep_pure_exports);
+
+  ast
+    .body
+    .iter()
+    .enumerate()
+    .filter(|(_, item)| analyzer.has_side_effects(item))
+    .map(|(i, _)| i)
+    .collect()
+}
+
+#[test]
+fn has_side_effects_basic() {
+  let code = r#"
+import { pureFn, impureFn } from './dep';
+let count = 0;
+const a = 1, b = `${a}`, c = { a, b: [b] };
+const d = Object.freeze({ d: 1 });
+const e = /*#__PURE__*/ impureFn();
+const f = impureFn();
+const g = new Map();
+const h = unknownGlobal;
+function add(a, b) {
+  const sum = a + b;
+  return sum;
+}
+function increase() {
+  count++;
+}
+const i = add(1, 2);
+const j = increase();
+const k = pureFn();
+class L {
+  static m = add(1, 2);
+  n = increase();
+}
+class O {
+  static p = increase();
+}
+add(1, 2);
+console.log(a);
+count = 1;
+if (a) {}
+export default add(3, 4);
+  "#;
+
+  GLOBALS.set(&Globals::new(), || {
+    let dep_pure_exports = HashSet::from(["pureFn".to_string()]);
+
+    assert_eq!(
+      stmts_with_side_effects(code, &dep_pure_exports),
+      vec![5, 7, 11, 14, 16, 17, 18]
+    );
+  });
+}
+
+#[test]
+fn pure_exports_basic() {
+  let code = r#"
+import { pureFn } from './dep';
+let count = 0;
+export function a() {
+  return pureFn();
+}
+export function b() {
+  count++;
+}
+export const c = () => a();
+export let d = () => 1;
+function e() {}
+export { e as f };
+export { g, h as i } from './dep';
+export * from './dep';
+export default function () {
+  return c();
+}
+  "#;
+
+  GLOBALS.set(&Globals::new(), || {
+    let (ast, comments, unresolved_mark) = parse_module(code);
+    let dep_pure_exports = HashSet::from(["pureFn".to_string(), "h".to_string()]);
+    let dep_pure_exports = |_: &str| Some(&dep_pure_exports);
+    let analyzer = SideEffectsAnalyzer::new(&ast, unresolved_mark, &comments, &dep_pure_exports);
+
+    let mut pure_exports = analyzer
+      .pure_exports(&ast, &dep_pure_exports)
+      .into_iter()
+      .collect::<Vec<_>>();
+    pure_exports.sort();
+
+    assert_eq!(
+      pure_exports,
+      vec!["a", "c", "default", "f", "h", "i", "pureFn"]
+    );
+  });
+}
+
+#[test]
+fn pure_exports_reassigned() {
+  let code = r#"
+export function a() {}
+export function b() {}
+export function c() {}
+export default function d() {}
+function e() {}
+export { e };
+a = () => console.log('a');
+[b] = [() => console.log('b')];
+for (c in {}) {}
+d = () => console.log('d');
+  "#;
+
+  GLOBALS.set(&Globals::new(), || {
+    let (ast, comments, unresolved_mark) = parse_module(code);
+    let dep_pure_exports = HashSet::new();
+    let dep_pure_exports = |_: &str| Some(&dep_pure_exports);
+    let analyzer = SideEffectsAnalyzer::new(&ast, unresolved_mark, &comments, &dep_pure_exports);
+
+    assert_eq!(
+      analyzer.pure_exports(&ast, &dep_pure_exports),
+      HashSet::from(["e".to_string()])
+    );
+  });
+}
@@ -28,6 +28,21 @@ pub fn transform_output_filename(
   name: &str,
   bytes: &[u8],
   ext: &str,
+) -> String {
+  let content_hash = if filename_config.contains(CONTENT_HASH) {
+    sha256(bytes, 8)
+  } else {
+    String::new()
+  };
+
+  transform_output_filename_with_hash(filename_config, name, &content_hash, ext)
+}
+
+pub fn transform_output_filename_with_hash(
+  filename_config: String,
+  name: &str,
+  content_hash: &str,
+  ext: &str,
 ) -> String {
   let mut res = filename_config;
 
@@ -36,8 +51,7 @@ pub fn transform_output_filename(
   }
 
   if res.contains(CONTENT_HASH) {
-    let content_hash = sha256(bytes, 8);
-    res = res.replace(CONTENT_HASH, &content_hash);
+    res = res.replace(CONTENT_HASH, content_hash);
   }
 
   if res.contains(EXT) {
@@ -51,7 +65,7 @@ pub fn transform_output_entry_filename(
   entry_filename_config: String,
   name: &str,
   entry_filename: &str,
-  bytes: &[u8],
+  content_hash: &str,
   ext: &str,
 ) -> String {
   let mut res = entry_filename_config;
@@ -60,5 +74,15 @@ pub fn transform_output_entry_filename(
     res = res.replace(ENTRY_NAME, entry_filename);
   }
 
-  transform_output_filename(res, name, bytes, ext)
+  transform_output_filename_with_hash(res, name, content_hash, ext)
+}
+
+/// The placeholder of the content hash of the resource generated from a resource pot, the content hash is unknown until
+/// all references between the resources are resolved. It has the same length as the content hash, so the source maps
+/// are still valid after the placeholder is replaced.
+pub fn content_hash_placeholder(resource_pot_id: &str, ext: &str) -> String {
+  sha256(
+    format!("__FARM_CONTENT_HASH__({}.{})", resource_pot_id, ext).as_bytes(),
+    8,
+  )
 }
@@ -23,11 +23,29 @@ pub fn get_dynamic_resources_map(
   });
 
   let mut dynamic_resources_map = HashMap::<ModuleId, Vec<(String, ResourceType)>>::new();
+  // the resource pots of the module group itself are loaded already
+  let loaded_resource_pots = module_group_graph
+    .module_group(module_group_id)
+    .unwrap()
+    .resource_pots();
 
   for mg_id in dep_module_groups {
     let mg = module_group_graph.module_group(&mg_id).unwrap();
+    // sort the resource pots to make sure the generated code is stable
+    let mut rp_ids = mg
+      .resource_pots()
+      .iter()
+      .filter(|rp_id| !loaded_resource_pots.contains(*rp_id))
+      .collect::<Vec<_>>();
+    rp_ids.sort_by_key(|rp_id| rp_id.to_string());
+
+    // all the resources are loaded already, for example, the module is both statically and dynamically imported,
+    // the runtime resolves the dynamic import from the loaded module
+    if rp_ids.is_empty() {
+      continue;
+    }
 
-    for rp_id in mg.resource_pots() {
+    for rp_id in rp_ids {
       let rp = resource_pot_map.resource_pot(rp_id).unwrap_or_else(|| {
         panic!(
           "Resource pot {} not found in resource pot map",
@@ -78,8 +96,11 @@ pub fn get_dynamic_resources_code(
 ) -> String {
   let mut dynamic_resources_code = String::new();
 
-  // inject dynamic resources
-  for (module_id, resources) in dynamic_resources_map {
+  // inject dynamic resources, sorted by module id to make sure the generated code is stable
+  let mut dynamic_resources = dynamic_resources_map.iter().collect::<Vec<_>>();
+  dynamic_resources.sort_by_key(|(module_id, _)| module_id.to_string());
+
+  for (module_id, resources) in dynamic_resources {
     let mut resources_code = String::new();
 
     for (resource_name, resource_type) in resources {
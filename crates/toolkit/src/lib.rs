@@ -17,6 +17,7 @@ pub use swc_ecma_parser;
 pub use swc_ecma_preset_env;
 pub use swc_ecma_transforms;
 pub use swc_ecma_transforms_base;
+pub use swc_ecma_utils;
 pub use swc_ecma_visit;
 
 pub use swc_css_codegen;
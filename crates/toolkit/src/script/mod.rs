@@ -11,25 +11,36 @@ use farmfe_core::{
   error::{CompilationError, Result},
   module::{ModuleSystem, ModuleType},
   plugin::ResolveKind,
-  swc_common::{BytePos, FileName, LineCol, Mark, SourceMap},
-  swc_ecma_ast::{CallExpr, Callee, EsVersion, Expr, Ident, Import, Module as SwcModule, Stmt},
+  swc_common::{
+    comments::{Comments, SingleThreadedComments},
+    BytePos, FileName, LineCol, Mark, SourceMap,
+  },
+  swc_ecma_ast::{
+    CallExpr, Callee, EsVersion, Expr, ExprOrSpread, Ident, Import, Lit, MemberExpr, MemberProp,
+    MetaPropExpr, MetaPropKind, Module as SwcModule, NewExpr, Stmt, Str,
+  },
 };
 use swc_error_reporters::handler::try_with_handler;
 
 pub mod swc_try_with;
 
-/// parse the content of a module to [SwcModule] ast.
+pub struct ParseScriptModuleResult {
+  pub ast: SwcModule,
+  pub comments: SingleThreadedComments,
+}
+
+/// parse the content of a module to [SwcModule] ast, the comments are collected too.
 pub fn parse_module(
   id: &str,
   content: &str,
   syntax: Syntax,
   target: EsVersion,
   cm: Arc<SourceMap>,
-) -> Result<SwcModule> {
+) -> Result<ParseScriptModuleResult> {
   let source_file = cm.new_source_file(FileName::Real(PathBuf::from(id)), content.to_string());
   let input = StringInput::from(&*source_file);
-  // TODO support parsing comments
-  let lexer = Lexer::new(syntax, target, input, None);
+  let comments = SingleThreadedComments::default();
+  let lexer = Lexer::new(syntax, target, input, Some(&comments));
 
   let mut parser = Parser::new_from(lexer);
   let module = parser.parse_module();
@@ -39,8 +50,8 @@ pub fn parse_module(
     Err(err) => {
       recovered_errors.push(err);
     }
-    Ok(m) => {
-      return Ok(m);
+    Ok(ast) => {
+      return Ok(ParseScriptModuleResult { ast, comments });
     }
   }
 
@@ -86,15 +97,17 @@ pub fn parse_stmt(
 /// ast codegen, return generated utf8 bytes. using [String::from_utf8] if you want to transform the bytes to string.
 /// Example:
 /// ```ignore
-/// let bytes = codegen(swc_ast, cm);
+/// let bytes = codegen_module(swc_ast, target, cm, None, false, None);
 /// let code = String::from_utf8(bytes).unwrap();
 /// ```
+/// The comments are emitted if `comments` is [Some].
 pub fn codegen_module(
   ast: &SwcModule,
   target: EsVersion,
   cm: Arc<SourceMap>,
   src_map: Option<&mut Vec<(BytePos, LineCol)>>,
   minify: bool,
+  comments: Option<&dyn Comments>,
 ) -> std::result::Result<Vec<u8>, std::io::Error> {
   let mut buf = vec![];
 
@@ -109,8 +122,7 @@ pub fn codegen_module(
         minify,
         omit_last_semi: true,
       },
-      // TODO preserve comments
-      comments: None,
+      comments,
       cm,
       wr,
     };
@@ -181,11 +193,84 @@ pub fn is_commonjs_require(
   }
 }
 
+/// Whether the call expr is `require.resolve('xxx')` generated by the `require.context` expander, which is replaced by the module id of `xxx`.
+/// The generated `resolve` property is marked by the unresolved mark, the property names written by users are never marked.
+pub fn is_require_context_resolve(unresolved_mark: Mark, call_expr: &CallExpr) -> bool {
+  matches!(
+    &call_expr.callee,
+    Callee::Expr(box Expr::Member(MemberExpr {
+      obj: box Expr::Ident(Ident { span: obj_span, sym: obj, .. }),
+      prop: MemberProp::Ident(Ident { span: prop_span, sym: prop, .. }),
+      ..
+    })) if obj == "require"
+      && prop == "resolve"
+      && obj_span.ctxt.outer() == unresolved_mark
+      && prop_span.ctxt.outer() == unresolved_mark
+  )
+}
+
 /// Whether the call expr is dynamic import.
 pub fn is_dynamic_import(call_expr: &CallExpr) -> bool {
   matches!(&call_expr.callee, Callee::Import(Import { .. }))
 }
 
+/// Whether the new expr is an asset reference relative to the current module, e.g. `new URL('./a.png', import.meta.url)`.
+/// A new expr is an asset reference if:
+/// * callee is an identifier named `URL` and it is a global variable
+/// * the first argument is a string literal and it's not an absolute url like `https://xxx`, `data:xxx` or `/xxx`
+/// * the second argument is `import.meta.url`
+pub fn is_import_meta_url(unresolved_mark: Mark, new_expr: &NewExpr) -> bool {
+  let is_url = matches!(&*new_expr.callee, Expr::Ident(Ident { span, sym, .. }) if sym == "URL" && span.ctxt.outer() == unresolved_mark);
+
+  is_url
+    && matches!(
+      new_expr.args.as_deref(),
+      Some(
+        [ExprOrSpread {
+          spread: None,
+          expr: box Expr::Lit(Lit::Str(Str { value: source, .. })),
+        }, ExprOrSpread {
+          spread: None,
+          expr: box Expr::Member(MemberExpr {
+            obj: box Expr::MetaProp(MetaPropExpr {
+              kind: MetaPropKind::ImportMeta,
+              ..
+            }),
+            prop: MemberProp::Ident(Ident { sym: url, .. }),
+            ..
+          }),
+        }]
+      ) if url == "url"
+        && !source.contains("://")
+        && !source.starts_with("data:")
+        && !source.starts_with('/')
+    )
+}
+
+/// Whether the new expr creates a web worker, e.g. `new Worker(new URL('./worker.js', import.meta.url), { type: 'module' })`.
+/// A new expr creates a web worker if:
+/// * callee is an identifier named `Worker` or `SharedWorker` and it is a global variable
+/// * the first argument is an asset reference, see [is_import_meta_url]
+pub fn is_worker(unresolved_mark: Mark, new_expr: &NewExpr) -> bool {
+  let is_worker = matches!(&*new_expr.callee, Expr::Ident(Ident { span, sym, .. }) if (sym == "Worker" || sym == "SharedWorker") && span.ctxt.outer() == unresolved_mark);
+
+  is_worker
+    && matches!(
+      new_expr.args.as_deref(),
+      Some([ExprOrSpread { spread: None, expr: box Expr::New(url) }, ..]) if is_import_meta_url(unresolved_mark, url)
+    )
+}
+
+/// The placeholder of the url of the worker whose entry module is `id`, it's replaced by the url of the worker entry resource when all resources are generated.
+pub fn worker_url_placeholder(id: &str) -> String {
+  format!("__FARM_WORKER_URL__({})", id)
+}
+
+/// The placeholder of the url of the library chunk rendered from the resource pot `id`, it's replaced by the relative url of the chunk resource when all resources are generated.
+pub fn library_chunk_placeholder(id: &str) -> String {
+  format!("__FARM_LIBRARY_CHUNK__({})", id)
+}
+
 pub fn module_system_from_deps(deps: Vec<ResolveKind>) -> ModuleSystem {
   let mut module_system = ModuleSystem::Custom(String::from("unknown"));
 
@@ -193,6 +278,8 @@ pub fn module_system_from_deps(deps: Vec<ResolveKind>) -> ModuleSystem {
     if matches!(resolve_kind, ResolveKind::Import)
       || matches!(resolve_kind, ResolveKind::DynamicImport)
       || matches!(resolve_kind, ResolveKind::ExportFrom)
+      || matches!(resolve_kind, ResolveKind::ImportMetaUrl)
+      || resolve_kind.is_worker()
     {
       match module_system {
         ModuleSystem::EsModule => continue,
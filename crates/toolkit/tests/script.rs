@@ -3,7 +3,11 @@ use std::{path::PathBuf, sync::Arc};
 use farmfe_core::swc_common::{FilePathMapping, SourceMap};
 use farmfe_toolkit::{
   fs::read_file_utf8,
-  script::{codegen_module, module_type_from_id, parse_module, syntax_from_module_type},
+  script::{
+    codegen_module, module_type_from_id, parse_module, syntax_from_module_type,
+    ParseScriptModuleResult,
+  },
+  swc_ecma_parser::Syntax,
 };
 
 use farmfe_testing_helpers::fixture;
@@ -17,11 +21,13 @@ fn parse_and_codegen_module() {
     let module_type = module_type_from_id(&id).unwrap();
     let syntax = syntax_from_module_type(&module_type, Default::default()).unwrap();
     let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
-    let ast = parse_module(&id, &content, syntax, Default::default(), cm.clone()).unwrap();
+    let ast = parse_module(&id, &content, syntax, Default::default(), cm.clone())
+      .unwrap()
+      .ast;
 
     assert_eq!(ast.body.len(), 3);
 
-    let bytes = codegen_module(&ast, Default::default(), cm, None, false).unwrap();
+    let bytes = codegen_module(&ast, Default::default(), cm, None, false, None).unwrap();
 
     let code = String::from_utf8(bytes).unwrap();
     assert_eq!(
@@ -30,3 +36,29 @@ fn parse_and_codegen_module() {
     );
   });
 }
+
+#[test]
+fn parse_and_codegen_module_with_comments() {
+  let content = r#"/*! license */
+// leading comment
+const a = /*#__PURE__*/ create();
+export default a; // trailing comment
+"#;
+  let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+  let ParseScriptModuleResult { ast, comments } = parse_module(
+    "any",
+    content,
+    Syntax::Es(Default::default()),
+    Default::default(),
+    cm.clone(),
+  )
+  .unwrap();
+
+  let bytes = codegen_module(&ast, Default::default(), cm, None, false, Some(&comments)).unwrap();
+
+  let code = String::from_utf8(bytes).unwrap();
+  assert_eq!(
+    code,
+    "/*! license */ // leading comment\nconst a = /*#__PURE__*/ create();\nexport default a; // trailing comment\n"
+  );
+}
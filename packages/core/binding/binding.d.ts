@@ -78,6 +78,8 @@ export class Compiler {
   getProcessRecordsById(id: string): Array<ModuleRecord>
   getAnalyzeDepsRecordsById(id: string): Array<AnalyzeDepsRecord>
   getResourcePotRecordsById(id: string): Array<ResourcePotRecord>
+  /** the reasons why the modules are retained after tree shaking, serialized as json */
+  getTreeShakeReport(): string
 }
 export type FileWatcher = JsFileWatcher
 export class JsFileWatcher {
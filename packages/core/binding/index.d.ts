@@ -13,6 +13,7 @@ export type ResolveKind =
   | 'import'
   | 'dynamicImport'
   | 'require'
+  | 'importMetaUrl'
   | 'cssAtImport'
   | 'cssUrl'
   | 'scriptSrc'
@@ -112,7 +113,11 @@ export interface Config {
       publicPath?: string;
       assetsFilename?: string;
       targetEnv?: 'browser' | 'node';
-      format?: 'cjs' | 'esm';
+      format?: 'cjs' | 'esm' | 'iife' | 'umd';
+      library?: boolean;
+      name?: string;
+      globals?: Record<string, string>;
+      manifest?: boolean;
     };
     env?: Record<string, any>;
     envDir?: string;
@@ -152,6 +157,8 @@ export interface Config {
         | 'es2020'
         | 'es2021'
         | 'es2022';
+      // transform the syntax newer than `target` down to `target`, disabled by default
+      downlevel?: boolean;
       // config swc parser
       parser?: {
         esConfig?: {
@@ -179,6 +186,16 @@ export interface Config {
           noEarlyErrors: boolean;
         };
       };
+      // typescript transform options, the options not specified are read from compilerOptions of the nearest tsconfig.json
+      typescript?: {
+        experimentalDecorators?: boolean;
+        emitDecoratorMetadata?: boolean;
+        useDefineForClassFields?: boolean;
+        jsxFactory?: string;
+        jsxFragmentFactory?: string;
+        importsNotUsedAsValues?: 'remove' | 'preserve' | 'error';
+        verbatimModuleSyntax?: boolean;
+      };
       plugins: {
         name: string;
         options?: Record<string, any>;
@@ -210,10 +227,12 @@ export interface Config {
     lazyCompilation?: boolean;
     treeShaking?: boolean;
     minify?: boolean;
+    concatenateModules?: boolean;
     record?: boolean;
     presetEnv?:
       | boolean
       | {
+          // the modules matched by `exclude` and not matched by `include` are neither polyfilled nor transformed down to `script.target`
           include?: string[];
           exclude?: string[];
           // TODO using swc's config
@@ -195,4 +195,8 @@ export class Compiler {
   getResourcePotRecordsById(id: string) {
     return this._bindingCompiler.getResourcePotRecordsById(id);
   }
+
+  getTreeShakeReport() {
+    return this._bindingCompiler.getTreeShakeReport();
+  }
 }
@@ -29,7 +29,7 @@ import type {
 
 import { CompilationMode, loadEnv } from './env.js';
 import { __FARM_GLOBAL__ } from './_global.js';
-import { importFresh } from '../utils/share.js';
+import { arraify, importFresh } from '../utils/share.js';
 
 export * from './types.js';
 export const DEFAULT_CONFIG_NAMES = [
@@ -86,6 +86,10 @@ export async function normalizeUserCompilationConfig(
     envPrefix
   );
 
+  // the rust compiler loads the env files too to expose them as `import.meta.env`
+  config.envDir = normalizePath(path.resolve(resolvedEnvPath));
+  config.envPrefix = arraify(envPrefix ?? 'FARM_');
+
   config.env = {
     ...userEnv,
     NODE_ENV: process.env.NODE_ENV || mode
@@ -15,7 +15,11 @@ const compilationConfigSchema = z
         publicPath: z.string().optional(),
         assetsFilename: z.string().optional(),
         targetEnv: z.enum(['browser', 'node']).optional(),
-        format: z.enum(['cjs', 'esm']).optional()
+        format: z.enum(['cjs', 'esm', 'iife', 'umd']).optional(),
+        library: z.boolean().optional(),
+        name: z.string().optional(),
+        globals: z.record(z.string()).optional(),
+        manifest: z.boolean().optional()
       })
       .strict()
       .optional(),
@@ -79,6 +83,7 @@ const compilationConfigSchema = z
             'es2022'
           ])
           .optional(),
+        downlevel: z.boolean().optional(),
         parser: z
           .object({
             esConfig: z
@@ -106,6 +111,20 @@ const compilationConfigSchema = z
               .optional()
           })
           .optional(),
+        typescript: z
+          .object({
+            experimentalDecorators: z.boolean().optional(),
+            emitDecoratorMetadata: z.boolean().optional(),
+            useDefineForClassFields: z.boolean().optional(),
+            jsxFactory: z.string().optional(),
+            jsxFragmentFactory: z.string().optional(),
+            importsNotUsedAsValues: z
+              .enum(['remove', 'preserve', 'error'])
+              .optional(),
+            verbatimModuleSyntax: z.boolean().optional()
+          })
+          .strict()
+          .optional(),
         plugins: z.array(z.any()).optional()
       })
       .strict()
@@ -129,6 +148,7 @@ const compilationConfigSchema = z
     lazyCompilation: z.boolean().optional(),
     treeShaking: z.boolean().optional(),
     minify: z.boolean().optional(),
+    concatenateModules: z.boolean().optional(),
     record: z.boolean().optional(),
     presetEnv: z
       .union([
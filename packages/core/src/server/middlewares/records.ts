@@ -29,6 +29,10 @@ export function records(server: DevServer) {
       const id = ctx.query.id as string;
       ctx.body = compiler.getResourcePotRecordsById(id);
       await next();
+    } else if (ctx.path === '/_tree_shake_report') {
+      ctx.type = 'application/json';
+      ctx.body = compiler.getTreeShakeReport();
+      await next();
     } else {
       await next();
     }
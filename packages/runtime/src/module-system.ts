@@ -31,7 +31,7 @@ const INTERNAL_MODULE_MAP: Record<string, any> = {
 export type ModuleInitialization = (
   module: Module,
   exports: any,
-  __farm_require__: (moduleId: string) => any,
+  __farm_require__: (moduleId: string, isAsync?: boolean) => any,
   __farm_dynamic_require__: (moduleId: string) => any
 ) => void | Promise<void>;
 
@@ -58,8 +58,8 @@ export class ModuleSystem {
     this.pluginContainer = new FarmRuntimePluginContainer([]);
   }
 
-  // TODO require should be async as we support `top level await`, This feature requires Node 16 and higher
-  require(moduleId: string): any {
+  // require the module, if isAsync is true, the async exports of the module are returned, which is resolved after the async module(the module uses top level await or imports async modules) is initialized
+  require(moduleId: string, isAsync?: boolean): any {
     if (INTERNAL_MODULE_MAP[moduleId]) {
       return INTERNAL_MODULE_MAP[moduleId];
     }
@@ -74,7 +74,7 @@ export class ModuleSystem {
 
       // console.log(`[Farm] shouldSkip: ${shouldSkip} ${moduleId}`);
       if (!shouldSkip) {
-        return this.cache[moduleId].exports;
+        return this.getExports(this.cache[moduleId], isAsync);
       }
     }
 
@@ -96,27 +96,38 @@ export class ModuleSystem {
 
     this.cache[moduleId] = module;
     // initialize the new module
-    initializer(
+    const result = initializer(
       module,
       module.exports,
       this.require.bind(this),
       this.dynamicRequire.bind(this)
     );
-    // call the module initialized hook
-    this.pluginContainer.hookSerial('moduleInitialized', module);
+
+    if (result instanceof Promise) {
+      // the module is an async module, it's initialized after the returned promise is resolved
+      module.asyncExports = result.then(() => {
+        this.pluginContainer.hookSerial('moduleInitialized', module);
+        return module.exports;
+      });
+    } else {
+      // call the module initialized hook
+      this.pluginContainer.hookSerial('moduleInitialized', module);
+    }
     // return the exports of the module
+    return this.getExports(module, isAsync);
+  }
+
+  private getExports(module: Module, isAsync?: boolean): any {
+    if (isAsync && module.asyncExports) {
+      return module.asyncExports;
+    }
+
     return module.exports;
   }
 
   dynamicRequire(moduleId: string): Promise<any> {
     if (this.modules[moduleId]) {
-      const exports = this.require(moduleId);
-
-      if (exports.__farm_async) {
-        return exports.default;
-      } else {
-        return Promise.resolve(exports);
-      }
+      return this.requireAsync(moduleId);
     }
 
     const resources = this.dynamicModuleResourcesMap[moduleId];
@@ -130,13 +141,17 @@ export class ModuleSystem {
     // loading all required resources, and return the exports of the entry module
     return Promise.all(
       resources.map((resource) => this.resourceLoader.load(resource))
-    ).then(() => {
-      const result = this.require(moduleId);
+    ).then(() => this.requireAsync(moduleId));
+  }
+
+  // require the module and wait for it to be initialized if it's an async module
+  private requireAsync(moduleId: string): Promise<any> {
+    return Promise.resolve(this.require(moduleId, true)).then((exports) => {
       // if the module is async, return the default export, the default export should be a promise
-      if (result.__farm_async) {
-        return result.default;
+      if (exports.__farm_async) {
+        return exports.default;
       } else {
-        return result;
+        return exports;
       }
     });
   }
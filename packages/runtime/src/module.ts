@@ -3,6 +3,8 @@ export class Module {
   id: string;
   exports: any;
   meta: Record<string, any>;
+  // resolved with the exports after the async module is initialized, only exists for async modules
+  asyncExports?: Promise<any>;
 
   dispose?: () => void;
 